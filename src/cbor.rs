@@ -0,0 +1,670 @@
+// Copyright (c) Meta Platforms, Inc. and affiliates.
+//
+// This source code is dual-licensed under either the MIT license found in the
+// LICENSE-MIT file in the root directory of this source tree or the Apache
+// License, Version 2.0 found in the LICENSE-APACHE file in the root directory
+// of this source tree. You may select, at your option, one of the above-listed
+// licenses.
+
+//! CBOR ([RFC 8949](https://www.rfc-editor.org/rfc/rfc8949)) encodings of
+//! protocol messages and COSE_Key
+//! ([RFC 9052](https://www.rfc-editor.org/rfc/rfc9052#section-7)) encodings of
+//! public keys
+//!
+//! Messages are encoded as CBOR byte strings containing their regular
+//! serialization, batch responses as an array of the evaluation elements
+//! followed by the proof. All encodings are deterministic.
+
+use alloc::vec::Vec;
+
+use generic_array::typenum::Unsigned;
+
+use crate::ciphersuite::NistCurve;
+use crate::{
+    BlindedElement, CipherSuite, DeserializationReason, Error, EvaluationElement,
+    EvaluationElementLen, PoprfServerBatchEvaluateResult, Proof, ProtocolMode, PublicKey,
+    PublicKeyLen, Result, VoprfServerBatchEvaluateResult,
+};
+
+///////////////
+// Constants //
+// ========= //
+///////////////
+
+const MAJOR_UNSIGNED: u8 = 0;
+const MAJOR_NEGATIVE: u8 = 1;
+const MAJOR_BYTES: u8 = 2;
+const MAJOR_TEXT: u8 = 3;
+const MAJOR_ARRAY: u8 = 4;
+const MAJOR_MAP: u8 = 5;
+const MAJOR_TAG: u8 = 6;
+const MAJOR_SIMPLE: u8 = 7;
+
+const SIMPLE_FALSE: u64 = 20;
+const SIMPLE_TRUE: u64 = 21;
+
+// COSE_Key labels and values, see RFC 9052 and RFC 9053.
+const COSE_KTY: i64 = 1;
+const COSE_KID: i64 = 2;
+const COSE_ALG: i64 = 3;
+const COSE_CRV: i64 = -1;
+const COSE_X: i64 = -2;
+const COSE_Y: i64 = -3;
+const COSE_KTY_OKP: i64 = 1;
+const COSE_KTY_EC2: i64 = 2;
+
+/// Nesting depth up to which unknown COSE_Key parameters are skipped.
+const MAX_DEPTH: usize = 16;
+
+////////////////////////////
+// High-level API Structs //
+// ====================== //
+////////////////////////////
+
+impl<CS: CipherSuite, M: ProtocolMode> BlindedElement<CS, M> {
+    /// Encodes this message as a CBOR byte string.
+    pub fn to_cbor(&self) -> Vec<u8> {
+        let mut output = Vec::new();
+        write_bytes(&mut output, &self.serialize());
+        output
+    }
+
+    /// Decodes a message produced by [`to_cbor()`](Self::to_cbor).
+    ///
+    /// # Errors
+    /// [`Error::Deserialization`] if failed to decode `input`.
+    pub fn from_cbor(input: &[u8]) -> Result<Self> {
+        let mut reader = Reader::new(input, "blinded_element");
+        let value = Self::deserialize(reader.bytes()?)?;
+        reader.finish()?;
+
+        Ok(value)
+    }
+}
+
+impl<CS: CipherSuite, M: ProtocolMode> EvaluationElement<CS, M> {
+    /// Encodes this message as a CBOR byte string.
+    pub fn to_cbor(&self) -> Vec<u8> {
+        let mut output = Vec::new();
+        write_bytes(&mut output, &self.serialize());
+        output
+    }
+
+    /// Decodes a message produced by [`to_cbor()`](Self::to_cbor).
+    ///
+    /// # Errors
+    /// [`Error::Deserialization`] if failed to decode `input`.
+    pub fn from_cbor(input: &[u8]) -> Result<Self> {
+        let mut reader = Reader::new(input, "evaluation_element");
+        let value = Self::deserialize(reader.bytes()?)?;
+        reader.finish()?;
+
+        Ok(value)
+    }
+}
+
+impl<CS: CipherSuite> Proof<CS> {
+    /// Encodes this proof as a CBOR byte string.
+    pub fn to_cbor(&self) -> Vec<u8> {
+        let mut output = Vec::new();
+        write_bytes(&mut output, &self.serialize());
+        output
+    }
+
+    /// Decodes a proof produced by [`to_cbor()`](Self::to_cbor).
+    ///
+    /// # Errors
+    /// [`Error::Deserialization`] if failed to decode `input`.
+    pub fn from_cbor(input: &[u8]) -> Result<Self> {
+        let mut reader = Reader::new(input, "proof");
+        let value = Self::deserialize(reader.bytes()?)?;
+        reader.finish()?;
+
+        Ok(value)
+    }
+}
+
+impl<CS: CipherSuite> VoprfServerBatchEvaluateResult<CS> {
+    /// Encodes the messages and the proof as a CBOR array of the form
+    /// `[[* evaluation_element], proof]`.
+    pub fn to_cbor(&self) -> Vec<u8> {
+        batch_to_cbor(&self.messages, &self.proof)
+    }
+
+    /// Decodes a response produced by [`to_cbor()`](Self::to_cbor).
+    ///
+    /// # Errors
+    /// [`Error::Deserialization`] if failed to decode `input`.
+    pub fn from_cbor(input: &[u8]) -> Result<Self> {
+        let (messages, proof) = batch_from_cbor(input)?;
+
+        Ok(Self { messages, proof })
+    }
+}
+
+impl<CS: CipherSuite> PoprfServerBatchEvaluateResult<CS> {
+    /// Encodes the messages and the proof as a CBOR array of the form
+    /// `[[* evaluation_element], proof]`.
+    pub fn to_cbor(&self) -> Vec<u8> {
+        batch_to_cbor(&self.messages, &self.proof)
+    }
+
+    /// Decodes a response produced by [`to_cbor()`](Self::to_cbor).
+    ///
+    /// # Errors
+    /// [`Error::Deserialization`] if failed to decode `input`.
+    pub fn from_cbor(input: &[u8]) -> Result<Self> {
+        let (messages, proof) = batch_from_cbor(input)?;
+
+        Ok(Self { messages, proof })
+    }
+}
+
+impl<CS: CipherSuite> PublicKey<CS> {
+    /// Encodes this key as a COSE_Key.
+    ///
+    /// Keys of the NIST curves are encoded with key type `EC2`, their
+    /// registered curve and the sign of the y-coordinate as `y`. Other keys are
+    /// encoded with key type `OKP` and [`CipherSuite::ID`] as curve. In both
+    /// cases the algorithm is [`CipherSuite::ID`] and the key ID is the
+    /// [fingerprint](Self::fingerprint).
+    pub fn to_cose_key(&self) -> Vec<u8> {
+        let pk = self.serialize();
+        let curve = NistCurve::of::<CS>().map(NistCurve::cose_id);
+        let mut output = Vec::new();
+
+        write_head(&mut output, MAJOR_MAP, if curve.is_some() { 6 } else { 5 });
+        write_int(&mut output, COSE_KTY);
+        write_int(
+            &mut output,
+            if curve.is_some() {
+                COSE_KTY_EC2
+            } else {
+                COSE_KTY_OKP
+            },
+        );
+        write_int(&mut output, COSE_KID);
+        write_bytes(&mut output, &self.fingerprint());
+        write_int(&mut output, COSE_ALG);
+        write_text(&mut output, CS::ID);
+        write_int(&mut output, COSE_CRV);
+
+        if let Some(curve) = curve {
+            // Compressed SEC1 encoding: the sign of y followed by x.
+            write_int(&mut output, curve);
+            write_int(&mut output, COSE_X);
+            write_bytes(&mut output, &pk[1..]);
+            write_int(&mut output, COSE_Y);
+            write_head(
+                &mut output,
+                MAJOR_SIMPLE,
+                if pk[0] == 0x03 {
+                    SIMPLE_TRUE
+                } else {
+                    SIMPLE_FALSE
+                },
+            );
+        } else {
+            write_text(&mut output, CS::ID);
+            write_int(&mut output, COSE_X);
+            write_bytes(&mut output, &pk);
+        }
+
+        output
+    }
+
+    /// Decodes a key produced by [`to_cose_key()`](Self::to_cose_key).
+    /// Unknown parameters are ignored.
+    ///
+    /// # Errors
+    /// [`Error::Deserialization`] if failed to decode `input`, or if the key
+    /// type, curve or algorithm doesn't match the [`CipherSuite`].
+    pub fn from_cose_key(input: &[u8]) -> Result<Self> {
+        let mut reader = Reader::new(input, "cose_key");
+        let curve = NistCurve::of::<CS>().map(NistCurve::cose_id);
+        let mut kty = None;
+        let mut alg = None;
+        let mut crv = None;
+        let mut x = None;
+        let mut y = None;
+
+        for _ in 0..reader.map()? {
+            match reader.int()? {
+                COSE_KTY => kty = Some(reader.int()?),
+                COSE_ALG => alg = Some(reader.text()?),
+                COSE_CRV => {
+                    crv = Some(match curve {
+                        Some(curve) => reader.int()? == curve,
+                        None => reader.text()? == CS::ID,
+                    })
+                }
+                COSE_X => x = Some(reader.bytes()?),
+                COSE_Y => y = Some(reader.bool()?),
+                _ => reader.skip(0)?,
+            }
+        }
+
+        reader.finish()?;
+
+        if alg.is_some_and(|alg| alg != CS::ID) {
+            return Err(Error::deserialization(
+                "cose_key",
+                DeserializationReason::Suite,
+            ));
+        }
+
+        let x = x.ok_or(Error::deserialization(
+            "cose_key",
+            DeserializationReason::Encoding,
+        ))?;
+
+        match (curve, kty, crv, y) {
+            (Some(_), Some(COSE_KTY_EC2), Some(true), Some(y)) => {
+                let mut pk = Vec::with_capacity(PublicKeyLen::<CS>::USIZE);
+                pk.push(if y { 0x03 } else { 0x02 });
+                pk.extend_from_slice(x);
+
+                Self::deserialize(&pk)
+            }
+            (None, Some(COSE_KTY_OKP), Some(true), None) => Self::deserialize(x),
+            _ => Err(Error::deserialization(
+                "cose_key",
+                DeserializationReason::Suite,
+            )),
+        }
+    }
+}
+
+/////////////////////
+// Inner functions //
+// =============== //
+/////////////////////
+
+fn batch_to_cbor<CS: CipherSuite, M: ProtocolMode>(
+    messages: &[EvaluationElement<CS, M>],
+    proof: &Proof<CS>,
+) -> Vec<u8> {
+    let mut output = Vec::new();
+
+    write_head(&mut output, MAJOR_ARRAY, 2);
+    write_head(&mut output, MAJOR_ARRAY, messages.len() as u64);
+
+    for message in messages {
+        write_bytes(&mut output, &message.serialize());
+    }
+
+    write_bytes(&mut output, &proof.serialize());
+
+    output
+}
+
+type BatchFromCborResult<CS, M> = (Vec<EvaluationElement<CS, M>>, Proof<CS>);
+
+fn batch_from_cbor<CS: CipherSuite, M: ProtocolMode>(
+    input: &[u8],
+) -> Result<BatchFromCborResult<CS, M>> {
+    let mut reader = Reader::new(input, "batch_response");
+
+    if reader.array()? != 2 {
+        return Err(reader.error());
+    }
+
+    let len = reader.array()?;
+    // Don't trust the length for the allocation, every message needs at least
+    // its size in bytes.
+    let mut messages = Vec::with_capacity(len.min(input.len() / EvaluationElementLen::<CS>::USIZE));
+
+    for _ in 0..len {
+        messages.push(EvaluationElement::deserialize(reader.bytes()?)?);
+    }
+
+    let proof = Proof::deserialize(reader.bytes()?)?;
+    reader.finish()?;
+
+    Ok((messages, proof))
+}
+
+fn write_head(output: &mut Vec<u8>, major: u8, value: u64) {
+    let major = major << 5;
+
+    if value < 24 {
+        output.push(major | value as u8);
+    } else if let Ok(value) = u8::try_from(value) {
+        output.push(major | 24);
+        output.push(value);
+    } else if let Ok(value) = u16::try_from(value) {
+        output.push(major | 25);
+        output.extend_from_slice(&value.to_be_bytes());
+    } else if let Ok(value) = u32::try_from(value) {
+        output.push(major | 26);
+        output.extend_from_slice(&value.to_be_bytes());
+    } else {
+        output.push(major | 27);
+        output.extend_from_slice(&value.to_be_bytes());
+    }
+}
+
+fn write_int(output: &mut Vec<u8>, value: i64) {
+    if value < 0 {
+        write_head(output, MAJOR_NEGATIVE, !value as u64);
+    } else {
+        write_head(output, MAJOR_UNSIGNED, value as u64);
+    }
+}
+
+fn write_bytes(output: &mut Vec<u8>, bytes: &[u8]) {
+    write_head(output, MAJOR_BYTES, bytes.len() as u64);
+    output.extend_from_slice(bytes);
+}
+
+fn write_text(output: &mut Vec<u8>, text: &str) {
+    write_head(output, MAJOR_TEXT, text.len() as u64);
+    output.extend_from_slice(text.as_bytes());
+}
+
+/// Decodes the subset of CBOR used by this module. Indefinite-length items
+/// are rejected.
+struct Reader<'a> {
+    input: &'a [u8],
+    field: &'static str,
+}
+
+impl<'a> Reader<'a> {
+    fn new(input: &'a [u8], field: &'static str) -> Self {
+        Self { input, field }
+    }
+
+    fn error(&self) -> Error {
+        Error::deserialization(self.field, DeserializationReason::Encoding)
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8]> {
+        if len > self.input.len() {
+            return Err(self.error());
+        }
+
+        let (front, back) = self.input.split_at(len);
+        self.input = back;
+        Ok(front)
+    }
+
+    fn head(&mut self) -> Result<(u8, u64)> {
+        let initial = self.take(1)?[0];
+        let major = initial >> 5;
+        let value = match initial & 0x1f {
+            info @ 0..24 => info.into(),
+            24 => self.take(1)?[0].into(),
+            25 => u16::from_be_bytes(self.take(2)?.try_into().unwrap()).into(),
+            26 => u32::from_be_bytes(self.take(4)?.try_into().unwrap()).into(),
+            27 => u64::from_be_bytes(self.take(8)?.try_into().unwrap()),
+            _ => return Err(self.error()),
+        };
+
+        Ok((major, value))
+    }
+
+    fn expect(&mut self, expected: u8) -> Result<usize> {
+        match self.head()? {
+            (major, value) if major == expected => usize::try_from(value).map_err(|_| self.error()),
+            _ => Err(self.error()),
+        }
+    }
+
+    fn bytes(&mut self) -> Result<&'a [u8]> {
+        let len = self.expect(MAJOR_BYTES)?;
+        self.take(len)
+    }
+
+    fn text(&mut self) -> Result<&'a str> {
+        let len = self.expect(MAJOR_TEXT)?;
+        let text = self.take(len)?;
+        core::str::from_utf8(text).map_err(|_| self.error())
+    }
+
+    fn array(&mut self) -> Result<usize> {
+        self.expect(MAJOR_ARRAY)
+    }
+
+    fn map(&mut self) -> Result<usize> {
+        self.expect(MAJOR_MAP)
+    }
+
+    fn int(&mut self) -> Result<i64> {
+        match self.head()? {
+            (MAJOR_UNSIGNED, value) => i64::try_from(value).map_err(|_| self.error()),
+            (MAJOR_NEGATIVE, value) => i64::try_from(value)
+                .map(|value| !value)
+                .map_err(|_| self.error()),
+            _ => Err(self.error()),
+        }
+    }
+
+    fn bool(&mut self) -> Result<bool> {
+        match self.head()? {
+            (MAJOR_SIMPLE, SIMPLE_FALSE) => Ok(false),
+            (MAJOR_SIMPLE, SIMPLE_TRUE) => Ok(true),
+            _ => Err(self.error()),
+        }
+    }
+
+    /// Skips the next item.
+    fn skip(&mut self, depth: usize) -> Result<()> {
+        if depth >= MAX_DEPTH {
+            return Err(self.error());
+        }
+
+        match self.head()? {
+            (MAJOR_UNSIGNED | MAJOR_NEGATIVE | MAJOR_SIMPLE, _) => Ok(()),
+            (MAJOR_BYTES | MAJOR_TEXT, len) => {
+                let len = usize::try_from(len).map_err(|_| self.error())?;
+                self.take(len).map(|_| ())
+            }
+            (MAJOR_ARRAY, len) => (0..len).try_for_each(|_| self.skip(depth + 1)),
+            (MAJOR_MAP, len) => (0..len).try_for_each(|_| {
+                self.skip(depth + 1)?;
+                self.skip(depth + 1)
+            }),
+            (MAJOR_TAG, _) => self.skip(depth + 1),
+            _ => Err(self.error()),
+        }
+    }
+
+    fn finish(&self) -> Result<()> {
+        if self.input.is_empty() {
+            Ok(())
+        } else {
+            Err(self.error())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ::alloc::vec;
+    use ::alloc::vec::Vec;
+    use core::fmt::Debug;
+
+    use rand::rngs::OsRng;
+
+    use super::*;
+    use crate::{Group, PoprfClient, PoprfServer, VoprfClient, VoprfMode, VoprfServer};
+
+    fn messages<CS: CipherSuite>() {
+        let mut rng = OsRng;
+        let inputs = [b"input 1", b"input 2", b"input 3"];
+        let server = VoprfServer::<CS>::new(&mut rng).unwrap();
+        let client_blind_results: Vec<_> = inputs
+            .iter()
+            .map(|input| VoprfClient::<CS>::blind(*input, &mut rng).unwrap())
+            .collect();
+        let blinded_elements: Vec<_> = client_blind_results
+            .iter()
+            .map(|result| result.message.clone())
+            .collect();
+
+        let message = &blinded_elements[0];
+        let cbor = message.to_cbor();
+        // A byte string with a one byte length prefix.
+        assert_eq!(cbor[..2], [0x58, message.serialize().len() as u8]);
+        assert_eq!(cbor[2..], message.serialize()[..]);
+        let decoded = BlindedElement::<CS, VoprfMode>::from_cbor(&cbor).unwrap();
+        assert_eq!(decoded.serialize(), message.serialize());
+
+        let result = server
+            .batch_blind_evaluate(&mut rng, &blinded_elements)
+            .unwrap();
+        let message = &result.messages[0];
+        let decoded = EvaluationElement::<CS, VoprfMode>::from_cbor(&message.to_cbor()).unwrap();
+        assert_eq!(decoded.serialize(), message.serialize());
+        let decoded = Proof::<CS>::from_cbor(&result.proof.to_cbor()).unwrap();
+        assert_eq!(decoded.serialize(), result.proof.serialize());
+
+        let decoded = VoprfServerBatchEvaluateResult::<CS>::from_cbor(&result.to_cbor()).unwrap();
+        let clients: Vec<_> = client_blind_results
+            .iter()
+            .map(|result| result.state.clone())
+            .collect();
+        let outputs: Vec<_> = VoprfClient::batch_finalize(
+            &inputs,
+            &clients,
+            &decoded.messages,
+            &decoded.proof,
+            &server.get_public_key(),
+        )
+        .unwrap()
+        .collect::<core::result::Result<_, _>>()
+        .unwrap();
+        assert_eq!(outputs.len(), inputs.len());
+
+        let server = PoprfServer::<CS>::new(&mut rng).unwrap();
+        let blinded_elements: Vec<_> = inputs
+            .iter()
+            .map(|input| PoprfClient::<CS>::blind(*input, &mut rng).unwrap().message)
+            .collect();
+        let result = server
+            .batch_blind_evaluate(&mut rng, &blinded_elements, Some(b"info"))
+            .unwrap();
+        let decoded = PoprfServerBatchEvaluateResult::<CS>::from_cbor(&result.to_cbor()).unwrap();
+        assert!(decoded
+            .messages
+            .iter()
+            .zip(&result.messages)
+            .all(|(a, b)| a.serialize() == b.serialize()));
+        assert_eq!(decoded.proof.serialize(), result.proof.serialize());
+    }
+
+    fn cbor_errors<CS: CipherSuite>() {
+        let message = VoprfClient::<CS>::blind(b"input", &mut OsRng)
+            .unwrap()
+            .message;
+        let cbor = message.to_cbor();
+
+        let mut bad = cbor.clone();
+        bad.push(0);
+        assert!(BlindedElement::<CS, VoprfMode>::from_cbor(&bad).is_err());
+        assert!(BlindedElement::<CS, VoprfMode>::from_cbor(&cbor[..cbor.len() - 1]).is_err());
+
+        // Indefinite-length byte string.
+        let mut bad = vec![0x5f];
+        bad.extend_from_slice(&cbor);
+        bad.push(0xff);
+        assert!(BlindedElement::<CS, VoprfMode>::from_cbor(&bad).is_err());
+
+        // Text string instead of a byte string.
+        let mut bad = cbor;
+        bad[0] = (bad[0] & 0x1f) | (MAJOR_TEXT << 5);
+        assert!(BlindedElement::<CS, VoprfMode>::from_cbor(&bad).is_err());
+
+        // Array with more than two items.
+        assert!(
+            VoprfServerBatchEvaluateResult::<CS>::from_cbor(&[0x83, 0x80, 0x40, 0x40]).is_err()
+        );
+    }
+
+    fn cose_key<CS: CipherSuite>()
+    where
+        <CS::Group as Group>::Elem: Debug,
+    {
+        let server = VoprfServer::<CS>::new(&mut OsRng).unwrap();
+        let pk = server.get_public_key();
+
+        let cose_key = pk.to_cose_key();
+        assert_eq!(PublicKey::<CS>::from_cose_key(&cose_key).unwrap(), pk);
+
+        // Unknown parameters are skipped: `key_ops` (4) with `[verify]` and a
+        // nested tagged map.
+        let mut extended = cose_key.clone();
+        extended[0] += 2;
+        extended.extend_from_slice(&[0x04, 0x81, 0x02]);
+        extended.extend_from_slice(&[0x20 | 19, 0xc1, 0xa1, 0x01, 0x62, b'a', b'b']);
+        assert_eq!(PublicKey::<CS>::from_cose_key(&extended).unwrap(), pk);
+
+        let mut bad = cose_key.clone();
+        bad.push(0);
+        assert!(PublicKey::<CS>::from_cose_key(&bad).is_err());
+
+        // Missing `x`.
+        let mut bad = Vec::new();
+        write_head(&mut bad, MAJOR_MAP, 1);
+        write_int(&mut bad, COSE_ALG);
+        write_text(&mut bad, CS::ID);
+        assert!(PublicKey::<CS>::from_cose_key(&bad).is_err());
+    }
+
+    #[test]
+    fn test_functionality() {
+        use p256::NistP256;
+        use p384::NistP384;
+        use p521::NistP521;
+
+        #[cfg(feature = "ristretto255")]
+        {
+            use crate::Ristretto255;
+
+            messages::<Ristretto255>();
+            cbor_errors::<Ristretto255>();
+            cose_key::<Ristretto255>();
+        }
+
+        messages::<NistP256>();
+        cbor_errors::<NistP256>();
+        cose_key::<NistP256>();
+
+        messages::<NistP384>();
+        cbor_errors::<NistP384>();
+        cose_key::<NistP384>();
+
+        messages::<NistP521>();
+        cbor_errors::<NistP521>();
+        cose_key::<NistP521>();
+    }
+
+    #[test]
+    fn test_cose_key_p256() {
+        use p256::NistP256;
+        use p384::NistP384;
+
+        let pk = VoprfServer::<NistP256>::new(&mut OsRng)
+            .unwrap()
+            .get_public_key();
+        let cose_key = pk.to_cose_key();
+
+        // {1: 2, 2: h'…', 3: "P256-SHA256", -1: 1, -2: h'…', -3: bool}
+        assert_eq!(cose_key[..5], [0xa6, 0x01, 0x02, 0x02, 0x58]);
+        assert!(cose_key.ends_with(&[0x22, 0xf4]) || cose_key.ends_with(&[0x22, 0xf5]));
+
+        assert_eq!(
+            PublicKey::<NistP384>::from_cose_key(&cose_key).unwrap_err(),
+            Error::deserialization("cose_key", DeserializationReason::Suite)
+        );
+
+        #[cfg(feature = "ristretto255")]
+        {
+            let cose_key = VoprfServer::<crate::Ristretto255>::new(&mut OsRng)
+                .unwrap()
+                .get_public_key()
+                .to_cose_key();
+            assert!(PublicKey::<NistP256>::from_cose_key(&cose_key).is_err());
+        }
+    }
+}
@@ -48,3 +48,60 @@ where
 
     type Hash = T::Hash;
 }
+
+/// The NIST curves of the RFC 9497 suites, which have registered identifiers
+/// in key formats.
+#[cfg(any(feature = "cbor", feature = "jwk", feature = "pkcs8"))]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum NistCurve {
+    P256,
+    P384,
+    P521,
+}
+
+#[cfg(any(feature = "cbor", feature = "jwk", feature = "pkcs8"))]
+impl NistCurve {
+    /// The NIST curve of `CS`, if any.
+    pub(crate) fn of<CS: CipherSuite>() -> Option<Self> {
+        match CS::ID {
+            "P256-SHA256" => Some(Self::P256),
+            "P384-SHA384" => Some(Self::P384),
+            "P521-SHA512" => Some(Self::P521),
+            _ => None,
+        }
+    }
+
+    /// The COSE Elliptic Curves identifier, see RFC 9053.
+    #[cfg(feature = "cbor")]
+    pub(crate) fn cose_id(self) -> i64 {
+        match self {
+            Self::P256 => 1,
+            Self::P384 => 2,
+            Self::P521 => 3,
+        }
+    }
+
+    /// The JWK `crv` parameter, see RFC 7518.
+    #[cfg(feature = "jwk")]
+    pub(crate) fn jwk_name(self) -> &'static str {
+        match self {
+            Self::P256 => "P-256",
+            Self::P384 => "P-384",
+            Self::P521 => "P-521",
+        }
+    }
+
+    /// The DER encoded contents of the named curve object identifier, see
+    /// RFC 5480.
+    #[cfg(feature = "pkcs8")]
+    pub(crate) fn oid(self) -> &'static [u8] {
+        match self {
+            // 1.2.840.10045.3.1.7
+            Self::P256 => &[0x2a, 0x86, 0x48, 0xce, 0x3d, 0x03, 0x01, 0x07],
+            // 1.3.132.0.34
+            Self::P384 => &[0x2b, 0x81, 0x04, 0x00, 0x22],
+            // 1.3.132.0.35
+            Self::P521 => &[0x2b, 0x81, 0x04, 0x00, 0x23],
+        }
+    }
+}
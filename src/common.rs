@@ -8,20 +8,36 @@
 
 //! Common functionality between multiple OPRF modes.
 
+#[cfg(feature = "alloc")]
+use alloc::vec;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
 use core::convert::TryFrom;
+use core::fmt::{self, Debug, Formatter};
+#[cfg(feature = "alloc")]
+use core::iter::Enumerate;
+use core::marker::PhantomData;
 use core::ops::Add;
+#[cfg(feature = "alloc")]
+use core::slice;
 
 use derive_where::derive_where;
+use digest::core_api::{Block, BlockSizeUser};
 use digest::{Digest, Output, OutputSizeUser};
 use generic_array::sequence::Concat;
 use generic_array::typenum::{IsLess, Unsigned, U2, U256, U9};
 use generic_array::{ArrayLength, GenericArray};
 use rand_core::{TryCryptoRng, TryRngCore};
-use subtle::ConstantTimeEq;
+use subtle::{Choice, ConstantTimeEq};
+use zeroize::Zeroize;
+#[cfg(feature = "alloc")]
+use zeroize::Zeroizing;
 
 #[cfg(feature = "serde")]
-use crate::serialization::serde::{Element, Scalar};
-use crate::{CipherSuite, Error, Group, InternalError, Result};
+use crate::serialization::serde::{Element, Hash, Scalar};
+use crate::{
+    BatchError, CipherSuite, Error, Group, InputError, InternalError, ProtocolError, Result,
+};
 
 ///////////////
 // Constants //
@@ -33,14 +49,20 @@ pub(crate) const STR_SEED: [u8; 5] = *b"Seed-";
 pub(crate) const STR_DERIVE_KEYPAIR: [u8; 13] = *b"DeriveKeyPair";
 pub(crate) const STR_COMPOSITE: [u8; 9] = *b"Composite";
 pub(crate) const STR_CHALLENGE: [u8; 9] = *b"Challenge";
+pub(crate) const STR_HEDGED_NONCE: [u8; 11] = *b"HedgedNonce";
 pub(crate) const STR_INFO: [u8; 4] = *b"Info";
 pub(crate) const STR_OPRF: [u8; 7] = *b"OPRFV1-";
 pub(crate) const STR_HASH_TO_SCALAR: [u8; 13] = *b"HashToScalar-";
 pub(crate) const STR_HASH_TO_GROUP: [u8; 12] = *b"HashToGroup-";
+pub(crate) const STR_FINGERPRINT: [u8; 12] = *b"Fingerprint-";
+
+/// Maximum number of elements a single proof can cover, limited by the 2-byte
+/// index used in `ComputeComposites`.
+pub const MAX_BATCH_SIZE: usize = u16::MAX as usize;
 
 /// Determines the mode of operation (either base mode or verifiable mode). This
 /// is only used for custom implementations for [`Group`].
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
 pub enum Mode {
     /// Non-verifiable mode.
     Oprf,
@@ -59,8 +81,65 @@ impl Mode {
             Mode::Poprf => 2,
         }
     }
+    /// Inverse of [`Mode::to_u8()`].
+    #[cfg(feature = "alloc")]
+    pub(crate) fn from_u8(mode: u8) -> Option<Self> {
+        match mode {
+            0 => Some(Mode::Oprf),
+            1 => Some(Mode::Voprf),
+            2 => Some(Mode::Poprf),
+            _ => None,
+        }
+    }
+}
+
+/// Type-level [`Mode`] of [`BlindedElement`]s and [`EvaluationElement`]s,
+/// implemented by [`OprfMode`], [`VoprfMode`] and [`PoprfMode`]. This trait is
+/// sealed.
+pub trait ProtocolMode: private::Sealed + 'static {
+    /// The corresponding [`Mode`].
+    const MODE: Mode;
+}
+
+/// Type-level [`Mode::Oprf`], see [`ProtocolMode`].
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum OprfMode {}
+
+/// Type-level [`Mode::Voprf`], see [`ProtocolMode`].
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum VoprfMode {}
+
+/// Type-level [`Mode::Poprf`], see [`ProtocolMode`].
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum PoprfMode {}
+
+impl ProtocolMode for OprfMode {
+    const MODE: Mode = Mode::Oprf;
+}
+
+impl ProtocolMode for VoprfMode {
+    const MODE: Mode = Mode::Voprf;
+}
+
+impl ProtocolMode for PoprfMode {
+    const MODE: Mode = Mode::Poprf;
+}
+
+mod private {
+    pub trait Sealed {}
+
+    impl Sealed for super::OprfMode {}
+    impl Sealed for super::VoprfMode {}
+    impl Sealed for super::PoprfMode {}
 }
 
+/// Exposes the secrets of the wrapped value in its [`Debug`] output, e.g. the
+/// private key of a server, which are otherwise redacted. Returned by
+/// `insecure_debug()` and only meant for debugging.
+#[cfg(feature = "danger")]
+#[derive(Clone, Copy)]
+pub struct InsecureDebug<'a, T>(pub(crate) &'a T);
+
 ////////////////////////////
 // High-level API Structs //
 // ====================== //
@@ -68,6 +147,25 @@ impl Mode {
 
 /// The first client message sent from a client (either verifiable or not) to a
 /// server (either verifiable or not).
+///
+/// The [`ProtocolMode`] `M` ties the message to the mode of the client that
+/// produced it, so it can't be passed to a server of a different mode.
+///
+/// ```compile_fail
+/// # #[cfg(feature = "ristretto255")]
+/// # type CipherSuite = voprf::Ristretto255;
+/// # #[cfg(not(feature = "ristretto255"))]
+/// # type CipherSuite = p256::NistP256;
+/// use rand::rngs::OsRng;
+/// use voprf::{OprfClient, PoprfServer};
+///
+/// let client_blind_result = OprfClient::<CipherSuite>::blind(b"input", &mut OsRng).unwrap();
+/// let server = PoprfServer::<CipherSuite>::new(&mut OsRng).unwrap();
+/// // An `OprfClient` message can't be evaluated by a `PoprfServer`.
+/// server
+///     .blind_evaluate(&mut OsRng, &client_blind_result.message, None)
+///     .unwrap();
+/// ```
 #[derive_where(Clone, ZeroizeOnDrop)]
 #[derive_where(Debug, Eq, Hash, Ord, PartialEq, PartialOrd; <CS::Group as Group>::Elem)]
 #[cfg_attr(
@@ -75,13 +173,17 @@ impl Mode {
     derive(serde::Deserialize, serde::Serialize),
     serde(bound = "")
 )]
-pub struct BlindedElement<CS: CipherSuite>(
+pub struct BlindedElement<CS: CipherSuite, M: ProtocolMode>(
     #[cfg_attr(feature = "serde", serde(with = "Element::<CS::Group>"))]
     pub(crate)  <CS::Group as Group>::Elem,
+    #[cfg_attr(feature = "serde", serde(skip))] pub(crate) PhantomData<M>,
 );
 
 /// The server's response to the [BlindedElement] message from a client (either
 /// verifiable or not) to a server (either verifiable or not).
+///
+/// The [`ProtocolMode`] `M` ties the message to the mode of the server that
+/// produced it, so it can't be passed to a client of a different mode.
 #[derive_where(Clone, ZeroizeOnDrop)]
 #[derive_where(Debug, Eq, Hash, Ord, PartialEq, PartialOrd; <CS::Group as Group>::Elem)]
 #[cfg_attr(
@@ -89,9 +191,10 @@ pub struct BlindedElement<CS: CipherSuite>(
     derive(serde::Deserialize, serde::Serialize),
     serde(bound = "")
 )]
-pub struct EvaluationElement<CS: CipherSuite>(
+pub struct EvaluationElement<CS: CipherSuite, M: ProtocolMode>(
     #[cfg_attr(feature = "serde", serde(with = "Element::<CS::Group>"))]
     pub(crate)  <CS::Group as Group>::Elem,
+    #[cfg_attr(feature = "serde", serde(skip))] pub(crate) PhantomData<M>,
 );
 
 /// Contains prepared [`EvaluationElement`]s by a server batch evaluate
@@ -103,7 +206,9 @@ pub struct EvaluationElement<CS: CipherSuite>(
     derive(serde::Deserialize, serde::Serialize),
     serde(bound = "")
 )]
-pub struct PreparedEvaluationElement<CS: CipherSuite>(pub(crate) EvaluationElement<CS>);
+pub struct PreparedEvaluationElement<CS: CipherSuite, M: ProtocolMode>(
+    pub(crate) EvaluationElement<CS, M>,
+);
 
 /// A proof produced by a server that the OPRF output matches against a server
 /// public key.
@@ -121,15 +226,312 @@ pub struct Proof<CS: CipherSuite> {
     pub(crate) s_scalar: <CS::Group as Group>::Scalar,
 }
 
+/// A server public key, against which the outputs of the verifiable modes are
+/// checked. Returned by `get_public_key()` of the verifiable servers.
+#[derive_where(Clone, ZeroizeOnDrop)]
+#[derive_where(Debug; <CS::Group as Group>::Elem)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize, serde::Serialize),
+    serde(bound = "")
+)]
+pub struct PublicKey<CS: CipherSuite>(
+    #[cfg_attr(feature = "serde", serde(with = "Element::<CS::Group>"))]
+    pub(crate)  <CS::Group as Group>::Elem,
+);
+
+impl<CS: CipherSuite> PublicKey<CS> {
+    /// Returns a fingerprint of this key, binding it to the [`CipherSuite`].
+    /// Useful to identify a key without comparing the whole key.
+    pub fn fingerprint(&self) -> Output<CS::Hash> {
+        CS::Hash::new()
+            .chain_update(STR_FINGERPRINT)
+            .chain_update(CS::ID)
+            .chain_update(CS::Group::serialize_elem(self.0))
+            .finalize()
+    }
+}
+
+impl<CS: CipherSuite> ConstantTimeEq for PublicKey<CS> {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.0.ct_eq(&other.0)
+    }
+}
+
+impl<CS: CipherSuite> PartialEq for PublicKey<CS> {
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
+impl<CS: CipherSuite> Eq for PublicKey<CS> {}
+
+/// A server private key. Returned by `get_private_key()` of the servers with the
+/// `danger` feature.
+#[derive_where(Clone, ZeroizeOnDrop)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize, serde::Serialize),
+    serde(bound = "")
+)]
+pub struct PrivateKey<CS: CipherSuite>(
+    #[cfg_attr(feature = "serde", serde(with = "Scalar::<CS::Group>"))]
+    pub(crate)  <CS::Group as Group>::Scalar,
+);
+
+impl<CS: CipherSuite> PrivateKey<CS> {
+    /// Returns the [`PublicKey`] belonging to this key.
+    pub fn public_key(&self) -> PublicKey<CS> {
+        PublicKey(CS::Group::base_elem() * &self.0)
+    }
+
+    /// Returns the fingerprint of the [`PublicKey`] belonging to this key, see
+    /// [`PublicKey::fingerprint`].
+    pub fn fingerprint(&self) -> Output<CS::Hash> {
+        self.public_key().fingerprint()
+    }
+
+    /// Exposes the key in the [`Debug`] output, which is otherwise redacted.
+    #[cfg(feature = "danger")]
+    pub fn insecure_debug(&self) -> InsecureDebug<'_, Self> {
+        InsecureDebug(self)
+    }
+}
+
+impl<CS: CipherSuite> Debug for PrivateKey<CS> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PrivateKey")
+            .field("suite", &CS::ID)
+            .field("fingerprint", &Hex(&self.fingerprint()))
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "danger")]
+impl<CS: CipherSuite> Debug for InsecureDebug<'_, PrivateKey<CS>>
+where
+    <CS::Group as Group>::Scalar: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("PrivateKey").field(&self.0 .0).finish()
+    }
+}
+
+impl<CS: CipherSuite> ConstantTimeEq for PrivateKey<CS> {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.0.ct_eq(&other.0)
+    }
+}
+
+impl<CS: CipherSuite> PartialEq for PrivateKey<CS> {
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
+impl<CS: CipherSuite> Eq for PrivateKey<CS> {}
+
+/// The output of an OPRF evaluation, returned by `finalize()` of the clients
+/// and `evaluate()` of the servers. Compares in constant-time.
+#[derive_where(Clone, ZeroizeOnDrop)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize, serde::Serialize),
+    serde(bound = "")
+)]
+pub struct OprfOutput<CS: CipherSuite>(
+    #[cfg_attr(feature = "serde", serde(with = "Hash::<CS::Hash>"))] pub(crate) Output<CS::Hash>,
+);
+
+impl<CS: CipherSuite> OprfOutput<CS> {
+    /// Derives a subkey for `label` into `okm`, following HKDF-Expand
+    /// ([RFC 5869](https://www.rfc-editor.org/rfc/rfc5869#section-2.3)) with
+    /// this output as the pseudorandom key. Subkeys for different `label`s are
+    /// independent of each other.
+    ///
+    /// # Errors
+    /// [`Error::ExpandLength`] if `okm` is longer then 255 times the hash
+    /// output size.
+    pub fn expand_into(&self, label: &[u8], okm: &mut [u8]) -> Result<()> {
+        let hash_len = <CS::Hash as OutputSizeUser>::OutputSize::USIZE;
+
+        if okm.len() > usize::from(u8::MAX) * hash_len {
+            return Err(Error::ExpandLength);
+        }
+
+        // T(0) = empty string
+        // T(i) = HMAC-Hash(PRK, T(i - 1) || info || i)
+        let mut block = Output::<CS::Hash>::default();
+
+        for (counter, chunk) in (1..=u8::MAX).zip(okm.chunks_mut(hash_len)) {
+            let previous: &[u8] = if counter == 1 { &[] } else { &block };
+            let next = hmac::<CS::Hash>(&self.0, &[previous, label, &[counter]]);
+            block.copy_from_slice(&next);
+            chunk.copy_from_slice(&block[..chunk.len()]);
+        }
+
+        block[..].zeroize();
+
+        Ok(())
+    }
+
+    /// Derives a subkey of `len` bytes for `label`, see
+    /// [`expand_into`](Self::expand_into).
+    ///
+    /// # Errors
+    /// [`Error::ExpandLength`] if `len` is longer then 255 times the hash output
+    /// size.
+    #[cfg(feature = "alloc")]
+    pub fn expand(&self, label: &[u8], len: usize) -> Result<Zeroizing<Vec<u8>>> {
+        let mut okm = Zeroizing::new(vec![0; len]);
+        self.expand_into(label, &mut okm)?;
+
+        Ok(okm)
+    }
+
+    /// Exposes the output in the [`Debug`] output, which is otherwise
+    /// redacted.
+    #[cfg(feature = "danger")]
+    pub fn insecure_debug(&self) -> InsecureDebug<'_, Self> {
+        InsecureDebug(self)
+    }
+}
+
+impl<CS: CipherSuite> Debug for OprfOutput<CS> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OprfOutput")
+            .field("suite", &CS::ID)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "danger")]
+impl<CS: CipherSuite> Debug for InsecureDebug<'_, OprfOutput<CS>> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("OprfOutput").field(&Hex(&self.0 .0)).finish()
+    }
+}
+
+impl<CS: CipherSuite> ConstantTimeEq for OprfOutput<CS> {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.0[..].ct_eq(&other.0[..])
+    }
+}
+
+impl<CS: CipherSuite> PartialEq for OprfOutput<CS> {
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
+impl<CS: CipherSuite> Eq for OprfOutput<CS> {}
+
+/// Records which elements of a batch were accepted by a server batch evaluate
+/// with a filter, e.g. `VoprfServer::batch_blind_evaluate_filtered`. Only the
+/// accepted elements are evaluated and covered by the proof.
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct BatchIndexMap(pub(crate) Vec<bool>);
+
+/// Iterator over indices of a [`BatchIndexMap`], returned by
+/// [`BatchIndexMap::accepted`] and [`BatchIndexMap::rejected`].
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug)]
+pub struct BatchIndexMapIndices<'a> {
+    elements: Enumerate<slice::Iter<'a, bool>>,
+    accepted: bool,
+}
+
+#[cfg(feature = "alloc")]
+impl BatchIndexMap {
+    /// Returns the number of elements in the batch, accepted or not.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if the batch has no elements.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns `true` if the element at `index` was accepted.
+    pub fn is_accepted(&self, index: usize) -> bool {
+        self.0.get(index).copied().unwrap_or_default()
+    }
+
+    /// Returns the indices of the accepted elements in ascending order.
+    pub fn accepted(&self) -> BatchIndexMapIndices<'_> {
+        self.indices(true)
+    }
+
+    /// Returns the indices of the rejected elements in ascending order.
+    pub fn rejected(&self) -> BatchIndexMapIndices<'_> {
+        self.indices(false)
+    }
+
+    fn indices(&self, accepted: bool) -> BatchIndexMapIndices<'_> {
+        BatchIndexMapIndices {
+            elements: self.0.iter().enumerate(),
+            accepted,
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Iterator for BatchIndexMapIndices<'_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let accepted = self.accepted;
+
+        self.elements
+            .find_map(|(index, &is_accepted)| (is_accepted == accepted).then_some(index))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.elements.size_hint().1)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl FromIterator<bool> for BatchIndexMap {
+    fn from_iter<I: IntoIterator<Item = bool>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
 /////////////////////
 // Proof Functions //
 // =============== //
 /////////////////////
 
-/// Can only fail with [`Error::Batch`].
-#[allow(clippy::many_single_char_names)]
-pub(crate) fn generate_proof<CS: CipherSuite, R: TryRngCore + TryCryptoRng>(
+/// Fresh randomness used to hedge the nonce of a [`Proof`].
+pub(crate) type ProofRandomness<CS> =
+    GenericArray<u8, <<CS as CipherSuite>::Group as Group>::ScalarLen>;
+
+/// Draws the fresh randomness used to hedge the nonce of a [`Proof`]. Returns
+/// [`None`] if the `rng` fails, in which case the nonce falls back to the
+/// deterministic one: it stays safe without fresh randomness, so proofs never
+/// fail because of the `rng`.
+pub(crate) fn proof_randomness<CS: CipherSuite, R: TryRngCore + TryCryptoRng>(
     rng: &mut R,
+) -> Option<ProofRandomness<CS>> {
+    let mut randomness = ProofRandomness::<CS>::default();
+    rng.try_fill_bytes(&mut randomness).ok()?;
+    Some(randomness)
+}
+
+/// The nonce of the proof is derived from `k`, the proof transcript and the
+/// fresh `randomness`, so a faulty RNG can't leak `k`. An empty `randomness`
+/// makes the proof deterministic. If `proof_random_scalar` is set, it is used
+/// as the nonce instead.
+///
+/// Can only fail with [`Error::Batch`].
+#[allow(clippy::many_single_char_names, clippy::too_many_arguments)]
+pub(crate) fn generate_proof<CS: CipherSuite>(
+    randomness: &[u8],
+    proof_random_scalar: Option<<CS::Group as Group>::Scalar>,
     k: <CS::Group as Group>::Scalar,
     a: <CS::Group as Group>::Elem,
     b: <CS::Group as Group>::Elem,
@@ -141,16 +543,70 @@ pub(crate) fn generate_proof<CS: CipherSuite, R: TryRngCore + TryCryptoRng>(
 
     let (m, z) = compute_composites::<CS, _, _>(Some(k), b, cs, ds, mode)?;
 
-    let r = CS::Group::random_scalar(rng)?;
-    let t2 = a * &r;
-    let t3 = m * &r;
-
     // Bm = GG.SerializeElement(B)
     let bm = CS::Group::serialize_elem(b);
     // a0 = GG.SerializeElement(M)
     let a0 = CS::Group::serialize_elem(m);
     // a1 = GG.SerializeElement(Z)
     let a1 = CS::Group::serialize_elem(z);
+
+    let r = match proof_random_scalar {
+        Some(r) => r,
+        None => hedged_nonce::<CS>(k, randomness, &bm, &a0, &a1, mode),
+    };
+    let t2 = a * &r;
+    let t3 = m * &r;
+
+    let c_scalar = challenge::<CS>(&bm, &a0, &a1, t2, t3, mode);
+    let s_scalar = r - &(c_scalar * &k);
+
+    Ok(Proof { c_scalar, s_scalar })
+}
+
+/// Like [`generate_proof`] with the generator as `a`, but without access to
+/// `k`: `prove` is called with the composites `M` and `Z` and the challenge for
+/// the commitments, e.g. [`OprfKeyProvider::prove()`](crate::OprfKeyProvider::prove).
+///
+/// Can fail with [`Error::Batch`] or any error of `prove`.
+pub(crate) fn generate_proof_with_provider<CS: CipherSuite>(
+    b: <CS::Group as Group>::Elem,
+    cs: impl ExactSizeIterator<Item = <CS::Group as Group>::Elem>,
+    ds: impl ExactSizeIterator<Item = <CS::Group as Group>::Elem>,
+    mode: Mode,
+    prove: impl FnOnce(
+        <CS::Group as Group>::Elem,
+        <CS::Group as Group>::Elem,
+        &mut dyn FnMut(
+            <CS::Group as Group>::Elem,
+            <CS::Group as Group>::Elem,
+        ) -> <CS::Group as Group>::Scalar,
+    ) -> Result<<CS::Group as Group>::Scalar>,
+) -> Result<Proof<CS>> {
+    let (m, z) = compute_composites::<CS, _, _>(None, b, cs, ds, mode)?;
+
+    let bm = CS::Group::serialize_elem(b);
+    let a0 = CS::Group::serialize_elem(m);
+    let a1 = CS::Group::serialize_elem(z);
+    let mut c_scalar = None;
+    let s_scalar = prove(m, z, &mut |t2, t3| {
+        *c_scalar.insert(challenge::<CS>(&bm, &a0, &a1, t2, t3, mode))
+    })?;
+    // A provider that never asked for the challenge can't have a valid response.
+    let c_scalar = c_scalar.ok_or(Error::KeyProvider)?;
+
+    Ok(Proof { c_scalar, s_scalar })
+}
+
+/// Computes the challenge of a [`Proof`] from the serialized `b`, `m` and `z`
+/// and the commitments `t2` and `t3`.
+fn challenge<CS: CipherSuite>(
+    bm: &[u8],
+    a0: &[u8],
+    a1: &[u8],
+    t2: <CS::Group as Group>::Elem,
+    t3: <CS::Group as Group>::Elem,
+    mode: Mode,
+) -> <CS::Group as Group>::Scalar {
     // a2 = GG.SerializeElement(t2)
     let a2 = CS::Group::serialize_elem(t2);
     // a3 = GG.SerializeElement(t3)
@@ -166,11 +622,11 @@ pub(crate) fn generate_proof<CS: CipherSuite, R: TryRngCore + TryCryptoRng>(
     //           "Challenge"
     let h2_input = [
         &elem_len,
-        bm.as_slice(),
+        bm,
         &elem_len,
-        &a0,
+        a0,
         &elem_len,
-        &a1,
+        a1,
         &elem_len,
         &a2,
         &elem_len,
@@ -180,10 +636,54 @@ pub(crate) fn generate_proof<CS: CipherSuite, R: TryRngCore + TryCryptoRng>(
 
     let dst = Dst::new::<CS, _, _>(STR_HASH_TO_SCALAR, mode);
     // This can't fail, the size of the `input` is known.
-    let c_scalar = CS::Group::hash_to_scalar::<CS::Hash>(&h2_input, &dst.as_dst()).unwrap();
-    let s_scalar = r - &(c_scalar * &k);
+    CS::Group::hash_to_scalar::<CS::Hash>(&h2_input, &dst.as_dst()).unwrap()
+}
 
-    Ok(Proof { c_scalar, s_scalar })
+/// Derives the nonce of a [`Proof`] in the style of
+/// [RFC 6979](https://www.rfc-editor.org/rfc/rfc6979), hedged with fresh
+/// `randomness`.
+///
+/// `randomness` is at most a scalar long, see [`proof_randomness`].
+pub(crate) fn hedged_nonce<CS: CipherSuite>(
+    k: <CS::Group as Group>::Scalar,
+    randomness: &[u8],
+    bm: &[u8],
+    a0: &[u8],
+    a1: &[u8],
+    mode: Mode,
+) -> <CS::Group as Group>::Scalar {
+    let scalar_len = <CS::Group as Group>::ScalarLen::U16.to_be_bytes();
+    let elem_len = <CS::Group as Group>::ElemLen::U16.to_be_bytes();
+    let mut sk = CS::Group::serialize_scalar(k);
+    // This can't fail, the size of the `randomness` is known.
+    let randomness_len = i2osp_2(randomness.len()).unwrap();
+
+    // nonceInput = I2OSP(len(sk), 2) || sk ||
+    //              I2OSP(len(randomness), 2) || randomness ||
+    //              I2OSP(len(Bm), 2) || Bm ||
+    //              I2OSP(len(a0), 2) || a0 ||
+    //              I2OSP(len(a1), 2) || a1 ||
+    //              "HedgedNonce"
+    let nonce_input = [
+        &scalar_len,
+        sk.as_slice(),
+        &randomness_len,
+        randomness,
+        &elem_len,
+        bm,
+        &elem_len,
+        a0,
+        &elem_len,
+        a1,
+        &STR_HEDGED_NONCE,
+    ];
+
+    let dst = Dst::new::<CS, _, _>(STR_HASH_TO_SCALAR, mode);
+    // This can't fail, the size of the `input` is known.
+    let nonce = CS::Group::hash_to_scalar::<CS::Hash>(&nonce_input, &dst.as_dst()).unwrap();
+    sk[..].zeroize();
+
+    nonce
 }
 
 /// Can only fail with [`Error::ProofVerification`] or [`Error::Batch`].
@@ -207,36 +707,7 @@ pub(crate) fn verify_proof<CS: CipherSuite>(
     let a0 = CS::Group::serialize_elem(m);
     // a1 = GG.SerializeElement(Z)
     let a1 = CS::Group::serialize_elem(z);
-    // a2 = GG.SerializeElement(t2)
-    let a2 = CS::Group::serialize_elem(t2);
-    // a3 = GG.SerializeElement(t3)
-    let a3 = CS::Group::serialize_elem(t3);
-
-    let elem_len = <CS::Group as Group>::ElemLen::U16.to_be_bytes();
-
-    // h2Input = I2OSP(len(Bm), 2) || Bm ||
-    //           I2OSP(len(a0), 2) || a0 ||
-    //           I2OSP(len(a1), 2) || a1 ||
-    //           I2OSP(len(a2), 2) || a2 ||
-    //           I2OSP(len(a3), 2) || a3 ||
-    //           "Challenge"
-    let h2_input = [
-        &elem_len,
-        bm.as_slice(),
-        &elem_len,
-        &a0,
-        &elem_len,
-        &a1,
-        &elem_len,
-        &a2,
-        &elem_len,
-        &a3,
-        &STR_CHALLENGE,
-    ];
-
-    let dst = Dst::new::<CS, _, _>(STR_HASH_TO_SCALAR, mode);
-    // This can't fail, the size of the `input` is known.
-    let c = CS::Group::hash_to_scalar::<CS::Hash>(&h2_input, &dst.as_dst()).unwrap();
+    let c = challenge::<CS>(&bm, &a0, &a1, t2, t3, mode);
 
     match c.ct_eq(&proof.c_scalar).into() {
         true => Ok(()),
@@ -356,7 +827,7 @@ pub(crate) fn derive_key_internal<CS: CipherSuite>(
         }
     }
 
-    Err(Error::Protocol)
+    Err(Error::Protocol(ProtocolError::DeriveKeyPairExhausted))
 }
 
 /// Corresponds to DeriveKeyPair() function from the VOPRF specification.
@@ -411,7 +882,33 @@ pub(crate) fn hash_to_group<CS: CipherSuite>(
     mode: Mode,
 ) -> Result<<CS::Group as Group>::Elem> {
     let dst = Dst::new::<CS, _, _>(STR_HASH_TO_GROUP, mode);
-    CS::Group::hash_to_curve::<CS::Hash>(&[input], &dst.as_dst()).map_err(|_| Error::Input)
+    CS::Group::hash_to_curve::<CS::Hash>(&[input], &dst.as_dst())
+        .map_err(|_| Error::Input(InputError::HashToGroup))
+}
+
+/// HMAC ([RFC 2104](https://www.rfc-editor.org/rfc/rfc2104)) of the
+/// concatenated `message` under `key`. The `key` can't be longer than the block
+/// size of `H`, which [`CipherSuite`] guarantees for its hash outputs.
+pub(crate) fn hmac<H: BlockSizeUser + Digest>(key: &[u8], message: &[&[u8]]) -> Output<H> {
+    const IPAD: u8 = 0x36;
+    const OPAD: u8 = 0x5C;
+
+    let mut pad = Block::<H>::default();
+    pad[..key.len()].copy_from_slice(key);
+
+    pad.iter_mut().for_each(|byte| *byte ^= IPAD);
+    let mut inner = H::new().chain_update(&pad);
+
+    for part in message {
+        inner.update(part);
+    }
+
+    let inner = inner.finalize();
+    pad.iter_mut().for_each(|byte| *byte ^= IPAD ^ OPAD);
+    let outer = H::new().chain_update(&pad).chain_update(inner).finalize();
+    pad[..].zeroize();
+
+    outer
 }
 
 /// Internal function that finalizes the hash input for OPRF, VOPRF & POPRF.
@@ -420,7 +917,7 @@ pub(crate) fn server_evaluate_hash_input<CS: CipherSuite>(
     input: &[u8],
     info: Option<&[u8]>,
     issued_element: GenericArray<u8, <<CS as CipherSuite>::Group as Group>::ElemLen>,
-) -> Result<Output<CS::Hash>> {
+) -> Result<OprfOutput<CS>> {
     // OPRF & VOPRF
     // hashInput = I2OSP(len(input), 2) || input ||
     //             I2OSP(len(issuedElement), 2) || issuedElement ||
@@ -434,18 +931,23 @@ pub(crate) fn server_evaluate_hash_input<CS: CipherSuite>(
     //             "Finalize"
 
     let mut hash = CS::Hash::new()
-        .chain_update(i2osp_2(input.as_ref().len()).map_err(|_| Error::Input)?)
+        .chain_update(
+            i2osp_2(input.as_ref().len())
+                .map_err(|_| Error::input_too_long(input.as_ref().len()))?,
+        )
         .chain_update(input.as_ref());
     if let Some(info) = info {
         hash = hash
-            .chain_update(i2osp_2(info.as_ref().len()).map_err(|_| Error::Input)?)
+            .chain_update(i2osp_2(info.as_ref().len()).map_err(|_| Error::Info)?)
             .chain_update(info.as_ref());
     }
-    Ok(hash
-        .chain_update(i2osp_2(issued_element.as_slice().len()).map_err(|_| Error::Input)?)
-        .chain_update(issued_element)
-        .chain_update(STR_FINALIZE)
-        .finalize())
+    // This can't fail, the size of the `issued_element` is fixed.
+    Ok(OprfOutput(
+        hash.chain_update(i2osp_2(issued_element.as_slice().len()).unwrap())
+            .chain_update(issued_element)
+            .chain_update(STR_FINALIZE)
+            .finalize(),
+    ))
 }
 
 pub(crate) struct Dst<L: ArrayLength> {
@@ -512,12 +1014,57 @@ where
 // ================= //
 ///////////////////////
 
+/// Formats bytes as lowercase hex in [`Debug`] output.
+pub(crate) struct Hex<'a>(pub(crate) &'a [u8]);
+
+impl Debug for Hex<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.0.iter().try_for_each(|byte| write!(f, "{byte:02x}"))
+    }
+}
+
 pub(crate) fn i2osp_2(input: usize) -> Result<[u8; 2], InternalError> {
     u16::try_from(input)
         .map(|input| input.to_be_bytes())
         .map_err(|_| InternalError::I2osp)
 }
 
+/// Checks up front that none of the `inputs` is empty or longer then
+/// [`u16::MAX`].
+///
+/// Fails with [`Error::Input`] and the index of the first invalid input.
+pub(crate) fn check_inputs<I: AsRef<[u8]>>(
+    inputs: impl IntoIterator<Item = I>,
+) -> Result<(), BatchError> {
+    inputs
+        .into_iter()
+        .enumerate()
+        .try_for_each(|(index, input)| {
+            let len = input.as_ref().len();
+
+            if len == 0 {
+                Err(BatchError::new(index, Error::Input(InputError::Empty)))
+            } else if i2osp_2(len).is_err() {
+                Err(BatchError::new(index, Error::input_too_long(len)))
+            } else {
+                Ok(())
+            }
+        })
+}
+
+/// Collects the next `size` items of `iter`. Returns [`None`] if `iter` is
+/// exhausted.
+#[cfg(feature = "alloc")]
+pub(crate) fn next_chunk<I: Iterator>(iter: &mut I, size: usize) -> Option<Vec<I::Item>> {
+    let chunk: Vec<_> = iter.by_ref().take(size).collect();
+
+    if chunk.is_empty() {
+        None
+    } else {
+        Some(chunk)
+    }
+}
+
 pub(crate) fn i2osp_2_array<L: ArrayLength + IsLess<U256>>() -> GenericArray<u8, U2> {
     L::U16.to_be_bytes().into()
 }
@@ -0,0 +1,607 @@
+// Copyright (c) Meta Platforms, Inc. and affiliates.
+//
+// This source code is dual-licensed under either the MIT license found in the
+// LICENSE-MIT file in the root directory of this source tree or the Apache
+// License, Version 2.0 found in the LICENSE-APACHE file in the root directory
+// of this source tree. You may select, at your option, one of the above-listed
+// licenses.
+
+//! Contains the ECVRF API as specified in
+//! [RFC 9381](https://www.rfc-editor.org/rfc/rfc9381).
+//!
+//! Only the `ECVRF-P256-SHA256-TAI` and `ECVRF-P256-SHA256-SSWU` ciphersuites
+//! are provided. RFC 9381 defines no ciphersuite over Ristretto255, so none is
+//! offered for it, and its Edwards25519 ciphersuites aren't supported, as
+//! there is no [`Group`] for Edwards25519.
+
+use core::fmt::{self, Debug, Formatter};
+use core::ops::Add;
+
+use derive_where::derive_where;
+use digest::{Digest, Output};
+use generic_array::sequence::Concat;
+use generic_array::typenum::{Sum, Unsigned, U16};
+use generic_array::{ArrayLength, GenericArray};
+use rand_core::{TryCryptoRng, TryRngCore};
+use subtle::ConstantTimeEq;
+
+#[cfg(any(feature = "p256", test))]
+use crate::common::hmac;
+#[cfg(feature = "danger")]
+use crate::common::InsecureDebug;
+#[cfg(feature = "serde")]
+use crate::serialization::serde::{Element, Scalar};
+use crate::serialization::{
+    deserialize_elem, deserialize_scalar, split_off, take_field, Serializable,
+};
+#[cfg(any(feature = "p256", test))]
+use crate::InputError;
+use crate::{Error, Group, Result};
+
+///////////////
+// Constants //
+// ========= //
+///////////////
+
+#[cfg(any(feature = "p256", test))]
+const STR_ECVRF: [u8; 6] = *b"ECVRF_";
+#[cfg(any(feature = "p256", test))]
+const ENCODE_TO_CURVE_FRONT: u8 = 0x01;
+const CHALLENGE_GENERATION_FRONT: u8 = 0x02;
+const PROOF_TO_HASH_FRONT: u8 = 0x03;
+const BACK: u8 = 0x00;
+
+/// Length of the challenge `c` in bytes, `cLen` in the specification. Equal
+/// for all ciphersuites defined in RFC 9381.
+pub type EcvrfChallengeLen = U16;
+
+/// Configures the underlying primitives of an ECVRF ciphersuite
+pub trait EcvrfSuite
+where
+    // `EcvrfProofLen`
+    <Self::Group as Group>::ElemLen: Add<EcvrfChallengeLen>,
+    Sum<<Self::Group as Group>::ElemLen, EcvrfChallengeLen>:
+        ArrayLength + Add<<Self::Group as Group>::ScalarLen>,
+    Sum<Sum<<Self::Group as Group>::ElemLen, EcvrfChallengeLen>, <Self::Group as Group>::ScalarLen>:
+        ArrayLength,
+{
+    /// The `suite_string` identifying the ciphersuite
+    const SUITE_STRING: u8;
+
+    /// A prime-order group with cofactor 1. See [`Group`].
+    type Group: Group;
+
+    /// The hash function used for challenge generation and to compute the
+    /// VRF output.
+    type Hash: Digest;
+
+    /// Implements `ECVRF_encode_to_curve()`.
+    ///
+    /// # Errors
+    /// [`Error::Input`] if the `alpha` can't be encoded to a curve point.
+    fn encode_to_curve(salt: &[u8], alpha: &[u8]) -> Result<<Self::Group as Group>::Elem>;
+
+    /// Implements `ECVRF_nonce_generation()`.
+    fn nonce_generation(
+        sk: <Self::Group as Group>::Scalar,
+        h_string: &[u8],
+    ) -> <Self::Group as Group>::Scalar;
+
+    /// Implements `string_to_int()` for the challenge `c`.
+    fn challenge_to_scalar(
+        c: &GenericArray<u8, EcvrfChallengeLen>,
+    ) -> <Self::Group as Group>::Scalar;
+}
+
+////////////////////////////
+// High-level API Structs //
+// ====================== //
+////////////////////////////
+
+/// A prover which holds the VRF secret key and produces [`EcvrfProof`]s.
+#[derive_where(Clone, ZeroizeOnDrop)]
+#[derive_where(Eq, Hash, Ord, PartialEq, PartialOrd; <S::Group as Group>::Scalar, <S::Group as Group>::Elem)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize, serde::Serialize),
+    serde(bound = "")
+)]
+pub struct EcvrfProver<S: EcvrfSuite> {
+    #[cfg_attr(feature = "serde", serde(with = "Scalar::<S::Group>"))]
+    pub(crate) sk: <S::Group as Group>::Scalar,
+    #[cfg_attr(feature = "serde", serde(with = "Element::<S::Group>"))]
+    pub(crate) pk: <S::Group as Group>::Elem,
+}
+
+/// A proof produced by an [`EcvrfProver`], `pi_string` in the
+/// specification.
+#[derive_where(Clone)]
+#[derive_where(Debug, Eq, Hash, Ord, PartialEq, PartialOrd; <S::Group as Group>::Scalar, <S::Group as Group>::Elem)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize, serde::Serialize),
+    serde(bound = "")
+)]
+pub struct EcvrfProof<S: EcvrfSuite> {
+    #[cfg_attr(feature = "serde", serde(with = "Element::<S::Group>"))]
+    pub(crate) gamma: <S::Group as Group>::Elem,
+    pub(crate) c: GenericArray<u8, EcvrfChallengeLen>,
+    #[cfg_attr(feature = "serde", serde(with = "Scalar::<S::Group>"))]
+    pub(crate) s: <S::Group as Group>::Scalar,
+}
+
+/////////////////////////
+// API Implementations //
+// =================== //
+/////////////////////////
+
+impl<S: EcvrfSuite> EcvrfProver<S> {
+    /// Produces a new instance of a [EcvrfProver] using a supplied RNG
+    ///
+    /// # Errors
+    /// [`Error::Rng`] if the random number generator fails.
+    pub fn new<R: TryRngCore + TryCryptoRng>(rng: &mut R) -> Result<Self> {
+        let sk = S::Group::random_scalar(rng)?;
+        let pk = S::Group::base_elem() * &sk;
+        Ok(Self { sk, pk })
+    }
+
+    /// Produces a new instance of a [EcvrfProver] using a supplied set of
+    /// bytes to represent the VRF secret scalar
+    ///
+    /// # Errors
+    /// [`Error::Deserialization`] if the private key is not a valid scalar or
+    /// zero.
+    pub fn new_with_key(private_key_bytes: &[u8]) -> Result<Self> {
+        let sk = S::Group::deserialize_scalar(private_key_bytes)?;
+        let pk = S::Group::base_elem() * &sk;
+        Ok(Self { sk, pk })
+    }
+
+    /// Computes a proof for `alpha`, corresponding to `ECVRF_prove()`.
+    ///
+    /// # Errors
+    /// [`Error::Input`] if the `alpha` can't be encoded to a curve point.
+    pub fn prove(&self, alpha: &[u8]) -> Result<EcvrfProof<S>> {
+        // https://www.rfc-editor.org/rfc/rfc9381#section-5.1
+        let pk_string = S::Group::serialize_elem(self.pk);
+        let h = S::encode_to_curve(&pk_string, alpha)?;
+        let h_string = S::Group::serialize_elem(h);
+        let gamma = h * &self.sk;
+        let k = S::nonce_generation(self.sk, &h_string);
+        let c = challenge_generation::<S>([self.pk, h, gamma, S::Group::base_elem() * &k, h * &k]);
+        let s = k + &(S::challenge_to_scalar(&c) * &self.sk);
+
+        Ok(EcvrfProof { gamma, c, s })
+    }
+
+    /// Retrieves the prover's public key
+    pub fn get_public_key(&self) -> <S::Group as Group>::Elem {
+        self.pk
+    }
+}
+
+impl<S: EcvrfSuite> EcvrfProof<S> {
+    /// Computes the VRF output of this proof, corresponding to
+    /// `ECVRF_proof_to_hash()`.
+    ///
+    /// The output is only meaningful after the proof was checked with
+    /// [`verify`](Self::verify).
+    pub fn proof_to_hash(&self) -> Output<S::Hash> {
+        // https://www.rfc-editor.org/rfc/rfc9381#section-5.2
+        S::Hash::new()
+            .chain_update([S::SUITE_STRING, PROOF_TO_HASH_FRONT])
+            .chain_update(S::Group::serialize_elem(self.gamma))
+            .chain_update([BACK])
+            .finalize()
+    }
+
+    /// Verifies this proof for `alpha` against the public key `pk` and returns
+    /// the VRF output, corresponding to `ECVRF_verify()`.
+    ///
+    /// # Errors
+    /// - [`Error::Input`] if the `alpha` can't be encoded to a curve point.
+    /// - [`Error::ProofVerification`] if the `pk` is the identity element or
+    ///   the proof failed to verify.
+    pub fn verify(&self, pk: <S::Group as Group>::Elem, alpha: &[u8]) -> Result<Output<S::Hash>> {
+        // https://www.rfc-editor.org/rfc/rfc9381#section-5.3
+        if S::Group::is_identity_elem(pk).into() {
+            return Err(Error::ProofVerification);
+        }
+
+        let pk_string = S::Group::serialize_elem(pk);
+        let h = S::encode_to_curve(&pk_string, alpha)?;
+        let minus_c = negate_scalar::<S::Group>(S::challenge_to_scalar(&self.c));
+        let u = (S::Group::base_elem() * &self.s) + &(pk * &minus_c);
+        let v = (h * &self.s) + &(self.gamma * &minus_c);
+        let c_prime = challenge_generation::<S>([pk, h, self.gamma, u, v]);
+
+        match c_prime.ct_eq(&self.c).into() {
+            true => Ok(self.proof_to_hash()),
+            false => Err(Error::ProofVerification),
+        }
+    }
+}
+
+impl<S: EcvrfSuite> Debug for EcvrfProver<S>
+where
+    <S::Group as Group>::Elem: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EcvrfProver")
+            .field("suite", &S::SUITE_STRING)
+            .field("pk", &self.pk)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "danger")]
+impl<S: EcvrfSuite> EcvrfProver<S> {
+    /// Exposes the secret key in the [`Debug`] output, which is otherwise
+    /// redacted.
+    pub fn insecure_debug(&self) -> InsecureDebug<'_, Self> {
+        InsecureDebug(self)
+    }
+}
+
+#[cfg(feature = "danger")]
+impl<S: EcvrfSuite> Debug for InsecureDebug<'_, EcvrfProver<S>>
+where
+    <S::Group as Group>::Scalar: Debug,
+    <S::Group as Group>::Elem: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EcvrfProver")
+            .field("sk", &self.0.sk)
+            .field("pk", &self.0.pk)
+            .finish()
+    }
+}
+
+/////////////////////
+// Inner functions //
+// =============== //
+/////////////////////
+
+/// [`Group`] doesn't expose scalar negation or zero, so compute it as
+/// `(c - c) - c`.
+fn negate_scalar<G: Group>(scalar: G::Scalar) -> G::Scalar {
+    (scalar - &scalar) - &scalar
+}
+
+/// Implements `ECVRF_challenge_generation()`.
+fn challenge_generation<S: EcvrfSuite>(
+    points: [<S::Group as Group>::Elem; 5],
+) -> GenericArray<u8, EcvrfChallengeLen> {
+    // https://www.rfc-editor.org/rfc/rfc9381#section-5.4.3
+    let mut hash = S::Hash::new().chain_update([S::SUITE_STRING, CHALLENGE_GENERATION_FRONT]);
+
+    for point in points {
+        hash.update(S::Group::serialize_elem(point));
+    }
+
+    let c_string = hash.chain_update([BACK]).finalize();
+    *GenericArray::from_slice(&c_string[..EcvrfChallengeLen::USIZE])
+}
+
+///////////////////
+// Serialization //
+// ============= //
+///////////////////
+
+/// Length of [`EcvrfProof`] in bytes for serialization.
+pub type EcvrfProofLen<S> = Sum<
+    Sum<<<S as EcvrfSuite>::Group as Group>::ElemLen, EcvrfChallengeLen>,
+    <<S as EcvrfSuite>::Group as Group>::ScalarLen,
+>;
+
+impl<S: EcvrfSuite> EcvrfProof<S> {
+    /// Serialization into bytes
+    pub fn serialize(&self) -> GenericArray<u8, EcvrfProofLen<S>> {
+        S::Group::serialize_elem(self.gamma)
+            .concat(self.c)
+            .concat(S::Group::serialize_scalar(self.s))
+    }
+
+    /// Deserialization from bytes
+    ///
+    /// # Errors
+    /// [`Error::Deserialization`] if failed to deserialize `input`.
+    pub fn deserialize(mut input: &[u8]) -> Result<Self> {
+        let gamma = deserialize_elem::<S::Group>(&mut input, "gamma")?;
+        let c = *GenericArray::from_slice(take_field(&mut input, EcvrfChallengeLen::USIZE, "c")?);
+        let s = deserialize_scalar::<S::Group>(&mut input, "s")?;
+
+        Ok(Self { gamma, c, s })
+    }
+}
+
+impl<S: EcvrfSuite> Serializable for EcvrfProof<S> {
+    type Len = EcvrfProofLen<S>;
+
+    fn serialize(&self) -> GenericArray<u8, Self::Len> {
+        self.serialize()
+    }
+
+    fn deserialize(input: &[u8]) -> Result<(Self, &[u8])> {
+        split_off(input, Self::deserialize)
+    }
+}
+
+//////////////////
+// Ciphersuites //
+// ============ //
+//////////////////
+
+/// The `ECVRF-P256-SHA256-TAI` ciphersuite from RFC 9381, encoding to the
+/// curve with try-and-increment.
+#[cfg(any(feature = "p256", test))]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct EcvrfP256Sha256Tai;
+
+#[cfg(any(feature = "p256", test))]
+impl EcvrfSuite for EcvrfP256Sha256Tai {
+    const SUITE_STRING: u8 = 0x01;
+
+    type Group = p256::NistP256;
+
+    type Hash = sha2::Sha256;
+
+    fn encode_to_curve(salt: &[u8], alpha: &[u8]) -> Result<p256::ProjectivePoint> {
+        // https://www.rfc-editor.org/rfc/rfc9381#section-5.4.1.1
+        for ctr in 0..=u8::MAX {
+            let hash_string = sha2::Sha256::new()
+                .chain_update([Self::SUITE_STRING, ENCODE_TO_CURVE_FRONT])
+                .chain_update(salt)
+                .chain_update(alpha)
+                .chain_update([ctr, BACK])
+                .finalize();
+
+            // `interpret_hash_value_as_a_point()` prepends the even point prefix
+            let mut point_string = [0x02; 33];
+            point_string[1..].copy_from_slice(&hash_string);
+
+            if let Ok(h) = Self::Group::deserialize_elem(&point_string) {
+                return Ok(h);
+            }
+        }
+
+        Err(Error::Input(InputError::HashToGroup))
+    }
+
+    fn nonce_generation(sk: p256::Scalar, h_string: &[u8]) -> p256::Scalar {
+        p256_nonce_generation(sk, h_string)
+    }
+
+    fn challenge_to_scalar(c: &GenericArray<u8, EcvrfChallengeLen>) -> p256::Scalar {
+        p256_challenge_to_scalar(c)
+    }
+}
+
+/// The `ECVRF-P256-SHA256-SSWU` ciphersuite from RFC 9381, encoding to the
+/// curve with `P256_XMD:SHA-256_SSWU_NU_`.
+#[cfg(any(feature = "p256", test))]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct EcvrfP256Sha256Sswu;
+
+#[cfg(any(feature = "p256", test))]
+impl EcvrfSuite for EcvrfP256Sha256Sswu {
+    const SUITE_STRING: u8 = 0x02;
+
+    type Group = p256::NistP256;
+
+    type Hash = sha2::Sha256;
+
+    fn encode_to_curve(salt: &[u8], alpha: &[u8]) -> Result<p256::ProjectivePoint> {
+        use elliptic_curve::hash2curve::{ExpandMsgXmd, GroupDigest};
+
+        // https://www.rfc-editor.org/rfc/rfc9381#section-5.4.1.2
+        const H2C_SUITE_ID: &[u8] = b"P256_XMD:SHA-256_SSWU_NU_";
+        let dst: [&[u8]; 3] = [&STR_ECVRF, H2C_SUITE_ID, &[Self::SUITE_STRING]];
+
+        p256::NistP256::encode_from_bytes::<ExpandMsgXmd<sha2::Sha256>>(&[salt, alpha], &dst)
+            .map_err(|_| Error::Input(InputError::HashToGroup))
+    }
+
+    fn nonce_generation(sk: p256::Scalar, h_string: &[u8]) -> p256::Scalar {
+        p256_nonce_generation(sk, h_string)
+    }
+
+    fn challenge_to_scalar(c: &GenericArray<u8, EcvrfChallengeLen>) -> p256::Scalar {
+        p256_challenge_to_scalar(c)
+    }
+}
+
+/// Implements `ECVRF_nonce_generation()` for the P-256 ciphersuites with the
+/// deterministic nonce generation of
+/// [RFC 6979](https://www.rfc-editor.org/rfc/rfc6979#section-3.2).
+#[cfg(any(feature = "p256", test))]
+fn p256_nonce_generation(sk: p256::Scalar, h_string: &[u8]) -> p256::Scalar {
+    use elliptic_curve::ops::Reduce;
+
+    type G = p256::NistP256;
+
+    // https://www.rfc-editor.org/rfc/rfc9381#section-5.4.2.1
+    let h1 = sha2::Sha256::digest(h_string);
+    // `bits2octets(h1)`
+    let h1 = G::serialize_scalar(<p256::Scalar as Reduce<p256::U256>>::reduce_bytes(&h1));
+    // `int2octets(x)`
+    let x = G::serialize_scalar(sk);
+
+    let hmac = hmac::<sha2::Sha256>;
+
+    let mut v = Output::<sha2::Sha256>::from([0x01; 32]);
+    let mut k = hmac(&[0x00; 32], &[&v, &[0x00], &x, &h1]);
+    v = hmac(&k, &[&v]);
+    k = hmac(&k, &[&v, &[0x01], &x, &h1]);
+    v = hmac(&k, &[&v]);
+
+    loop {
+        v = hmac(&k, &[&v]);
+
+        // Only accepts values in `[1, q - 1]`.
+        if let Ok(nonce) = G::deserialize_scalar(&v) {
+            return nonce;
+        }
+
+        k = hmac(&k, &[&v, &[0x00]]);
+        v = hmac(&k, &[&v]);
+    }
+}
+
+/// Implements `string_to_int()` for the P-256 ciphersuites, which is
+/// big-endian.
+#[cfg(any(feature = "p256", test))]
+fn p256_challenge_to_scalar(c: &GenericArray<u8, EcvrfChallengeLen>) -> p256::Scalar {
+    use elliptic_curve::ops::Reduce;
+
+    let mut bytes = p256::FieldBytes::default();
+    bytes[32 - EcvrfChallengeLen::USIZE..].copy_from_slice(c);
+    <p256::Scalar as Reduce<p256::U256>>::reduce_bytes(&bytes)
+}
+
+///////////
+// Tests //
+// ===== //
+///////////
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::OsRng;
+
+    use super::*;
+
+    fn prove_and_verify<S: EcvrfSuite>() {
+        let mut rng = OsRng;
+        let prover = EcvrfProver::<S>::new(&mut rng).unwrap();
+        let proof = prover.prove(b"alpha").unwrap();
+
+        // Proving is deterministic
+        assert_eq!(
+            proof.serialize(),
+            prover.prove(b"alpha").unwrap().serialize()
+        );
+
+        let beta = proof.verify(prover.get_public_key(), b"alpha").unwrap();
+        assert_eq!(beta, proof.proof_to_hash());
+
+        let proof = EcvrfProof::<S>::deserialize(&proof.serialize()).unwrap();
+        assert_eq!(
+            beta,
+            proof.verify(prover.get_public_key(), b"alpha").unwrap()
+        );
+
+        // Different inputs result in different outputs
+        let other_proof = prover.prove(b"other alpha").unwrap();
+        assert!(beta != other_proof.proof_to_hash());
+    }
+
+    fn bad_proof<S: EcvrfSuite>() {
+        let mut rng = OsRng;
+        let prover = EcvrfProver::<S>::new(&mut rng).unwrap();
+        let proof = prover.prove(b"alpha").unwrap();
+
+        let result = proof.verify(prover.get_public_key(), b"wrong alpha");
+        assert!(matches!(result, Err(Error::ProofVerification)));
+
+        let wrong_pk = EcvrfProver::<S>::new(&mut rng).unwrap().get_public_key();
+        let result = proof.verify(wrong_pk, b"alpha");
+        assert!(matches!(result, Err(Error::ProofVerification)));
+
+        let result = proof.verify(S::Group::identity_elem(), b"alpha");
+        assert!(matches!(result, Err(Error::ProofVerification)));
+
+        let mut bad_proof = proof.clone();
+        bad_proof.c[0] ^= 1;
+        let result = bad_proof.verify(prover.get_public_key(), b"alpha");
+        assert!(matches!(result, Err(Error::ProofVerification)));
+
+        let proof_bytes = proof.serialize();
+        let result = EcvrfProof::<S>::deserialize(&proof_bytes[..proof_bytes.len() - 1]);
+        assert!(matches!(result, Err(Error::Deserialization(_))));
+    }
+
+    #[test]
+    fn rfc_6979_nonce() {
+        // https://www.rfc-editor.org/rfc/rfc6979#appendix-A.2.5, SHA-256 with
+        // message "sample"
+        let sk = hex::decode("c9afa9d845ba75166b5c215767b1d6934e50c3db36e89b127b8a622b120f6721")
+            .unwrap();
+        let sk = <p256::NistP256 as Group>::deserialize_scalar(&sk).unwrap();
+        let k = p256_nonce_generation(sk, b"sample");
+
+        assert_eq!(
+            hex::encode(<p256::NistP256 as Group>::serialize_scalar(k)),
+            "a6e3c57dd01abe90086538398355dd4c3b17aa873382b0f24d6129493d8aad60"
+        );
+    }
+
+    /// The private and public key of the P-256 examples of RFC 9381.
+    const P256_SK: &str = "c9afa9d845ba75166b5c215767b1d6934e50c3db36e89b127b8a622b120f6721";
+    const P256_PK: &str = "0360fed4ba255a9d31c961eb74c6356d68c049b8923b61fa6ce669622e60f29fb6";
+
+    /// Checks `(alpha, pi, beta)` vectors for the P-256 key of RFC 9381.
+    fn p256_test_vectors<S: EcvrfSuite<Group = p256::NistP256>>(vectors: &[(&[u8], &str, &str)]) {
+        let prover = EcvrfProver::<S>::new_with_key(&hex::decode(P256_SK).unwrap()).unwrap();
+        let pk = prover.get_public_key();
+        assert_eq!(
+            hex::encode(<p256::NistP256 as Group>::serialize_elem(pk)),
+            P256_PK
+        );
+
+        for &(alpha, pi, beta) in vectors {
+            let proof = prover.prove(alpha).unwrap();
+            assert_eq!(hex::encode(proof.serialize()), pi);
+
+            let proof = EcvrfProof::<S>::deserialize(&hex::decode(pi).unwrap()).unwrap();
+            assert_eq!(hex::encode(proof.verify(pk, alpha).unwrap()), beta);
+        }
+    }
+
+    #[test]
+    fn p256_sha256_tai_test_vectors() {
+        // https://www.rfc-editor.org/rfc/rfc9381#appendix-B.1, examples 10 and 11
+        p256_test_vectors::<EcvrfP256Sha256Tai>(&[
+            (
+                b"sample",
+                "035b5c726e8c0e2c488a107c600578ee75cb702343c153cb1eb8dec77f4b5071b4a53f0a46f018bc2c\
+                 56e58d383f2305e0975972c26feea0eb122fe7893c15af376b33edf7de17c6ea056d4d82de6bc02f",
+                "a3ad7b0ef73d8fc6655053ea22f9bede8c743f08bbed3d38821f0e16474b505e",
+            ),
+            (
+                b"test",
+                "034dac60aba508ba0c01aa9be80377ebd7562c4a52d74722e0abae7dc3080ddb56c19e067b15a8a817\
+                 4905b13617804534214f935b94c2287f797e393eb0816969d864f37625b443f30f1a5a33f2b3c854",
+                "a284f94ceec2ff4b3794629da7cbafa49121972671b466cab4ce170aa365f26d",
+            ),
+        ]);
+    }
+
+    #[test]
+    fn p256_sha256_sswu_test_vectors() {
+        // https://www.rfc-editor.org/rfc/rfc9381#appendix-B.2, examples 13 and 14
+        p256_test_vectors::<EcvrfP256Sha256Sswu>(&[
+            (
+                b"sample",
+                "0331d984ca8fece9cbb9a144c0d53df3c4c7a33080c1e02ddb1a96a365394c7888782fffde7b842c38\
+                 c20c08de6ec6c2e7027a97000f2c9fa4425d5c03e639fb48fde58114d755985498d7eb234cf4aed9",
+                "21e66dc9747430f17ed9efeda054cf4a264b097b9e8956a1787526ed00dc664b",
+            ),
+            (
+                b"test",
+                "03f814c0455d32dbc75ad3aea08c7e2db31748e12802db23640203aebf1fa8db2743aad348a3006dc1\
+                 caad7da28687320740bf7dd78fe13c298867321ce3b36b79ec3093b7083ac5e4daf3465f9f43c627",
+                "8e7185d2b420e4f4681f44ce313a26d05613323837da09a69f00491a83ad25dd",
+            ),
+        ]);
+    }
+
+    #[test]
+    fn test_functionality() {
+        prove_and_verify::<EcvrfP256Sha256Tai>();
+        bad_proof::<EcvrfP256Sha256Tai>();
+
+        prove_and_verify::<EcvrfP256Sha256Sswu>();
+        bad_proof::<EcvrfP256Sha256Sswu>();
+    }
+}
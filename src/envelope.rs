@@ -0,0 +1,613 @@
+// Copyright (c) Meta Platforms, Inc. and affiliates.
+//
+// This source code is dual-licensed under either the MIT license found in the
+// LICENSE-MIT file in the root directory of this source tree or the Apache
+// License, Version 2.0 found in the LICENSE-APACHE file in the root directory
+// of this source tree. You may select, at your option, one of the above-listed
+// licenses.
+
+//! Self-describing envelope around protocol messages
+//!
+//! The serialization of [`BlindedElement`]s, [`EvaluationElement`]s and
+//! [`Proof`]s is a bare fixed-length byte string. An envelope additionally
+//! records the version, cipher suite, mode, message type and batch size, so a
+//! message meant for a different endpoint is rejected with a descriptive
+//! [`Error::Deserialization`] instead of failing to decode as a group element.
+//!
+//! The encoding is:
+//!
+//! ```text
+//! version:      u8 (currently `1`)
+//! suite_len:    u8
+//! suite:        [u8; suite_len] (`CipherSuite::ID`)
+//! mode:         u8 (`Mode::to_u8()`)
+//! message_type: u8 (`1` for requests, `2` for responses)
+//! count:        u16 (big-endian)
+//! payload_len:  u32 (big-endian)
+//! payload:      [u8; payload_len]
+//! ```
+//!
+//! The payload of a request is `count` blinded elements followed by the
+//! optional info, a response contains `count` evaluation elements followed by
+//! the optional proof. Optional fields are preceded by a `0` or `1` byte
+//! indicating their presence, info is additionally prefixed by its length as a
+//! big-endian u16.
+
+use alloc::vec::Vec;
+
+use derive_where::derive_where;
+use generic_array::typenum::Unsigned;
+
+use crate::serialization::take_field;
+use crate::{
+    BlindedElement, BlindedElementLen, CipherSuite, DeserializationReason, Error,
+    EvaluationElement, EvaluationElementLen, Group, Mode, Proof, ProofLen, ProtocolMode, Result,
+};
+
+/// The current version of the envelope format.
+pub const ENVELOPE_VERSION: u8 = 1;
+
+/// The type of message contained in an envelope.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum MessageType {
+    /// Blinded elements sent from the client to the server, see
+    /// [`encode_request()`].
+    Request,
+    /// Evaluation elements sent from the server to the client, see
+    /// [`encode_response()`].
+    Response,
+}
+
+impl MessageType {
+    /// Message type as it is represented in an envelope.
+    pub fn to_u8(self) -> u8 {
+        match self {
+            MessageType::Request => 1,
+            MessageType::Response => 2,
+        }
+    }
+
+    fn from_u8(message_type: u8) -> Option<Self> {
+        match message_type {
+            1 => Some(MessageType::Request),
+            2 => Some(MessageType::Response),
+            _ => None,
+        }
+    }
+}
+
+/// The header of an envelope, which can be inspected before knowing the
+/// [`CipherSuite`] of the message, e.g. to dispatch to the right endpoint.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct EnvelopeHeader<'a> {
+    /// The [`CipherSuite::ID`] of the message.
+    pub suite: &'a [u8],
+    /// The mode of the message.
+    pub mode: Mode,
+    /// The type of the message.
+    pub message_type: MessageType,
+    /// The number of elements in the message.
+    pub count: u16,
+}
+
+impl<'a> EnvelopeHeader<'a> {
+    /// Decodes the header of an envelope and returns it together with the
+    /// payload.
+    ///
+    /// # Errors
+    /// [`Error::Deserialization`] if the header is malformed, has an
+    /// unsupported version or the payload length doesn't match.
+    pub fn decode(mut input: &'a [u8]) -> Result<(Self, &'a [u8])> {
+        let version = take_field(&mut input, 1, "version")?[0];
+
+        if version != ENVELOPE_VERSION {
+            return Err(Error::deserialization(
+                "version",
+                DeserializationReason::Version,
+            ));
+        }
+
+        let suite_len = take_field(&mut input, 1, "suite")?[0];
+        let suite = take_field(&mut input, suite_len.into(), "suite")?;
+        let mode = Mode::from_u8(take_field(&mut input, 1, "mode")?[0])
+            .ok_or(Error::deserialization("mode", DeserializationReason::Mode))?;
+        let message_type =
+            MessageType::from_u8(take_field(&mut input, 1, "message_type")?[0]).ok_or(
+                Error::deserialization("message_type", DeserializationReason::MessageType),
+            )?;
+        let count = take_field(&mut input, 2, "count")?;
+        let count = u16::from_be_bytes([count[0], count[1]]);
+        let payload_len = take_field(&mut input, 4, "payload")?;
+        let payload_len = u32::from_be_bytes([
+            payload_len[0],
+            payload_len[1],
+            payload_len[2],
+            payload_len[3],
+        ]);
+        let payload_len = usize::try_from(payload_len)
+            .map_err(|_| Error::deserialization("payload", DeserializationReason::Encoding))?;
+
+        if input.len() != payload_len {
+            return Err(Error::deserialization(
+                "payload",
+                DeserializationReason::Length {
+                    expected: payload_len,
+                    actual: input.len(),
+                },
+            ));
+        }
+
+        Ok((
+            Self {
+                suite,
+                mode,
+                message_type,
+                count,
+            },
+            input,
+        ))
+    }
+
+    /// Validates that the envelope belongs to `CS` and `M` and contains a
+    /// message of `message_type`.
+    fn check<CS: CipherSuite, M: ProtocolMode>(&self, message_type: MessageType) -> Result<()> {
+        if self.suite != CS::ID.as_bytes() {
+            return Err(Error::deserialization(
+                "suite",
+                DeserializationReason::Suite,
+            ));
+        }
+
+        if self.mode != M::MODE {
+            return Err(Error::deserialization("mode", DeserializationReason::Mode));
+        }
+
+        if self.message_type != message_type {
+            return Err(Error::deserialization(
+                "message_type",
+                DeserializationReason::MessageType,
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// A request decoded by [`decode_request()`].
+#[derive_where(Clone)]
+#[derive_where(Debug, Eq, Hash, PartialEq; <CS::Group as Group>::Elem)]
+pub struct EnvelopeRequest<CS: CipherSuite, M: ProtocolMode> {
+    /// The blinded elements of the request.
+    pub blinded_elements: Vec<BlindedElement<CS, M>>,
+    /// The public info, if any.
+    pub info: Option<Vec<u8>>,
+}
+
+/// A response decoded by [`decode_response()`].
+#[derive_where(Clone)]
+#[derive_where(Debug, Eq, Hash, PartialEq; <CS::Group as Group>::Elem, <CS::Group as Group>::Scalar)]
+pub struct EnvelopeResponse<CS: CipherSuite, M: ProtocolMode> {
+    /// The evaluation elements of the response.
+    pub evaluation_elements: Vec<EvaluationElement<CS, M>>,
+    /// The proof, if any.
+    pub proof: Option<Proof<CS>>,
+}
+
+/// Encodes blinded elements and the optional public info of a client into an
+/// envelope.
+///
+/// # Errors
+/// - [`Error::Batch`] if the number of `blinded_elements` is zero or exceeds
+///   [`u16::MAX`].
+/// - [`Error::Input`] if `info` is longer than [`u16::MAX`].
+pub fn encode_request<CS: CipherSuite, M: ProtocolMode>(
+    blinded_elements: &[BlindedElement<CS, M>],
+    info: Option<&[u8]>,
+) -> Result<Vec<u8>> {
+    let info_len = info
+        .map(|info| u16::try_from(info.len()).map_err(|_| Error::input_too_long(info.len())))
+        .transpose()?;
+    let mut payload = Vec::with_capacity(
+        blinded_elements.len() * BlindedElementLen::<CS>::USIZE + 3 + info.map_or(0, <[u8]>::len),
+    );
+
+    for blinded_element in blinded_elements {
+        payload.extend_from_slice(&blinded_element.serialize());
+    }
+
+    match (info, info_len) {
+        (Some(info), Some(info_len)) => {
+            payload.push(1);
+            payload.extend_from_slice(&info_len.to_be_bytes());
+            payload.extend_from_slice(info);
+        }
+        _ => payload.push(0),
+    }
+
+    encode::<CS, M>(MessageType::Request, blinded_elements.len(), &payload)
+}
+
+/// Decodes an envelope produced by [`encode_request()`].
+///
+/// # Errors
+/// [`Error::Deserialization`] if `input` is malformed, belongs to a different
+/// [`CipherSuite`] or [`ProtocolMode`] or doesn't contain a request.
+pub fn decode_request<CS: CipherSuite, M: ProtocolMode>(
+    input: &[u8],
+) -> Result<EnvelopeRequest<CS, M>> {
+    let (header, mut payload) = EnvelopeHeader::decode(input)?;
+    header.check::<CS, M>(MessageType::Request)?;
+
+    let blinded_elements = (0..header.count)
+        .map(|_| {
+            let bytes = take_field(
+                &mut payload,
+                BlindedElementLen::<CS>::USIZE,
+                "blinded_element",
+            )?;
+            BlindedElement::deserialize(bytes)
+        })
+        .collect::<Result<_>>()?;
+
+    let info = if take_present(&mut payload, "info")? {
+        let info_len = take_field(&mut payload, 2, "info")?;
+        let info_len = u16::from_be_bytes([info_len[0], info_len[1]]);
+        Some(take_field(&mut payload, info_len.into(), "info")?.to_vec())
+    } else {
+        None
+    };
+
+    check_empty(payload)?;
+
+    Ok(EnvelopeRequest {
+        blinded_elements,
+        info,
+    })
+}
+
+/// Encodes evaluation elements and the optional proof of a server into an
+/// envelope.
+///
+/// # Errors
+/// [`Error::Batch`] if the number of `evaluation_elements` is zero or exceeds
+/// [`u16::MAX`].
+pub fn encode_response<CS: CipherSuite, M: ProtocolMode>(
+    evaluation_elements: &[EvaluationElement<CS, M>],
+    proof: Option<&Proof<CS>>,
+) -> Result<Vec<u8>> {
+    let mut payload = Vec::with_capacity(
+        evaluation_elements.len() * EvaluationElementLen::<CS>::USIZE + 1 + ProofLen::<CS>::USIZE,
+    );
+
+    for evaluation_element in evaluation_elements {
+        payload.extend_from_slice(&evaluation_element.serialize());
+    }
+
+    if let Some(proof) = proof {
+        payload.push(1);
+        payload.extend_from_slice(&proof.serialize());
+    } else {
+        payload.push(0);
+    }
+
+    encode::<CS, M>(MessageType::Response, evaluation_elements.len(), &payload)
+}
+
+/// Decodes an envelope produced by [`encode_response()`].
+///
+/// # Errors
+/// [`Error::Deserialization`] if `input` is malformed, belongs to a different
+/// [`CipherSuite`] or [`ProtocolMode`] or doesn't contain a response.
+pub fn decode_response<CS: CipherSuite, M: ProtocolMode>(
+    input: &[u8],
+) -> Result<EnvelopeResponse<CS, M>> {
+    let (header, mut payload) = EnvelopeHeader::decode(input)?;
+    header.check::<CS, M>(MessageType::Response)?;
+
+    let evaluation_elements = (0..header.count)
+        .map(|_| {
+            let bytes = take_field(
+                &mut payload,
+                EvaluationElementLen::<CS>::USIZE,
+                "evaluation_element",
+            )?;
+            EvaluationElement::deserialize(bytes)
+        })
+        .collect::<Result<_>>()?;
+
+    let proof = if take_present(&mut payload, "proof")? {
+        let bytes = take_field(&mut payload, ProofLen::<CS>::USIZE, "proof")?;
+        Some(Proof::deserialize(bytes)?)
+    } else {
+        None
+    };
+
+    check_empty(payload)?;
+
+    Ok(EnvelopeResponse {
+        evaluation_elements,
+        proof,
+    })
+}
+
+/////////////////////
+// Inner functions //
+// =============== //
+/////////////////////
+
+fn encode<CS: CipherSuite, M: ProtocolMode>(
+    message_type: MessageType,
+    count: usize,
+    payload: &[u8],
+) -> Result<Vec<u8>> {
+    let count = match u16::try_from(count) {
+        Ok(0) | Err(_) => return Err(Error::Batch),
+        Ok(count) => count,
+    };
+    let suite = CS::ID.as_bytes();
+    let suite_len = u8::try_from(suite.len()).map_err(|_| Error::input_too_long(suite.len()))?;
+    let payload_len = u32::try_from(payload.len()).map_err(|_| Error::Batch)?;
+
+    let mut output = Vec::with_capacity(10 + suite.len() + payload.len());
+    output.push(ENVELOPE_VERSION);
+    output.push(suite_len);
+    output.extend_from_slice(suite);
+    output.push(M::MODE.to_u8());
+    output.push(message_type.to_u8());
+    output.extend_from_slice(&count.to_be_bytes());
+    output.extend_from_slice(&payload_len.to_be_bytes());
+    output.extend_from_slice(payload);
+
+    Ok(output)
+}
+
+/// Takes the byte indicating the presence of an optional `field`.
+fn take_present(payload: &mut &[u8], field: &'static str) -> Result<bool> {
+    match take_field(payload, 1, field)?[0] {
+        0 => Ok(false),
+        1 => Ok(true),
+        _ => Err(Error::deserialization(
+            field,
+            DeserializationReason::Encoding,
+        )),
+    }
+}
+
+fn check_empty(payload: &[u8]) -> Result<()> {
+    if payload.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::deserialization(
+            "payload",
+            DeserializationReason::Encoding,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use core::slice;
+
+    use ::alloc::vec;
+    use rand::rngs::OsRng;
+
+    use super::*;
+    use crate::{
+        DeserializationError, InputError, OprfClient, OprfMode, OprfServer, PoprfClient, PoprfMode,
+        VoprfClient, VoprfMode, VoprfServer,
+    };
+
+    fn request_round_trip<CS: CipherSuite>() {
+        let mut rng = OsRng;
+        let blinded_elements: Vec<_> = (0..3)
+            .map(|_| {
+                PoprfClient::<CS>::blind(b"input", &mut rng)
+                    .unwrap()
+                    .message
+            })
+            .collect();
+
+        for info in [None, Some(&b""[..]), Some(&b"info"[..])] {
+            let bytes = encode_request(&blinded_elements, info).unwrap();
+            let (header, _) = EnvelopeHeader::decode(&bytes).unwrap();
+            assert_eq!(header.suite, CS::ID.as_bytes());
+            assert_eq!(header.mode, Mode::Poprf);
+            assert_eq!(header.message_type, MessageType::Request);
+            assert_eq!(header.count, 3);
+
+            let request = decode_request::<CS, PoprfMode>(&bytes).unwrap();
+            assert!(request
+                .blinded_elements
+                .iter()
+                .zip(&blinded_elements)
+                .all(|(a, b)| a.serialize() == b.serialize()));
+            assert_eq!(request.blinded_elements.len(), 3);
+            assert_eq!(request.info.as_deref(), info);
+        }
+    }
+
+    fn response_round_trip<CS: CipherSuite>() {
+        let mut rng = OsRng;
+        let server = VoprfServer::<CS>::new(&mut rng).unwrap();
+        let client_blind_result = VoprfClient::<CS>::blind(b"input", &mut rng).unwrap();
+        let server_result = server.blind_evaluate(&mut rng, &client_blind_result.message);
+
+        let bytes = encode_response(
+            slice::from_ref(&server_result.message),
+            Some(&server_result.proof),
+        )
+        .unwrap();
+        let response = decode_response::<CS, VoprfMode>(&bytes).unwrap();
+        assert_eq!(
+            response.evaluation_elements[0].serialize(),
+            server_result.message.serialize()
+        );
+        let proof = response.proof.unwrap();
+        assert_eq!(proof.serialize(), server_result.proof.serialize());
+
+        client_blind_result
+            .state
+            .finalize(
+                b"input",
+                &response.evaluation_elements[0],
+                &proof,
+                &server.get_public_key(),
+            )
+            .unwrap();
+
+        let server = OprfServer::<CS>::new(&mut rng).unwrap();
+        let client_blind_result = OprfClient::<CS>::blind(b"input", &mut rng).unwrap();
+        let message = server.blind_evaluate(&client_blind_result.message);
+
+        let bytes = encode_response(&[message], None).unwrap();
+        let response = decode_response::<CS, OprfMode>(&bytes).unwrap();
+        assert!(response.proof.is_none());
+
+        client_blind_result
+            .state
+            .finalize(b"input", &response.evaluation_elements[0])
+            .unwrap();
+    }
+
+    fn envelope_errors<CS: CipherSuite>() {
+        fn reason<T>(result: Result<T>) -> Option<DeserializationError> {
+            match result {
+                Err(Error::Deserialization(error)) => Some(error),
+                _ => None,
+            }
+        }
+
+        let mut rng = OsRng;
+        let blinded_element = VoprfClient::<CS>::blind(b"input", &mut rng)
+            .unwrap()
+            .message;
+        let bytes = encode_request(slice::from_ref(&blinded_element), None).unwrap();
+        let suite_len = CS::ID.len();
+
+        assert!(matches!(
+            encode_request::<CS, VoprfMode>(&[], None),
+            Err(Error::Batch)
+        ));
+        assert!(matches!(
+            encode_request(&[blinded_element], Some(&vec![0; 0x10000])),
+            Err(Error::Input(InputError::TooLong { len: 0x10000 }))
+        ));
+
+        let mut bad = bytes.clone();
+        bad[0] = 2;
+        assert_eq!(
+            reason(decode_request::<CS, VoprfMode>(&bad))
+                .unwrap()
+                .reason,
+            DeserializationReason::Version
+        );
+
+        let mut bad = bytes.clone();
+        bad[2] ^= 1;
+        assert_eq!(
+            reason(decode_request::<CS, VoprfMode>(&bad))
+                .unwrap()
+                .reason,
+            DeserializationReason::Suite
+        );
+
+        let mut bad = bytes.clone();
+        bad[2 + suite_len] = 3;
+        assert_eq!(
+            reason(decode_request::<CS, VoprfMode>(&bad))
+                .unwrap()
+                .reason,
+            DeserializationReason::Mode
+        );
+        assert_eq!(
+            reason(decode_request::<CS, PoprfMode>(&bytes)).unwrap(),
+            DeserializationError {
+                field: "mode",
+                reason: DeserializationReason::Mode,
+            }
+        );
+
+        assert_eq!(
+            reason(decode_response::<CS, VoprfMode>(&bytes))
+                .unwrap()
+                .reason,
+            DeserializationReason::MessageType
+        );
+
+        let mut bad = bytes.clone();
+        bad.push(0);
+        assert_eq!(
+            reason(decode_request::<CS, VoprfMode>(&bad)).unwrap().field,
+            "payload"
+        );
+
+        let mut bad = bytes.clone();
+        // Claim two elements with only one present.
+        bad[5 + suite_len] = 2;
+        assert_eq!(
+            reason(decode_request::<CS, VoprfMode>(&bad)).unwrap().field,
+            "blinded_element"
+        );
+
+        let mut bad = bytes;
+        *bad.last_mut().unwrap() = 2;
+        assert_eq!(
+            reason(decode_request::<CS, VoprfMode>(&bad)).unwrap(),
+            DeserializationError {
+                field: "info",
+                reason: DeserializationReason::Encoding,
+            }
+        );
+    }
+
+    #[test]
+    fn test_functionality() {
+        use p256::NistP256;
+        use p384::NistP384;
+        use p521::NistP521;
+
+        #[cfg(feature = "ristretto255")]
+        {
+            use crate::Ristretto255;
+
+            request_round_trip::<Ristretto255>();
+            response_round_trip::<Ristretto255>();
+            envelope_errors::<Ristretto255>();
+        }
+
+        request_round_trip::<NistP256>();
+        response_round_trip::<NistP256>();
+        envelope_errors::<NistP256>();
+
+        request_round_trip::<NistP384>();
+        response_round_trip::<NistP384>();
+        envelope_errors::<NistP384>();
+
+        request_round_trip::<NistP521>();
+        response_round_trip::<NistP521>();
+        envelope_errors::<NistP521>();
+    }
+
+    #[test]
+    fn test_suite_mismatch() {
+        #[cfg(feature = "ristretto255")]
+        {
+            use p256::NistP256;
+
+            use crate::Ristretto255;
+
+            let blinded_element = VoprfClient::<NistP256>::blind(b"input", &mut OsRng)
+                .unwrap()
+                .message;
+            let bytes = encode_request(&[blinded_element], None).unwrap();
+
+            assert!(matches!(
+                decode_request::<Ristretto255, VoprfMode>(&bytes),
+                Err(Error::Deserialization(DeserializationError {
+                    field: "suite",
+                    reason: DeserializationReason::Suite,
+                }))
+            ));
+        }
+    }
+}
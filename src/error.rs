@@ -13,23 +13,187 @@ pub type Result<T, E = Error> = core::result::Result<T, E>;
 
 /// Represents an error in the manipulation of internal cryptographic data
 #[derive(Clone, Copy, Debug, displaydoc::Display, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[non_exhaustive]
 pub enum Error {
     /// Size of info is longer then [`u16::MAX`].
     Info,
-    /// Size of input is empty or longer then [`u16::MAX`].
-    Input,
+    /// The info doesn't match the info a
+    /// [`PoprfPreparedTweakedKey`](crate::PoprfPreparedTweakedKey) was prepared
+    /// with.
+    TweakMismatch,
+    /// Invalid input: {0}
+    Input(InputError),
     /// Size of info and seed together are longer then `u16::MAX - 3`.
     DeriveKeyPair,
-    /// Failure to deserialize bytes
-    Deserialization,
+    /// Failure to deserialize {0}
+    Deserialization(DeserializationError),
     /// Batched items are more then [`u16::MAX`] or length don't match.
     Batch,
     /// In verifiable mode, occurs when the proof failed to verify
     ProofVerification,
-    /// The protocol has failed and can't be completed.
-    Protocol,
+    /// The protocol has failed and can't be completed: {0}
+    Protocol(ProtocolError),
     /// Random number generator failure.
     Rng,
+    /// Requested length of an expanded [`OprfOutput`](crate::OprfOutput) is
+    /// longer then 255 times the hash output size.
+    ExpandLength,
+    /// The [`CipherSuite`](crate::CipherSuite) doesn't support the requested
+    /// key format.
+    UnsupportedSuite,
+    /// Invalid password hashing parameters for sealing a key.
+    SealParams,
+    /// Failed to unseal a key, either the password is wrong or the sealed key
+    /// was modified.
+    Unseal,
+    /// An [`OprfKeyProvider`](crate::OprfKeyProvider) failed to perform an
+    /// operation with the private key.
+    KeyProvider,
+    /// No key with the requested key ID is in the key set.
+    UnknownKeyId,
+    /// A key with the same key ID is already in the key set.
+    DuplicateKeyId,
+    /// The requested key isn't active at the requested time, or no key of the
+    /// key set is.
+    InactiveKey,
+    /// The [`KeyValidity`](crate::KeyValidity) window is empty, as the key
+    /// expires before it activates.
+    KeyValidity,
+    /// Output buffer of {actual} bytes is shorter then the {expected} bytes to
+    /// serialize.
+    BufferLength {
+        /// Length of the serialized value.
+        expected: usize,
+        /// Length of the output buffer.
+        actual: usize,
+    },
+}
+
+/// Reason of an [`Error::Input`].
+#[derive(Clone, Copy, Debug, displaydoc::Display, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[non_exhaustive]
+pub enum InputError {
+    /// the input is empty
+    Empty,
+    /// the input is {len} bytes long, which is longer then `u16::MAX`
+    TooLong {
+        /// Length of the input.
+        len: usize,
+    },
+    /// the input can't be hashed to a valid group element
+    HashToGroup,
+}
+
+/// Reason of an [`Error::Deserialization`], together with the field which
+/// failed to deserialize.
+#[derive(Clone, Copy, Debug, displaydoc::Display, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[displaydoc("`{field}`: {reason}")]
+pub struct DeserializationError {
+    /// Name of the field which failed to deserialize.
+    pub field: &'static str,
+    /// Why the field failed to deserialize.
+    pub reason: DeserializationReason,
+}
+
+/// Why a field failed to deserialize, see [`DeserializationError`].
+#[derive(Clone, Copy, Debug, displaydoc::Display, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[non_exhaustive]
+pub enum DeserializationReason {
+    /// expected {expected} bytes, but got {actual}
+    Length {
+        /// Expected length in bytes.
+        expected: usize,
+        /// Length of the given bytes.
+        actual: usize,
+    },
+    /// not a valid non-zero scalar
+    Scalar,
+    /// not a valid non-identity group element
+    Element,
+    /// not a valid encoding
+    Encoding,
+    /// unsupported format version
+    Version,
+    /// message belongs to a different cipher suite
+    Suite,
+    /// unknown mode
+    Mode,
+    /// unexpected message type
+    MessageType,
+    /// checksum mismatch
+    Checksum,
+    /// public key doesn't match the private key
+    KeyMismatch,
+}
+
+/// Reason of an [`Error::Protocol`].
+#[derive(Clone, Copy, Debug, displaydoc::Display, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[non_exhaustive]
+pub enum ProtocolError {
+    /// the tweak of the private key is zero
+    ZeroTweak,
+    /// the tweaked public key is the identity element
+    IdentityTweakedKey,
+    /// no valid private key could be derived from the seed
+    DeriveKeyPairExhausted,
+}
+
+impl Error {
+    pub(crate) fn input_too_long(len: usize) -> Self {
+        Self::Input(InputError::TooLong { len })
+    }
+
+    pub(crate) fn deserialization(field: &'static str, reason: DeserializationReason) -> Self {
+        Self::Deserialization(DeserializationError { field, reason })
+    }
+
+    /// Replaces the field name of an [`Error::Deserialization`].
+    pub(crate) fn field(self, field: &'static str) -> Self {
+        match self {
+            Self::Deserialization(error) => Self::deserialization(field, error.reason),
+            error => error,
+        }
+    }
+}
+
+/// An [`Error`] in a batch operation, together with the index of the item
+/// which caused it.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct BatchError {
+    /// Index of the item in the batch which caused the error, or [`None`] if
+    /// the error concerns the whole batch, e.g. [`Error::ProofVerification`].
+    ///
+    /// The item is an element for batches of elements. For batches of server
+    /// responses, e.g. in
+    /// [`VoprfServer::verify_server_responses`](crate::VoprfServer::verify_server_responses),
+    /// it is a whole response.
+    pub index: Option<usize>,
+    /// The reason of the failure.
+    pub error: Error,
+}
+
+impl BatchError {
+    pub(crate) fn new(index: usize, error: Error) -> Self {
+        Self {
+            index: Some(index),
+            error,
+        }
+    }
+}
+
+impl From<Error> for BatchError {
+    fn from(error: Error) -> Self {
+        Self { index: None, error }
+    }
+}
+
+impl core::fmt::Display for BatchError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self.index {
+            Some(index) => write!(f, "Item {index} of the batch: {}", self.error),
+            None => self.error.fmt(f),
+        }
+    }
 }
 
 /// Only used to implement [`Group`](crate::Group).
@@ -42,3 +206,9 @@ pub enum InternalError {
 }
 
 impl core::error::Error for Error {}
+
+impl core::error::Error for BatchError {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
@@ -6,6 +6,8 @@
 // of this source tree. You may select, at your option, one of the above-listed
 // licenses.
 
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
 use core::num::NonZeroU32;
 use core::ops::Add;
 
@@ -23,7 +25,7 @@ use generic_array::{ArrayLength, GenericArray};
 use rand_core::{TryCryptoRng, TryRngCore};
 
 use super::Group;
-use crate::{Error, InternalError, Result};
+use crate::{DeserializationReason, Error, InternalError, Result};
 
 type ElemLen<C> = <ScalarLen<C> as ModulusSize>::CompressedPointSize;
 type ScalarLen<C> = FieldBytesSize<C>;
@@ -88,10 +90,15 @@ where
         result
     }
 
+    #[cfg(feature = "alloc")]
+    fn serialize_elem_uncompressed(elem: Self::Elem) -> Option<Vec<u8>> {
+        Some(elem.to_encoded_point(false).as_bytes().to_vec())
+    }
+
     fn deserialize_elem(element_bits: &[u8]) -> Result<Self::Elem> {
         PublicKey::<Self>::from_sec1_bytes(element_bits)
             .map(|public_key| public_key.to_projective())
-            .map_err(|_| Error::Deserialization)
+            .map_err(|_| Error::deserialization("element", DeserializationReason::Element))
     }
 
     fn random_scalar<R: TryRngCore + TryCryptoRng>(rng: &mut R) -> Result<Self::Scalar> {
@@ -121,7 +128,7 @@ where
     fn deserialize_scalar(scalar_bits: &[u8]) -> Result<Self::Scalar> {
         SecretKey::<Self>::from_slice(scalar_bits)
             .map(|secret_key| *secret_key.to_nonzero_scalar())
-            .map_err(|_| Error::Deserialization)
+            .map_err(|_| Error::deserialization("scalar", DeserializationReason::Scalar))
     }
 }
 
@@ -12,6 +12,8 @@ mod elliptic_curve;
 #[cfg(feature = "ristretto255")]
 mod ristretto;
 
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
 use core::ops::{Add, Mul, Sub};
 
 use digest::core_api::BlockSizeUser;
@@ -92,6 +94,18 @@ where
     /// Serializes the `self` group element
     fn serialize_elem(elem: Self::Elem) -> GenericArray<u8, Self::ElemLen>;
 
+    /// Serializes the `self` group element uncompressed, e.g. as uncompressed
+    /// SEC1 encoding for elliptic curves. Returns [`None`] if the group has no
+    /// uncompressed representation, which is the default.
+    ///
+    /// [`Group::deserialize_elem()`] is expected to accept this
+    /// representation too.
+    #[cfg(feature = "alloc")]
+    fn serialize_elem_uncompressed(elem: Self::Elem) -> Option<Vec<u8>> {
+        let _ = elem;
+        None
+    }
+
     /// Return an element from its fixed-length bytes representation. If the
     /// element is the identity element, return an error.
     ///
@@ -13,13 +13,13 @@ use curve25519_dalek::traits::Identity;
 use digest::core_api::BlockSizeUser;
 use digest::{FixedOutput, HashMarker};
 use elliptic_curve::hash2curve::{ExpandMsg, ExpandMsgXmd, Expander};
-use generic_array::typenum::{IsLess, IsLessOrEqual, U256, U32, U64};
+use generic_array::typenum::{IsLess, IsLessOrEqual, Unsigned, U256, U32, U64};
 use generic_array::GenericArray;
 use rand_core::{TryCryptoRng, TryRngCore};
 use subtle::ConstantTimeEq;
 
 use super::Group;
-use crate::{Error, InternalError, Result};
+use crate::{DeserializationReason, Error, InternalError, Result};
 
 /// [`Group`] implementation for Ristretto255.
 #[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
@@ -88,10 +88,21 @@ impl Group for Ristretto255 {
 
     fn deserialize_elem(element_bits: &[u8]) -> Result<Self::Elem> {
         CompressedRistretto::from_slice(element_bits)
-            .map_err(|_| Error::Deserialization)?
+            .map_err(|_| {
+                Error::deserialization(
+                    "element",
+                    DeserializationReason::Length {
+                        expected: Self::ElemLen::USIZE,
+                        actual: element_bits.len(),
+                    },
+                )
+            })?
             .decompress()
             .filter(|point| point != &RistrettoPoint::identity())
-            .ok_or(Error::Deserialization)
+            .ok_or(Error::deserialization(
+                "element",
+                DeserializationReason::Element,
+            ))
     }
 
     fn random_scalar<R: TryRngCore + TryCryptoRng>(rng: &mut R) -> Result<Self::Scalar> {
@@ -124,11 +135,21 @@ impl Group for Ristretto255 {
     }
 
     fn deserialize_scalar(scalar_bits: &[u8]) -> Result<Self::Scalar> {
-        scalar_bits
-            .try_into()
-            .ok()
-            .and_then(|bytes| Scalar::from_canonical_bytes(bytes).into())
+        let bytes = scalar_bits.try_into().map_err(|_| {
+            Error::deserialization(
+                "scalar",
+                DeserializationReason::Length {
+                    expected: Self::ScalarLen::USIZE,
+                    actual: scalar_bits.len(),
+                },
+            )
+        })?;
+
+        Option::from(Scalar::from_canonical_bytes(bytes))
             .filter(|scalar| scalar != &Scalar::ZERO)
-            .ok_or(Error::Deserialization)
+            .ok_or(Error::deserialization(
+                "scalar",
+                DeserializationReason::Scalar,
+            ))
     }
 }
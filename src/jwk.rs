@@ -0,0 +1,393 @@
+// Copyright (c) Meta Platforms, Inc. and affiliates.
+//
+// This source code is dual-licensed under either the MIT license found in the
+// LICENSE-MIT file in the root directory of this source tree or the Apache
+// License, Version 2.0 found in the LICENSE-APACHE file in the root directory
+// of this source tree. You may select, at your option, one of the above-listed
+// licenses.
+
+//! JSON Web Key ([RFC 7517](https://www.rfc-editor.org/rfc/rfc7517)) encodings
+//! of keys
+//!
+//! Only keys of the NIST suites can be encoded, with key type `EC` and their
+//! registered curve
+//! ([RFC 7518](https://www.rfc-editor.org/rfc/rfc7518#section-6.2)), as JOSE
+//! registers no curve for the other suites. The algorithm is
+//! [`CipherSuite::ID`] and the key ID is the fingerprint of the public key.
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::{self, Debug, Formatter};
+
+use generic_array::typenum::Unsigned;
+use zeroize::{Zeroize, Zeroizing};
+
+use crate::ciphersuite::NistCurve;
+use crate::serialization::base64::{self, Alphabet};
+use crate::{CipherSuite, DeserializationReason, Error, Group, PrivateKey, PublicKey, Result};
+
+///////////////
+// Constants //
+// ========= //
+///////////////
+
+const KTY_EC: &str = "EC";
+
+////////////////////////////
+// High-level API Structs //
+// ====================== //
+////////////////////////////
+
+/// A JSON Web Key, see [RFC 7517](https://www.rfc-editor.org/rfc/rfc7517).
+///
+/// Binary parameters are encoded in unpadded Base64url. The private key `d` is
+/// redacted in the [`Debug`] output and zeroized on drop. With the `serde`
+/// feature this can be serialized to and from JSON, absent parameters are
+/// omitted.
+#[derive(Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Jwk {
+    /// The key type, `EC`.
+    pub kty: String,
+    /// The curve.
+    pub crv: String,
+    /// The x-coordinate.
+    pub x: String,
+    /// The y-coordinate.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub y: Option<String>,
+    /// The private key.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub d: Option<String>,
+    /// The algorithm, the [`CipherSuite::ID`].
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub alg: Option<String>,
+    /// The key ID.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub kid: Option<String>,
+}
+
+impl Debug for Jwk {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Jwk")
+            .field("kty", &self.kty)
+            .field("crv", &self.crv)
+            .field("x", &self.x)
+            .field("y", &self.y)
+            .field("d", &self.d.as_ref().map(|_| "[REDACTED]"))
+            .field("alg", &self.alg)
+            .field("kid", &self.kid)
+            .finish()
+    }
+}
+
+impl Drop for Jwk {
+    fn drop(&mut self) {
+        self.d.zeroize();
+    }
+}
+
+impl<CS: CipherSuite> PublicKey<CS> {
+    /// Encodes this key as a [`Jwk`] with key type `EC`.
+    ///
+    /// # Errors
+    /// [`Error::UnsupportedSuite`] if `CS` isn't a NIST suite.
+    pub fn to_jwk(&self) -> Result<Jwk> {
+        let curve = NistCurve::of::<CS>().ok_or(Error::UnsupportedSuite)?;
+        // Uncompressed SEC1 encoding: a tag followed by x and y.
+        let pk = CS::Group::serialize_elem_uncompressed(self.0)
+            .expect("NIST suites support uncompressed points");
+        let (x, y) = pk[1..].split_at((pk.len() - 1) / 2);
+
+        Ok(Jwk {
+            kty: KTY_EC.to_string(),
+            crv: curve.jwk_name().to_string(),
+            x: base64::encode(x, Alphabet::Url),
+            y: Some(base64::encode(y, Alphabet::Url)),
+            d: None,
+            alg: Some(CS::ID.to_string()),
+            kid: Some(base64::encode(&self.fingerprint(), Alphabet::Url)),
+        })
+    }
+
+    /// Decodes the public key of a [`Jwk`]. The key ID and the private key, if
+    /// present, are ignored.
+    ///
+    /// # Errors
+    /// - [`Error::UnsupportedSuite`] if `CS` isn't a NIST suite.
+    /// - [`Error::Deserialization`] if failed to decode `jwk`, or if the key
+    ///   type, curve or algorithm doesn't match the [`CipherSuite`].
+    pub fn from_jwk(jwk: &Jwk) -> Result<Self> {
+        let curve = NistCurve::of::<CS>().ok_or(Error::UnsupportedSuite)?;
+
+        if jwk.alg.as_ref().is_some_and(|alg| alg != CS::ID)
+            || jwk.kty != KTY_EC
+            || jwk.crv != curve.jwk_name()
+        {
+            return Err(suite_mismatch());
+        }
+
+        let coordinate_len = <CS::Group as Group>::ElemLen::USIZE - 1;
+        let x = decode(&jwk.x, coordinate_len)?;
+        let y = decode(jwk.y.as_ref().ok_or_else(encoding)?, coordinate_len)?;
+
+        let mut pk = Vec::with_capacity(1 + 2 * coordinate_len);
+        pk.push(0x04);
+        pk.extend_from_slice(&x);
+        pk.extend_from_slice(&y);
+
+        CS::Group::deserialize_elem(&pk)
+            .map(Self)
+            .map_err(|error| error.field("jwk"))
+    }
+}
+
+impl<CS: CipherSuite> PrivateKey<CS> {
+    /// Encodes this key as a [`Jwk`] including the public key, see
+    /// [`PublicKey::to_jwk()`].
+    ///
+    /// # Errors
+    /// [`Error::UnsupportedSuite`] if `CS` isn't a NIST suite.
+    pub fn to_jwk(&self) -> Result<Jwk> {
+        let mut jwk = self.public_key().to_jwk()?;
+        let mut sk = CS::Group::serialize_scalar(self.0);
+        jwk.d = Some(base64::encode(&sk, Alphabet::Url));
+        sk[..].zeroize();
+
+        Ok(jwk)
+    }
+
+    /// Decodes the private key of a [`Jwk`].
+    ///
+    /// # Errors
+    /// - [`Error::UnsupportedSuite`] if `CS` isn't a NIST suite.
+    /// - [`Error::Deserialization`] if failed to decode `jwk`, if the key
+    ///   type, curve or algorithm doesn't match the [`CipherSuite`] or if the
+    ///   public key doesn't match the private key.
+    pub fn from_jwk(jwk: &Jwk) -> Result<Self> {
+        let pk = PublicKey::<CS>::from_jwk(jwk)?;
+        let sk = decode(
+            jwk.d.as_ref().ok_or_else(encoding)?,
+            <CS::Group as Group>::ScalarLen::USIZE,
+        )?;
+        let sk =
+            PrivateKey(CS::Group::deserialize_scalar(&sk).map_err(|error| error.field("jwk"))?);
+
+        if sk.public_key() != pk {
+            return Err(Error::deserialization(
+                "jwk",
+                DeserializationReason::KeyMismatch,
+            ));
+        }
+
+        Ok(sk)
+    }
+}
+
+/////////////////////
+// Inner functions //
+// =============== //
+/////////////////////
+
+fn encoding() -> Error {
+    Error::deserialization("jwk", DeserializationReason::Encoding)
+}
+
+fn suite_mismatch() -> Error {
+    Error::deserialization("jwk", DeserializationReason::Suite)
+}
+
+/// Decodes an unpadded Base64url parameter of exactly `len` bytes.
+fn decode(input: &str, len: usize) -> Result<Zeroizing<Vec<u8>>> {
+    let output = base64::decode(input, Alphabet::Url).ok_or_else(encoding)?;
+
+    if output.len() != len {
+        return Err(Error::deserialization(
+            "jwk",
+            DeserializationReason::Length {
+                expected: len,
+                actual: output.len(),
+            },
+        ));
+    }
+
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use core::fmt::Debug;
+
+    use rand::rngs::OsRng;
+
+    use super::*;
+    use crate::VoprfServer;
+
+    fn jwk<CS: CipherSuite>()
+    where
+        <CS::Group as Group>::Elem: Debug,
+    {
+        let server = VoprfServer::<CS>::new(&mut OsRng).unwrap();
+        let sk = server.get_private_key();
+        let pk = server.get_public_key();
+
+        if NistCurve::of::<CS>().is_none() {
+            assert_eq!(pk.to_jwk().unwrap_err(), Error::UnsupportedSuite);
+            assert_eq!(sk.to_jwk().unwrap_err(), Error::UnsupportedSuite);
+
+            let jwk = Jwk {
+                kty: "OKP".into(),
+                crv: CS::ID.into(),
+                x: base64::encode(&pk.serialize(), Alphabet::Url),
+                y: None,
+                d: None,
+                alg: Some(CS::ID.into()),
+                kid: None,
+            };
+            assert_eq!(
+                PublicKey::<CS>::from_jwk(&jwk).unwrap_err(),
+                Error::UnsupportedSuite
+            );
+            assert_eq!(
+                PrivateKey::<CS>::from_jwk(&jwk).unwrap_err(),
+                Error::UnsupportedSuite
+            );
+            return;
+        }
+
+        let curve = NistCurve::of::<CS>().unwrap();
+        let jwk = pk.to_jwk().unwrap();
+        assert_eq!(jwk.alg.as_deref(), Some(CS::ID));
+        assert_eq!(
+            jwk.kid,
+            Some(base64::encode(&pk.fingerprint(), Alphabet::Url))
+        );
+        assert_eq!(jwk.d, None);
+        assert_eq!(PublicKey::<CS>::from_jwk(&jwk).unwrap(), pk);
+        assert!(PrivateKey::<CS>::from_jwk(&jwk).is_err());
+
+        assert_eq!(jwk.kty, "EC");
+        assert_eq!(jwk.crv, curve.jwk_name());
+        // The compressed encoding contains x after the tag.
+        assert_eq!(
+            base64::decode(&jwk.x, Alphabet::Url).unwrap().as_slice(),
+            &pk.serialize()[1..]
+        );
+
+        let jwk = sk.to_jwk().unwrap();
+        assert_eq!(
+            base64::decode(jwk.d.as_ref().unwrap(), Alphabet::Url)
+                .unwrap()
+                .as_slice(),
+            &sk.serialize()[..]
+        );
+        assert_eq!(PrivateKey::<CS>::from_jwk(&jwk).unwrap(), sk);
+        assert_eq!(PublicKey::<CS>::from_jwk(&jwk).unwrap(), pk);
+        assert!(!::alloc::format!("{jwk:?}").contains(jwk.d.as_ref().unwrap().as_str()));
+
+        // Optional parameters.
+        let mut optional = jwk.clone();
+        optional.alg = None;
+        optional.kid = None;
+        assert_eq!(PrivateKey::<CS>::from_jwk(&optional).unwrap(), sk);
+
+        let mut bad = jwk.clone();
+        bad.alg = Some("ES256".into());
+        assert_eq!(
+            PrivateKey::<CS>::from_jwk(&bad).unwrap_err(),
+            Error::deserialization("jwk", DeserializationReason::Suite)
+        );
+
+        let mut bad = jwk.clone();
+        bad.crv = "X25519".into();
+        assert_eq!(
+            PrivateKey::<CS>::from_jwk(&bad).unwrap_err(),
+            Error::deserialization("jwk", DeserializationReason::Suite)
+        );
+
+        let mut bad = jwk.clone();
+        bad.x.push('A');
+        assert!(PublicKey::<CS>::from_jwk(&bad).is_err());
+
+        let mut bad = jwk.clone();
+        bad.x.pop();
+        assert!(PublicKey::<CS>::from_jwk(&bad).is_err());
+
+        // A private key that doesn't match the public key.
+        let mut bad = jwk.clone();
+        bad.d = VoprfServer::<CS>::new(&mut OsRng)
+            .unwrap()
+            .get_private_key()
+            .to_jwk()
+            .unwrap()
+            .d
+            .clone();
+        assert_eq!(
+            PrivateKey::<CS>::from_jwk(&bad).unwrap_err(),
+            Error::deserialization("jwk", DeserializationReason::KeyMismatch)
+        );
+
+        #[cfg(feature = "serde")]
+        {
+            let json = serde_json::to_string(&pk.to_jwk().unwrap()).unwrap();
+            assert!(!json.contains("\"d\""));
+            let jwk: Jwk = serde_json::from_str(&json).unwrap();
+            assert_eq!(PublicKey::<CS>::from_jwk(&jwk).unwrap(), pk);
+
+            let json = serde_json::to_string(&sk.to_jwk().unwrap()).unwrap();
+            let jwk: Jwk = serde_json::from_str(&json).unwrap();
+            assert_eq!(PrivateKey::<CS>::from_jwk(&jwk).unwrap(), sk);
+        }
+    }
+
+    #[test]
+    fn test_functionality() {
+        use p256::NistP256;
+        use p384::NistP384;
+        use p521::NistP521;
+
+        #[cfg(feature = "ristretto255")]
+        {
+            use crate::Ristretto255;
+
+            jwk::<Ristretto255>();
+        }
+
+        jwk::<NistP256>();
+        jwk::<NistP384>();
+        jwk::<NistP521>();
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_p256() {
+        use p256::NistP256;
+
+        // RFC 7517, Appendix A.1 with the suite as algorithm.
+        let json = r#"{
+            "kty": "EC",
+            "crv": "P-256",
+            "x": "MKBCTNIcKUSDii11ySs3526iDZ8AiTo7Tu6KPAqv7D4",
+            "y": "4Etl6SRW2YiLUrN5vfvVHuhp7x8PxltmWWlbbM4IFyM",
+            "alg": "P256-SHA256",
+            "kid": "1"
+        }"#;
+        let jwk: Jwk = serde_json::from_str(json).unwrap();
+        let pk = PublicKey::<NistP256>::from_jwk(&jwk).unwrap();
+        let exported = pk.to_jwk().unwrap();
+        assert_eq!(exported.x, jwk.x);
+        assert_eq!(exported.y, jwk.y);
+    }
+}
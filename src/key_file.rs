@@ -0,0 +1,420 @@
+// Copyright (c) Meta Platforms, Inc. and affiliates.
+//
+// This source code is dual-licensed under either the MIT license found in the
+// LICENSE-MIT file in the root directory of this source tree or the Apache
+// License, Version 2.0 found in the LICENSE-APACHE file in the root directory
+// of this source tree. You may select, at your option, one of the above-listed
+// licenses.
+
+//! Versioned key files for persisting servers
+//!
+//! The serialization of servers is a bare key without any metadata. A key file
+//! additionally records the version, cipher suite, mode, key ID and creation
+//! time and ends with a checksum, so a key of a different suite or mode or a
+//! corrupted file is rejected instead of being loaded. The format is
+//! described in [`KeyFileMetadata`].
+
+use alloc::vec::Vec;
+
+use digest::{Digest, Output, OutputSizeUser};
+use generic_array::typenum::Unsigned;
+use zeroize::Zeroizing;
+
+use crate::serialization::take_field;
+use crate::{
+    CipherSuite, DeserializationReason, Error, Mode, OprfMode, OprfServer, PoprfMode, PoprfServer,
+    PrivateKey, ProtocolMode, Result, Serializable, VoprfMode, VoprfServer,
+};
+
+///////////////
+// Constants //
+// ========= //
+///////////////
+
+/// The current version of the key file format.
+pub const KEY_FILE_VERSION: u8 = 1;
+
+const STR_KEY_FILE: [u8; 8] = *b"KeyFile-";
+
+////////////////////////////
+// High-level API Structs //
+// ====================== //
+////////////////////////////
+
+/// The metadata of a key file, which can be inspected before knowing the
+/// [`CipherSuite`] of the key, e.g. to pick the right loader.
+///
+/// The encoding is:
+///
+/// ```text
+/// version:    u8 (currently `1`)
+/// suite_len:  u8
+/// suite:      [u8; suite_len] (`CipherSuite::ID`)
+/// mode:       u8 (`Mode::to_u8()`)
+/// created_at: u64 (big-endian)
+/// key_id_len: u8
+/// key_id:     [u8; key_id_len] (`PublicKey::fingerprint()`)
+/// key_len:    u16 (big-endian)
+/// key:        [u8; key_len] (the serialized server)
+/// checksum:   [u8; Hash::OutputSize]
+/// ```
+///
+/// The checksum is the [`CipherSuite::Hash`] of `"KeyFile-"` followed by all
+/// preceding bytes. It detects corruption, not tampering.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct KeyFileMetadata<'a> {
+    /// The [`CipherSuite::ID`] of the key.
+    pub suite: &'a [u8],
+    /// The mode of the server.
+    pub mode: Mode,
+    /// The time the key file was created at, as passed to `to_key_file()`.
+    pub created_at: u64,
+    /// The fingerprint of the public key.
+    pub key_id: &'a [u8],
+}
+
+impl<'a> KeyFileMetadata<'a> {
+    /// Decodes the metadata of a key file and returns it together with the
+    /// key and the checksum. The checksum is not verified, which requires the
+    /// [`CipherSuite`].
+    ///
+    /// # Errors
+    /// [`Error::Deserialization`] if the metadata is malformed or has an
+    /// unsupported version.
+    fn decode(mut input: &'a [u8]) -> Result<(Self, &'a [u8], &'a [u8])> {
+        let version = take_field(&mut input, 1, "version")?[0];
+
+        if version != KEY_FILE_VERSION {
+            return Err(Error::deserialization(
+                "version",
+                DeserializationReason::Version,
+            ));
+        }
+
+        let suite_len = take_field(&mut input, 1, "suite")?[0];
+        let suite = take_field(&mut input, suite_len.into(), "suite")?;
+        let mode = Mode::from_u8(take_field(&mut input, 1, "mode")?[0])
+            .ok_or(Error::deserialization("mode", DeserializationReason::Mode))?;
+        let created_at = take_field(&mut input, 8, "created_at")?;
+        let created_at = u64::from_be_bytes(created_at.try_into().unwrap());
+        let key_id_len = take_field(&mut input, 1, "key_id")?[0];
+        let key_id = take_field(&mut input, key_id_len.into(), "key_id")?;
+        let key_len = take_field(&mut input, 2, "key")?;
+        let key_len = u16::from_be_bytes([key_len[0], key_len[1]]);
+        let key = take_field(&mut input, key_len.into(), "key")?;
+
+        Ok((
+            Self {
+                suite,
+                mode,
+                created_at,
+                key_id,
+            },
+            key,
+            input,
+        ))
+    }
+
+    /// Decodes the metadata of a key file. The checksum is not verified, which
+    /// requires the [`CipherSuite`].
+    ///
+    /// # Errors
+    /// [`Error::Deserialization`] if the metadata is malformed or has an
+    /// unsupported version.
+    pub fn from_key_file(input: &'a [u8]) -> Result<Self> {
+        Self::decode(input).map(|(metadata, _, _)| metadata)
+    }
+}
+
+impl<CS: CipherSuite> OprfServer<CS> {
+    /// Encodes this server into a key file, see [`KeyFileMetadata`].
+    /// `created_at` is recorded as is, usually the seconds since the UNIX
+    /// epoch.
+    pub fn to_key_file(&self, created_at: u64) -> Zeroizing<Vec<u8>> {
+        encode::<CS, OprfMode, _>(self, &PrivateKey::<CS>(self.sk).fingerprint(), created_at)
+    }
+
+    /// Decodes a key file produced by [`to_key_file()`](Self::to_key_file).
+    ///
+    /// # Errors
+    /// [`Error::Deserialization`] if `input` is malformed, belongs to a
+    /// different [`CipherSuite`] or mode, or the checksum or key ID don't
+    /// match.
+    pub fn from_key_file(input: &[u8]) -> Result<Self> {
+        decode::<CS, OprfMode, _>(input, |server: &Self| {
+            PrivateKey::<CS>(server.sk).fingerprint()
+        })
+    }
+}
+
+impl<CS: CipherSuite> VoprfServer<CS> {
+    /// Encodes this server into a key file, see [`KeyFileMetadata`].
+    /// `created_at` is recorded as is, usually the seconds since the UNIX
+    /// epoch.
+    pub fn to_key_file(&self, created_at: u64) -> Zeroizing<Vec<u8>> {
+        encode::<CS, VoprfMode, _>(self, &self.get_public_key().fingerprint(), created_at)
+    }
+
+    /// Decodes a key file produced by [`to_key_file()`](Self::to_key_file).
+    ///
+    /// # Errors
+    /// [`Error::Deserialization`] if `input` is malformed, belongs to a
+    /// different [`CipherSuite`] or mode, or the checksum or key ID don't
+    /// match.
+    pub fn from_key_file(input: &[u8]) -> Result<Self> {
+        decode::<CS, VoprfMode, _>(input, |server: &Self| server.get_public_key().fingerprint())
+    }
+}
+
+impl<CS: CipherSuite> PoprfServer<CS> {
+    /// Encodes this server into a key file, see [`KeyFileMetadata`].
+    /// `created_at` is recorded as is, usually the seconds since the UNIX
+    /// epoch.
+    pub fn to_key_file(&self, created_at: u64) -> Zeroizing<Vec<u8>> {
+        encode::<CS, PoprfMode, _>(self, &self.get_public_key().fingerprint(), created_at)
+    }
+
+    /// Decodes a key file produced by [`to_key_file()`](Self::to_key_file).
+    ///
+    /// # Errors
+    /// [`Error::Deserialization`] if `input` is malformed, belongs to a
+    /// different [`CipherSuite`] or mode, or the checksum or key ID don't
+    /// match.
+    pub fn from_key_file(input: &[u8]) -> Result<Self> {
+        decode::<CS, PoprfMode, _>(input, |server: &Self| server.get_public_key().fingerprint())
+    }
+}
+
+/////////////////////
+// Inner functions //
+// =============== //
+/////////////////////
+
+fn encode<CS: CipherSuite, M: ProtocolMode, S: Serializable>(
+    server: &S,
+    key_id: &[u8],
+    created_at: u64,
+) -> Zeroizing<Vec<u8>> {
+    let suite = CS::ID.as_bytes();
+    let key_len = S::Len::USIZE;
+    let mut output = Zeroizing::new(Vec::with_capacity(
+        14 + suite.len() + key_id.len() + key_len + <CS::Hash as OutputSizeUser>::OutputSize::USIZE,
+    ));
+
+    // Suite IDs, fingerprints and serialized servers are short.
+    output.push(KEY_FILE_VERSION);
+    output.push(suite.len().try_into().unwrap());
+    output.extend_from_slice(suite);
+    output.push(M::MODE.to_u8());
+    output.extend_from_slice(&created_at.to_be_bytes());
+    output.push(key_id.len().try_into().unwrap());
+    output.extend_from_slice(key_id);
+    output.extend_from_slice(&u16::try_from(key_len).unwrap().to_be_bytes());
+    let start = output.len();
+    output.resize(start + key_len, 0);
+    server
+        .serialize_into(&mut output[start..])
+        .expect("buffer has the length of the key");
+    let checksum = checksum::<CS>(&output);
+    output.extend_from_slice(&checksum);
+
+    output
+}
+
+fn decode<CS: CipherSuite, M: ProtocolMode, S: Serializable>(
+    input: &[u8],
+    key_id: impl FnOnce(&S) -> Output<CS::Hash>,
+) -> Result<S> {
+    let (metadata, key, expected) = KeyFileMetadata::decode(input)?;
+
+    if metadata.suite != CS::ID.as_bytes() {
+        return Err(Error::deserialization(
+            "suite",
+            DeserializationReason::Suite,
+        ));
+    }
+
+    if metadata.mode != M::MODE {
+        return Err(Error::deserialization("mode", DeserializationReason::Mode));
+    }
+
+    let checksum_len = <CS::Hash as OutputSizeUser>::OutputSize::USIZE;
+
+    if expected.len() != checksum_len {
+        return Err(Error::deserialization(
+            "checksum",
+            DeserializationReason::Length {
+                expected: checksum_len,
+                actual: expected.len(),
+            },
+        ));
+    }
+
+    if checksum::<CS>(&input[..input.len() - checksum_len])[..] != *expected {
+        return Err(Error::deserialization(
+            "checksum",
+            DeserializationReason::Checksum,
+        ));
+    }
+
+    let (server, rest) = S::deserialize(key)?;
+
+    if !rest.is_empty() {
+        return Err(Error::deserialization(
+            "key",
+            DeserializationReason::Length {
+                expected: key.len() - rest.len(),
+                actual: key.len(),
+            },
+        ));
+    }
+
+    if key_id(&server)[..] != *metadata.key_id {
+        return Err(Error::deserialization(
+            "key_id",
+            DeserializationReason::Checksum,
+        ));
+    }
+
+    Ok(server)
+}
+
+fn checksum<CS: CipherSuite>(input: &[u8]) -> Output<CS::Hash> {
+    CS::Hash::new()
+        .chain_update(STR_KEY_FILE)
+        .chain_update(input)
+        .finalize()
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::rngs::OsRng;
+
+    use super::*;
+    use crate::DeserializationError;
+
+    fn key_file<CS: CipherSuite>() {
+        let mut rng = OsRng;
+
+        let server = OprfServer::<CS>::new(&mut rng).unwrap();
+        let bytes = server.to_key_file(1_700_000_000);
+        let metadata = KeyFileMetadata::from_key_file(&bytes).unwrap();
+        assert_eq!(metadata.suite, CS::ID.as_bytes());
+        assert_eq!(metadata.mode, Mode::Oprf);
+        assert_eq!(metadata.created_at, 1_700_000_000);
+        assert_eq!(metadata.key_id, &server.get_private_key().fingerprint()[..]);
+        let decoded = OprfServer::<CS>::from_key_file(&bytes).unwrap();
+        assert_eq!(decoded.serialize(), server.serialize());
+
+        let server = VoprfServer::<CS>::new(&mut rng).unwrap();
+        let bytes = server.to_key_file(0);
+        let metadata = KeyFileMetadata::from_key_file(&bytes).unwrap();
+        assert_eq!(metadata.mode, Mode::Voprf);
+        assert_eq!(metadata.key_id, &server.get_public_key().fingerprint()[..]);
+        let decoded = VoprfServer::<CS>::from_key_file(&bytes).unwrap();
+        assert_eq!(decoded.serialize(), server.serialize());
+
+        assert_eq!(
+            PoprfServer::<CS>::from_key_file(&bytes).unwrap_err(),
+            Error::Deserialization(DeserializationError {
+                field: "mode",
+                reason: DeserializationReason::Mode,
+            })
+        );
+        assert_eq!(
+            OprfServer::<CS>::from_key_file(&bytes).unwrap_err(),
+            Error::Deserialization(DeserializationError {
+                field: "mode",
+                reason: DeserializationReason::Mode,
+            })
+        );
+
+        let server = PoprfServer::<CS>::new(&mut rng).unwrap();
+        let bytes = server.to_key_file(u64::MAX);
+        assert_eq!(
+            KeyFileMetadata::from_key_file(&bytes).unwrap().mode,
+            Mode::Poprf
+        );
+        let decoded = PoprfServer::<CS>::from_key_file(&bytes).unwrap();
+        assert_eq!(decoded.serialize(), server.serialize());
+
+        // Every corrupted byte is detected.
+        for index in 0..bytes.len() {
+            let mut bad = bytes.clone();
+            bad[index] ^= 1;
+            assert!(PoprfServer::<CS>::from_key_file(&bad).is_err());
+        }
+
+        let mut bad = bytes.clone();
+        let index = bad.len() - 1;
+        bad[index] ^= 1;
+        assert_eq!(
+            PoprfServer::<CS>::from_key_file(&bad).unwrap_err(),
+            Error::Deserialization(DeserializationError {
+                field: "checksum",
+                reason: DeserializationReason::Checksum,
+            })
+        );
+
+        let mut bad = bytes.clone();
+        bad[0] = KEY_FILE_VERSION + 1;
+        assert_eq!(
+            KeyFileMetadata::from_key_file(&bad).unwrap_err(),
+            Error::Deserialization(DeserializationError {
+                field: "version",
+                reason: DeserializationReason::Version,
+            })
+        );
+
+        assert!(PoprfServer::<CS>::from_key_file(&bytes[..bytes.len() - 1]).is_err());
+        let mut bad = bytes.clone();
+        bad.push(0);
+        assert!(PoprfServer::<CS>::from_key_file(&bad).is_err());
+
+        // A key ID that doesn't belong to the key.
+        let other = PoprfServer::<CS>::new(&mut rng).unwrap();
+        let bad = encode::<CS, PoprfMode, _>(&server, &other.get_public_key().fingerprint(), 0);
+        assert_eq!(
+            PoprfServer::<CS>::from_key_file(&bad).unwrap_err(),
+            Error::Deserialization(DeserializationError {
+                field: "key_id",
+                reason: DeserializationReason::Checksum,
+            })
+        );
+    }
+
+    #[test]
+    fn test_functionality() {
+        use p256::NistP256;
+        use p384::NistP384;
+        use p521::NistP521;
+
+        #[cfg(feature = "ristretto255")]
+        {
+            use crate::Ristretto255;
+
+            key_file::<Ristretto255>();
+        }
+
+        key_file::<NistP256>();
+        key_file::<NistP384>();
+        key_file::<NistP521>();
+    }
+
+    #[test]
+    fn test_suite_mismatch() {
+        use p256::NistP256;
+        use p384::NistP384;
+
+        let bytes = VoprfServer::<NistP256>::new(&mut OsRng)
+            .unwrap()
+            .to_key_file(0);
+        assert_eq!(
+            VoprfServer::<NistP384>::from_key_file(&bytes).unwrap_err(),
+            Error::Deserialization(DeserializationError {
+                field: "suite",
+                reason: DeserializationReason::Suite,
+            })
+        );
+    }
+}
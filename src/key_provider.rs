@@ -0,0 +1,1029 @@
+// Copyright (c) Meta Platforms, Inc. and affiliates.
+//
+// This source code is dual-licensed under either the MIT license found in the
+// LICENSE-MIT file in the root directory of this source tree or the Apache
+// License, Version 2.0 found in the LICENSE-APACHE file in the root directory
+// of this source tree. You may select, at your option, one of the above-listed
+// licenses.
+
+//! Servers whose private key is held outside of the process
+//!
+//! An [`OprfKeyProvider`] performs all operations involving the private key:
+//! multiplying blinded elements and the nonce-dependent part of a [`Proof`].
+//! A [`PoprfKeyProvider`] additionally performs them with the private key
+//! tweaked by the POPRF `info`. [`OprfProviderServer`],
+//! [`VoprfProviderServer`] and [`PoprfProviderServer`] build the protocol on
+//! top of them, so the key can be kept in an HSM or a remote signer.
+//! [`PrivateKey`] is the in-process reference implementation.
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+
+use derive_where::derive_where;
+use rand_core::{TryCryptoRng, TryRngCore};
+use zeroize::Zeroizing;
+
+#[cfg(feature = "alloc")]
+use crate::common::MAX_BATCH_SIZE;
+use crate::common::{generate_proof_with_provider, hedged_nonce, proof_randomness};
+use crate::poprf::{compute_tweak, compute_tweaked_key, PoprfServerEvaluateResult};
+use crate::{
+    BlindedElement, CipherSuite, EvaluationElement, Group, Mode, OprfMode, PoprfMode, PrivateKey,
+    Proof, PublicKey, Result, VoprfMode, VoprfServerEvaluateResult,
+};
+#[cfg(feature = "alloc")]
+use crate::{Error, PoprfServerBatchEvaluateResult, VoprfServerBatchEvaluateResult};
+
+/// Performs the operations involving the private key `k` of a server.
+///
+/// A [`Proof`] is generated in a single call to [`prove()`](Self::prove),
+/// which draws a secret nonce `r`, obtains the challenge for its commitments
+/// and returns `r - challenge * k`. The nonce never leaves the provider.
+///
+/// A misbehaving provider results in [`Proof`]s that fail to verify on the
+/// client.
+///
+/// The POPRF additionally requires a [`PoprfKeyProvider`].
+pub trait OprfKeyProvider<CS: CipherSuite> {
+    /// Returns the public key `k * G`.
+    ///
+    /// # Errors
+    /// [`Error::KeyProvider`](crate::Error::KeyProvider) if the provider
+    /// fails.
+    fn public_key(&self) -> Result<PublicKey<CS>>;
+
+    /// Multiplies each of `elements` by `k` in place.
+    ///
+    /// # Errors
+    /// [`Error::KeyProvider`](crate::Error::KeyProvider) if the provider
+    /// fails.
+    fn multiply(&self, elements: &mut [<CS::Group as Group>::Elem]) -> Result<()>;
+
+    /// Draws a nonce `r`, passes the commitments `r * G` and `r * composite`
+    /// to `challenge` and returns `r - challenge * k`. `evaluated_composite`
+    /// is `k * composite`, which together with `composite` may be used to
+    /// derive the nonce. If `rng` fails, the nonce should be derived without
+    /// fresh randomness instead of failing the proof.
+    ///
+    /// # Errors
+    /// [`Error::KeyProvider`](crate::Error::KeyProvider) if the provider fails.
+    fn prove<R: TryRngCore + TryCryptoRng>(
+        &self,
+        composite: <CS::Group as Group>::Elem,
+        evaluated_composite: <CS::Group as Group>::Elem,
+        challenge: impl FnOnce(
+            <CS::Group as Group>::Elem,
+            <CS::Group as Group>::Elem,
+        ) -> <CS::Group as Group>::Scalar,
+        rng: &mut R,
+    ) -> Result<<CS::Group as Group>::Scalar>;
+}
+
+/// Performs the operations involving the tweaked private key `k + m` of a
+/// POPRF server, where `m` is derived from the public `info`, see
+/// [`PoprfProviderServer`].
+///
+/// The tweak is derived by the provider from `info`, so the key is only ever
+/// used tweaked as specified by the POPRF.
+pub trait PoprfKeyProvider<CS: CipherSuite>: OprfKeyProvider<CS> {
+    /// Multiplies each of `elements` by the inverse of the tweaked key,
+    /// `(k + m)^-1`, in place.
+    ///
+    /// # Errors
+    /// - [`Error::Info`](crate::Error::Info) if the `info` is longer than
+    ///   `u16::MAX`.
+    /// - [`Error::Protocol`](crate::Error::Protocol) if the tweaked key is
+    ///   zero.
+    /// - [`Error::KeyProvider`](crate::Error::KeyProvider) if the provider
+    ///   fails.
+    fn multiply_by_inverted_tweaked_key(
+        &self,
+        info: Option<&[u8]>,
+        elements: &mut [<CS::Group as Group>::Elem],
+    ) -> Result<()>;
+
+    /// Same as [`OprfKeyProvider::prove()`], but with the tweaked key: returns
+    /// `r - challenge * (k + m)`. `evaluated_composite` is
+    /// `(k + m) * composite`.
+    ///
+    /// # Errors
+    /// - [`Error::Info`](crate::Error::Info) if the `info` is longer than
+    ///   `u16::MAX`.
+    /// - [`Error::Protocol`](crate::Error::Protocol) if the tweaked key is
+    ///   zero.
+    /// - [`Error::KeyProvider`](crate::Error::KeyProvider) if the provider
+    ///   fails.
+    fn prove_with_tweaked_key<R: TryRngCore + TryCryptoRng>(
+        &self,
+        info: Option<&[u8]>,
+        composite: <CS::Group as Group>::Elem,
+        evaluated_composite: <CS::Group as Group>::Elem,
+        challenge: impl FnOnce(
+            <CS::Group as Group>::Elem,
+            <CS::Group as Group>::Elem,
+        ) -> <CS::Group as Group>::Scalar,
+        rng: &mut R,
+    ) -> Result<<CS::Group as Group>::Scalar>;
+}
+
+/// The in-process reference implementation.
+impl<CS: CipherSuite> OprfKeyProvider<CS> for PrivateKey<CS> {
+    fn public_key(&self) -> Result<PublicKey<CS>> {
+        Ok(PrivateKey::public_key(self))
+    }
+
+    fn multiply(&self, elements: &mut [<CS::Group as Group>::Elem]) -> Result<()> {
+        for element in elements {
+            *element = *element * &self.0;
+        }
+
+        Ok(())
+    }
+
+    fn prove<R: TryRngCore + TryCryptoRng>(
+        &self,
+        composite: <CS::Group as Group>::Elem,
+        evaluated_composite: <CS::Group as Group>::Elem,
+        challenge: impl FnOnce(
+            <CS::Group as Group>::Elem,
+            <CS::Group as Group>::Elem,
+        ) -> <CS::Group as Group>::Scalar,
+        rng: &mut R,
+    ) -> Result<<CS::Group as Group>::Scalar> {
+        Ok(prove_with_key::<CS, _>(
+            self.0,
+            Mode::Voprf,
+            composite,
+            evaluated_composite,
+            challenge,
+            rng,
+        ))
+    }
+}
+
+/// The in-process reference implementation.
+impl<CS: CipherSuite> PoprfKeyProvider<CS> for PrivateKey<CS> {
+    fn multiply_by_inverted_tweaked_key(
+        &self,
+        info: Option<&[u8]>,
+        elements: &mut [<CS::Group as Group>::Elem],
+    ) -> Result<()> {
+        let inverted_tweak =
+            Zeroizing::new(CS::Group::invert_scalar(compute_tweak::<CS>(self.0, info)?));
+
+        for element in elements {
+            *element = *element * &*inverted_tweak;
+        }
+
+        Ok(())
+    }
+
+    fn prove_with_tweaked_key<R: TryRngCore + TryCryptoRng>(
+        &self,
+        info: Option<&[u8]>,
+        composite: <CS::Group as Group>::Elem,
+        evaluated_composite: <CS::Group as Group>::Elem,
+        challenge: impl FnOnce(
+            <CS::Group as Group>::Elem,
+            <CS::Group as Group>::Elem,
+        ) -> <CS::Group as Group>::Scalar,
+        rng: &mut R,
+    ) -> Result<<CS::Group as Group>::Scalar> {
+        let tweak = Zeroizing::new(compute_tweak::<CS>(self.0, info)?);
+
+        Ok(prove_with_key::<CS, _>(
+            *tweak,
+            Mode::Poprf,
+            composite,
+            evaluated_composite,
+            challenge,
+            rng,
+        ))
+    }
+}
+
+/// An OPRF server whose private key is held by an [`OprfKeyProvider`].
+#[derive_where(Clone, Debug; P)]
+pub struct OprfProviderServer<CS: CipherSuite, P: OprfKeyProvider<CS>> {
+    provider: P,
+    suite: PhantomData<CS>,
+}
+
+impl<CS: CipherSuite, P: OprfKeyProvider<CS>> OprfProviderServer<CS, P> {
+    /// Produces a new instance of an [`OprfProviderServer`] using `provider`.
+    pub fn new(provider: P) -> Self {
+        Self {
+            provider,
+            suite: PhantomData,
+        }
+    }
+
+    /// Returns the [`OprfKeyProvider`].
+    pub fn provider(&self) -> &P {
+        &self.provider
+    }
+
+    /// Computes the second step for the multiplicative blinding version of
+    /// DH-OPRF. This message is sent from the server (who holds the OPRF key)
+    /// to the client.
+    ///
+    /// # Errors
+    /// [`Error::KeyProvider`](crate::Error::KeyProvider) if the provider
+    /// fails.
+    pub fn blind_evaluate(
+        &self,
+        blinded_element: &BlindedElement<CS, OprfMode>,
+    ) -> Result<EvaluationElement<CS, OprfMode>> {
+        let mut elements = [blinded_element.0];
+        self.provider.multiply(&mut elements)?;
+
+        Ok(EvaluationElement(elements[0], PhantomData))
+    }
+
+    /// Evaluates multiple [`BlindedElement`]s with a single call to the
+    /// [`OprfKeyProvider`].
+    ///
+    /// # Errors
+    /// - [`Error::Batch`] if the number of `blinded_elements` is longer than
+    ///   [`MAX_BATCH_SIZE`].
+    /// - [`Error::KeyProvider`] if the provider fails.
+    #[cfg(feature = "alloc")]
+    pub fn batch_blind_evaluate(
+        &self,
+        blinded_elements: &[BlindedElement<CS, OprfMode>],
+    ) -> Result<Vec<EvaluationElement<CS, OprfMode>>> {
+        if blinded_elements.len() > MAX_BATCH_SIZE {
+            return Err(Error::Batch);
+        }
+
+        let mut elements: Vec<_> = blinded_elements
+            .iter()
+            .map(|blinded_element| blinded_element.0)
+            .collect();
+        self.provider.multiply(&mut elements)?;
+
+        Ok(elements
+            .into_iter()
+            .map(|element| EvaluationElement(element, PhantomData))
+            .collect())
+    }
+}
+
+/// A VOPRF server whose private key is held by an [`OprfKeyProvider`].
+#[derive_where(Clone; P)]
+#[derive_where(Debug; P, <CS::Group as Group>::Elem)]
+pub struct VoprfProviderServer<CS: CipherSuite, P: OprfKeyProvider<CS>> {
+    provider: P,
+    pk: <CS::Group as Group>::Elem,
+}
+
+impl<CS: CipherSuite, P: OprfKeyProvider<CS>> VoprfProviderServer<CS, P> {
+    /// Produces a new instance of a [`VoprfProviderServer`] using `provider`.
+    ///
+    /// # Errors
+    /// [`Error::KeyProvider`](crate::Error::KeyProvider) if the provider fails
+    /// to return the public key.
+    pub fn new(provider: P) -> Result<Self> {
+        let pk = provider.public_key()?.0;
+
+        Ok(Self { provider, pk })
+    }
+
+    /// Returns the [`OprfKeyProvider`].
+    pub fn provider(&self) -> &P {
+        &self.provider
+    }
+
+    /// Retrieves the public key to be used in the verifiable OPRF protocol.
+    pub fn get_public_key(&self) -> PublicKey<CS> {
+        PublicKey(self.pk)
+    }
+
+    /// Computes the second step for the multiplicative blinding version of
+    /// DH-OPRF. This message is sent from the server (who holds the OPRF key)
+    /// to the client.
+    ///
+    /// # Errors
+    /// [`Error::KeyProvider`](crate::Error::KeyProvider) if the provider fails.
+    pub fn blind_evaluate<R: TryRngCore + TryCryptoRng>(
+        &self,
+        rng: &mut R,
+        blinded_element: &BlindedElement<CS, VoprfMode>,
+    ) -> Result<VoprfServerEvaluateResult<CS>> {
+        let mut elements = [blinded_element.0];
+        self.provider.multiply(&mut elements)?;
+        let proof = self.prove(rng, &[blinded_element.0], &elements)?;
+
+        Ok(VoprfServerEvaluateResult {
+            message: EvaluationElement(elements[0], PhantomData),
+            proof,
+        })
+    }
+
+    /// Allows for batching of the evaluation of multiple [`BlindedElement`]
+    /// messages from a [`VoprfClient`](crate::VoprfClient), with a single call
+    /// to the [`OprfKeyProvider`] to multiply them.
+    ///
+    /// # Errors
+    /// - [`Error::Batch`] if the number of `blinded_elements` is longer than
+    ///   [`MAX_BATCH_SIZE`].
+    /// - [`Error::KeyProvider`] if the provider fails.
+    #[cfg(feature = "alloc")]
+    pub fn batch_blind_evaluate<R: TryRngCore + TryCryptoRng>(
+        &self,
+        rng: &mut R,
+        blinded_elements: &[BlindedElement<CS, VoprfMode>],
+    ) -> Result<VoprfServerBatchEvaluateResult<CS>> {
+        if blinded_elements.len() > MAX_BATCH_SIZE {
+            return Err(Error::Batch);
+        }
+
+        let blinded_elements: Vec<_> = blinded_elements
+            .iter()
+            .map(|blinded_element| blinded_element.0)
+            .collect();
+        let mut elements = blinded_elements.clone();
+        self.provider.multiply(&mut elements)?;
+        let proof = self.prove(rng, &blinded_elements, &elements)?;
+
+        Ok(VoprfServerBatchEvaluateResult {
+            messages: elements
+                .into_iter()
+                .map(|element| EvaluationElement(element, PhantomData))
+                .collect(),
+            proof,
+        })
+    }
+
+    fn prove<R: TryRngCore + TryCryptoRng>(
+        &self,
+        rng: &mut R,
+        blinded_elements: &[<CS::Group as Group>::Elem],
+        evaluation_elements: &[<CS::Group as Group>::Elem],
+    ) -> Result<Proof<CS>> {
+        generate_proof_with_provider(
+            self.pk,
+            blinded_elements.iter().copied(),
+            evaluation_elements.iter().copied(),
+            Mode::Voprf,
+            |composite, evaluated_composite, challenge| {
+                self.provider
+                    .prove(composite, evaluated_composite, challenge, rng)
+            },
+        )
+    }
+}
+
+/// A POPRF server whose private key is held by a [`PoprfKeyProvider`].
+#[derive_where(Clone; P)]
+#[derive_where(Debug; P, <CS::Group as Group>::Elem)]
+pub struct PoprfProviderServer<CS: CipherSuite, P: PoprfKeyProvider<CS>> {
+    provider: P,
+    pk: <CS::Group as Group>::Elem,
+}
+
+impl<CS: CipherSuite, P: PoprfKeyProvider<CS>> PoprfProviderServer<CS, P> {
+    /// Produces a new instance of a [`PoprfProviderServer`] using `provider`.
+    ///
+    /// # Errors
+    /// [`Error::KeyProvider`](crate::Error::KeyProvider) if the provider fails
+    /// to return the public key.
+    pub fn new(provider: P) -> Result<Self> {
+        let pk = provider.public_key()?.0;
+
+        Ok(Self { provider, pk })
+    }
+
+    /// Returns the [`PoprfKeyProvider`].
+    pub fn provider(&self) -> &P {
+        &self.provider
+    }
+
+    /// Retrieves the public key to be used in the partially oblivious PRF
+    /// protocol.
+    pub fn get_public_key(&self) -> PublicKey<CS> {
+        PublicKey(self.pk)
+    }
+
+    /// Computes the second step for the multiplicative blinding version of
+    /// DH-OPRF. This message is sent from the server (who holds the OPRF key)
+    /// to the client.
+    ///
+    /// # Errors
+    /// - [`Error::Info`](crate::Error::Info) if the `info` is longer than
+    ///   `u16::MAX`.
+    /// - [`Error::Protocol`](crate::Error::Protocol) if the protocol fails and
+    ///   can't be completed.
+    /// - [`Error::KeyProvider`](crate::Error::KeyProvider) if the provider
+    ///   fails.
+    pub fn blind_evaluate<R: TryRngCore + TryCryptoRng>(
+        &self,
+        rng: &mut R,
+        blinded_element: &BlindedElement<CS, PoprfMode>,
+        info: Option<&[u8]>,
+    ) -> Result<PoprfServerEvaluateResult<CS>> {
+        let tweaked_key = compute_tweaked_key::<CS>(self.pk, info)?;
+        let mut elements = [blinded_element.0];
+        self.provider
+            .multiply_by_inverted_tweaked_key(info, &mut elements)?;
+        let proof = self.prove(rng, tweaked_key, &[blinded_element.0], &elements, info)?;
+
+        Ok(PoprfServerEvaluateResult {
+            message: EvaluationElement(elements[0], PhantomData),
+            proof,
+        })
+    }
+
+    /// Allows for batching of the evaluation of multiple [`BlindedElement`]
+    /// messages from a [`PoprfClient`](crate::PoprfClient), with a single call
+    /// to the [`PoprfKeyProvider`] to multiply them.
+    ///
+    /// # Errors
+    /// - [`Error::Batch`] if the number of `blinded_elements` is longer than
+    ///   [`MAX_BATCH_SIZE`].
+    /// - [`Error::Info`] if the `info` is longer than `u16::MAX`.
+    /// - [`Error::Protocol`] if the protocol fails and can't be completed.
+    /// - [`Error::KeyProvider`] if the provider fails.
+    #[cfg(feature = "alloc")]
+    pub fn batch_blind_evaluate<R: TryRngCore + TryCryptoRng>(
+        &self,
+        rng: &mut R,
+        blinded_elements: &[BlindedElement<CS, PoprfMode>],
+        info: Option<&[u8]>,
+    ) -> Result<PoprfServerBatchEvaluateResult<CS>> {
+        if blinded_elements.len() > MAX_BATCH_SIZE {
+            return Err(Error::Batch);
+        }
+
+        let tweaked_key = compute_tweaked_key::<CS>(self.pk, info)?;
+        let blinded_elements: Vec<_> = blinded_elements
+            .iter()
+            .map(|blinded_element| blinded_element.0)
+            .collect();
+        let mut elements = blinded_elements.clone();
+        self.provider
+            .multiply_by_inverted_tweaked_key(info, &mut elements)?;
+        let proof = self.prove(rng, tweaked_key, &blinded_elements, &elements, info)?;
+
+        Ok(PoprfServerBatchEvaluateResult {
+            messages: elements
+                .into_iter()
+                .map(|element| EvaluationElement(element, PhantomData))
+                .collect(),
+            proof,
+        })
+    }
+
+    fn prove<R: TryRngCore + TryCryptoRng>(
+        &self,
+        rng: &mut R,
+        tweaked_key: <CS::Group as Group>::Elem,
+        blinded_elements: &[<CS::Group as Group>::Elem],
+        evaluation_elements: &[<CS::Group as Group>::Elem],
+        info: Option<&[u8]>,
+    ) -> Result<Proof<CS>> {
+        // The proof shows that the blinded elements are the evaluation elements
+        // multiplied by the tweaked key.
+        generate_proof_with_provider(
+            tweaked_key,
+            evaluation_elements.iter().copied(),
+            blinded_elements.iter().copied(),
+            Mode::Poprf,
+            |composite, evaluated_composite, challenge| {
+                self.provider.prove_with_tweaked_key(
+                    info,
+                    composite,
+                    evaluated_composite,
+                    challenge,
+                    rng,
+                )
+            },
+        )
+    }
+}
+
+/////////////////////
+// Inner functions //
+// =============== //
+/////////////////////
+
+/// Implements [`OprfKeyProvider::prove()`] with the scalar `k`, with the nonce
+/// hedged like the one of a server holding `k`.
+fn prove_with_key<CS: CipherSuite, R: TryRngCore + TryCryptoRng>(
+    k: <CS::Group as Group>::Scalar,
+    mode: Mode,
+    composite: <CS::Group as Group>::Elem,
+    evaluated_composite: <CS::Group as Group>::Elem,
+    challenge: impl FnOnce(
+        <CS::Group as Group>::Elem,
+        <CS::Group as Group>::Elem,
+    ) -> <CS::Group as Group>::Scalar,
+    rng: &mut R,
+) -> <CS::Group as Group>::Scalar {
+    let randomness = proof_randomness::<CS, _>(rng);
+    let r = Zeroizing::new(hedged_nonce::<CS>(
+        k,
+        randomness.as_deref().unwrap_or_default(),
+        &CS::Group::serialize_elem(CS::Group::base_elem() * &k),
+        &CS::Group::serialize_elem(composite),
+        &CS::Group::serialize_elem(evaluated_composite),
+        mode,
+    ));
+    let c = challenge(CS::Group::base_elem() * &r, composite * &r);
+
+    *r - &(c * &k)
+}
+
+#[cfg(test)]
+mod tests {
+    use core::cell::Cell;
+    use core::fmt::Debug;
+
+    #[cfg(feature = "alloc")]
+    use ::alloc::vec::Vec;
+    use rand::rngs::OsRng;
+
+    use super::*;
+    use crate::tests::mock_rng::FailingRng;
+    use crate::{
+        Error, OprfClient, OprfOutput, OprfServer, PoprfClient, PoprfServer, VoprfClient,
+        VoprfServer,
+    };
+
+    /// Stands in for a signer in another process, reachable only through
+    /// serialized messages.
+    struct RemoteSigner<CS: CipherSuite> {
+        key: PrivateKey<CS>,
+        calls: Cell<usize>,
+        offline: Cell<bool>,
+    }
+
+    impl<CS: CipherSuite> RemoteSigner<CS> {
+        fn new(key: PrivateKey<CS>) -> Self {
+            Self {
+                key,
+                calls: Cell::new(0),
+                offline: Cell::new(false),
+            }
+        }
+
+        /// Simulates a round trip, in which elements are serialized.
+        fn call(&self) -> Result<()> {
+            self.calls.set(self.calls.get() + 1);
+
+            if self.offline.get() {
+                Err(Error::KeyProvider)
+            } else {
+                Ok(())
+            }
+        }
+
+        fn transmit(element: <CS::Group as Group>::Elem) -> <CS::Group as Group>::Elem {
+            CS::Group::deserialize_elem(&CS::Group::serialize_elem(element)).unwrap()
+        }
+    }
+
+    impl<CS: CipherSuite> OprfKeyProvider<CS> for RemoteSigner<CS> {
+        fn public_key(&self) -> Result<PublicKey<CS>> {
+            self.call()?;
+            Ok(PublicKey(Self::transmit(self.key.public_key().0)))
+        }
+
+        fn multiply(&self, elements: &mut [<CS::Group as Group>::Elem]) -> Result<()> {
+            self.call()?;
+
+            for element in elements.iter_mut() {
+                *element = Self::transmit(*element);
+            }
+
+            self.key.multiply(elements)?;
+
+            for element in elements {
+                *element = Self::transmit(*element);
+            }
+
+            Ok(())
+        }
+
+        fn prove<R: TryRngCore + TryCryptoRng>(
+            &self,
+            composite: <CS::Group as Group>::Elem,
+            evaluated_composite: <CS::Group as Group>::Elem,
+            challenge: impl FnOnce(
+                <CS::Group as Group>::Elem,
+                <CS::Group as Group>::Elem,
+            ) -> <CS::Group as Group>::Scalar,
+            rng: &mut R,
+        ) -> Result<<CS::Group as Group>::Scalar> {
+            self.call()?;
+            // The commitments are sent back for the challenge, the nonce stays
+            // with the signer.
+            let s = self.key.prove(
+                Self::transmit(composite),
+                Self::transmit(evaluated_composite),
+                |t2, t3| challenge(Self::transmit(t2), Self::transmit(t3)),
+                rng,
+            )?;
+            self.call()?;
+
+            Ok(s)
+        }
+    }
+
+    impl<CS: CipherSuite> PoprfKeyProvider<CS> for RemoteSigner<CS> {
+        fn multiply_by_inverted_tweaked_key(
+            &self,
+            info: Option<&[u8]>,
+            elements: &mut [<CS::Group as Group>::Elem],
+        ) -> Result<()> {
+            self.call()?;
+
+            for element in elements.iter_mut() {
+                *element = Self::transmit(*element);
+            }
+
+            self.key.multiply_by_inverted_tweaked_key(info, elements)?;
+
+            for element in elements {
+                *element = Self::transmit(*element);
+            }
+
+            Ok(())
+        }
+
+        fn prove_with_tweaked_key<R: TryRngCore + TryCryptoRng>(
+            &self,
+            info: Option<&[u8]>,
+            composite: <CS::Group as Group>::Elem,
+            evaluated_composite: <CS::Group as Group>::Elem,
+            challenge: impl FnOnce(
+                <CS::Group as Group>::Elem,
+                <CS::Group as Group>::Elem,
+            ) -> <CS::Group as Group>::Scalar,
+            rng: &mut R,
+        ) -> Result<<CS::Group as Group>::Scalar> {
+            self.call()?;
+            let s = self.key.prove_with_tweaked_key(
+                info,
+                Self::transmit(composite),
+                Self::transmit(evaluated_composite),
+                |t2, t3| challenge(Self::transmit(t2), Self::transmit(t3)),
+                rng,
+            )?;
+            self.call()?;
+
+            Ok(s)
+        }
+    }
+
+    fn oprf<CS: CipherSuite>() {
+        let mut rng = OsRng;
+        let server = OprfServer::<CS>::new(&mut rng).unwrap();
+        let expected = server.evaluate(b"input").unwrap();
+
+        let local = OprfProviderServer::new(server.get_private_key());
+        let remote = OprfProviderServer::new(RemoteSigner::new(server.get_private_key()));
+
+        let client_blind_result = OprfClient::<CS>::blind(b"input", &mut rng).unwrap();
+
+        for message in [
+            local.blind_evaluate(&client_blind_result.message).unwrap(),
+            remote.blind_evaluate(&client_blind_result.message).unwrap(),
+        ] {
+            let output = client_blind_result
+                .state
+                .finalize(b"input", &message)
+                .unwrap();
+            assert_eq!(output, expected);
+        }
+
+        #[cfg(feature = "alloc")]
+        {
+            let blinded_elements = [
+                client_blind_result.message.clone(),
+                OprfClient::<CS>::blind(b"other", &mut rng).unwrap().message,
+            ];
+            let calls = remote.provider().calls.get();
+            let messages = remote.batch_blind_evaluate(&blinded_elements).unwrap();
+            // A single round trip for the whole batch.
+            assert_eq!(remote.provider().calls.get(), calls + 1);
+            assert_eq!(messages.len(), 2);
+            let output = client_blind_result
+                .state
+                .finalize(b"input", &messages[0])
+                .unwrap();
+            assert_eq!(output, expected);
+
+            // Oversized batches are rejected without calling the provider.
+            let blinded_elements =
+                ::alloc::vec![client_blind_result.message.clone(); MAX_BATCH_SIZE + 1];
+            let calls = remote.provider().calls.get();
+            assert_eq!(
+                remote.batch_blind_evaluate(&blinded_elements).err(),
+                Some(Error::Batch)
+            );
+            assert_eq!(remote.provider().calls.get(), calls);
+        }
+
+        remote.provider().offline.set(true);
+        assert_eq!(
+            remote.blind_evaluate(&client_blind_result.message).err(),
+            Some(Error::KeyProvider)
+        );
+    }
+
+    fn voprf_round_trip<CS: CipherSuite, P: OprfKeyProvider<CS>>(
+        server: &VoprfProviderServer<CS, P>,
+        expected: &OprfOutput<CS>,
+    ) {
+        let mut rng = OsRng;
+        let client_blind_result = VoprfClient::<CS>::blind(b"input", &mut rng).unwrap();
+        let result = server
+            .blind_evaluate(&mut rng, &client_blind_result.message)
+            .unwrap();
+        let output = client_blind_result
+            .state
+            .finalize(
+                b"input",
+                &result.message,
+                &result.proof,
+                &server.get_public_key(),
+            )
+            .unwrap();
+        assert_eq!(&output, expected);
+
+        #[cfg(feature = "alloc")]
+        {
+            let inputs = [b"input", b"other"];
+            let client_blind_results: Vec<_> = inputs
+                .iter()
+                .map(|input| VoprfClient::<CS>::blind(*input, &mut rng).unwrap())
+                .collect();
+            let blinded_elements: Vec<_> = client_blind_results
+                .iter()
+                .map(|result| result.message.clone())
+                .collect();
+            let result = server
+                .batch_blind_evaluate(&mut rng, &blinded_elements)
+                .unwrap();
+            let clients: Vec<_> = client_blind_results
+                .iter()
+                .map(|result| result.state.clone())
+                .collect();
+            let outputs: Vec<_> = VoprfClient::batch_finalize(
+                &inputs,
+                &clients,
+                &result.messages,
+                &result.proof,
+                &server.get_public_key(),
+            )
+            .unwrap()
+            .collect::<core::result::Result<_, _>>()
+            .unwrap();
+            assert_eq!(&outputs[0], expected);
+        }
+    }
+
+    fn voprf<CS: CipherSuite>()
+    where
+        <CS::Group as Group>::Elem: Debug,
+    {
+        let mut rng = OsRng;
+        let server = VoprfServer::<CS>::new(&mut rng).unwrap();
+        let expected = server.evaluate(b"input").unwrap();
+
+        let local = VoprfProviderServer::new(server.get_private_key()).unwrap();
+        assert_eq!(local.get_public_key(), server.get_public_key());
+        voprf_round_trip(&local, &expected);
+
+        let remote = VoprfProviderServer::new(RemoteSigner::new(server.get_private_key())).unwrap();
+        assert_eq!(remote.get_public_key(), server.get_public_key());
+        voprf_round_trip(&remote, &expected);
+
+        #[cfg(feature = "alloc")]
+        {
+            let message = VoprfClient::<CS>::blind(b"input", &mut rng)
+                .unwrap()
+                .message;
+            let blinded_elements = ::alloc::vec![message; MAX_BATCH_SIZE + 1];
+            let calls = remote.provider().calls.get();
+            assert_eq!(
+                remote
+                    .batch_blind_evaluate(&mut rng, &blinded_elements)
+                    .err(),
+                Some(Error::Batch)
+            );
+            assert_eq!(remote.provider().calls.get(), calls);
+        }
+
+        // A provider with a different key than announced.
+        let dishonest = VoprfProviderServer {
+            provider: VoprfServer::<CS>::new(&mut rng).unwrap().get_private_key(),
+            pk: server.get_public_key().0,
+        };
+        let client_blind_result = VoprfClient::<CS>::blind(b"input", &mut rng).unwrap();
+        let result = dishonest
+            .blind_evaluate(&mut rng, &client_blind_result.message)
+            .unwrap();
+        assert_eq!(
+            client_blind_result.state.finalize(
+                b"input",
+                &result.message,
+                &result.proof,
+                &server.get_public_key(),
+            ),
+            Err(Error::ProofVerification)
+        );
+
+        let offline = RemoteSigner::new(server.get_private_key());
+        offline.offline.set(true);
+        assert_eq!(
+            VoprfProviderServer::new(offline).err(),
+            Some(Error::KeyProvider)
+        );
+        remote.provider().offline.set(true);
+        assert_eq!(
+            remote
+                .blind_evaluate(&mut rng, &client_blind_result.message)
+                .err(),
+            Some(Error::KeyProvider)
+        );
+    }
+
+    fn poprf_round_trip<CS: CipherSuite, P: PoprfKeyProvider<CS>>(
+        server: &PoprfProviderServer<CS, P>,
+        expected: &OprfOutput<CS>,
+    ) {
+        let mut rng = OsRng;
+        let info = Some(b"info".as_slice());
+        let client_blind_result = PoprfClient::<CS>::blind(b"input", &mut rng).unwrap();
+        let result = server
+            .blind_evaluate(&mut rng, &client_blind_result.message, info)
+            .unwrap();
+        let output = client_blind_result
+            .state
+            .finalize(
+                b"input",
+                &result.message,
+                &result.proof,
+                &server.get_public_key(),
+                info,
+            )
+            .unwrap();
+        assert_eq!(&output, expected);
+
+        #[cfg(feature = "alloc")]
+        {
+            let inputs = [b"input".as_slice(), b"other"];
+            let client_blind_results: Vec<_> = inputs
+                .iter()
+                .map(|input| PoprfClient::<CS>::blind(input, &mut rng).unwrap())
+                .collect();
+            let blinded_elements: Vec<_> = client_blind_results
+                .iter()
+                .map(|result| result.message.clone())
+                .collect();
+            let result = server
+                .batch_blind_evaluate(&mut rng, &blinded_elements, info)
+                .unwrap();
+            let clients: Vec<_> = client_blind_results
+                .iter()
+                .map(|result| result.state.clone())
+                .collect();
+            let outputs: Vec<_> = PoprfClient::batch_finalize(
+                inputs.iter().copied(),
+                &clients,
+                &result.messages,
+                &result.proof,
+                &server.get_public_key(),
+                info,
+            )
+            .unwrap()
+            .collect::<core::result::Result<_, _>>()
+            .unwrap();
+            assert_eq!(&outputs[0], expected);
+        }
+    }
+
+    fn poprf<CS: CipherSuite>()
+    where
+        <CS::Group as Group>::Elem: Debug,
+    {
+        let mut rng = OsRng;
+        let info = Some(b"info".as_slice());
+        let server = PoprfServer::<CS>::new(&mut rng).unwrap();
+        let expected = server.evaluate(b"input", info).unwrap();
+
+        let local = PoprfProviderServer::new(server.get_private_key()).unwrap();
+        assert_eq!(local.get_public_key(), server.get_public_key());
+        poprf_round_trip(&local, &expected);
+
+        let remote = PoprfProviderServer::new(RemoteSigner::new(server.get_private_key())).unwrap();
+        assert_eq!(remote.get_public_key(), server.get_public_key());
+        poprf_round_trip(&remote, &expected);
+
+        // Without fresh randomness the proof is the one of the server.
+        let client_blind_result = PoprfClient::<CS>::blind(b"input", &mut rng).unwrap();
+        let result = remote
+            .blind_evaluate(&mut FailingRng, &client_blind_result.message, info)
+            .unwrap();
+        let deterministic_result = server
+            .blind_evaluate_deterministic(&client_blind_result.message, info)
+            .unwrap();
+        assert_eq!(
+            result.proof.serialize(),
+            deterministic_result.proof.serialize()
+        );
+
+        #[cfg(feature = "alloc")]
+        {
+            let calls = remote.provider().calls.get();
+            remote
+                .batch_blind_evaluate(
+                    &mut rng,
+                    core::slice::from_ref(&client_blind_result.message),
+                    info,
+                )
+                .unwrap();
+            // One round trip to multiply and two to prove.
+            assert_eq!(remote.provider().calls.get(), calls + 3);
+
+            let blinded_elements =
+                ::alloc::vec![client_blind_result.message.clone(); MAX_BATCH_SIZE + 1];
+            let calls = remote.provider().calls.get();
+            assert_eq!(
+                remote
+                    .batch_blind_evaluate(&mut rng, &blinded_elements, info)
+                    .err(),
+                Some(Error::Batch)
+            );
+            assert_eq!(remote.provider().calls.get(), calls);
+        }
+
+        // A provider with a different key than announced.
+        let dishonest = PoprfProviderServer {
+            provider: PoprfServer::<CS>::new(&mut rng).unwrap().get_private_key(),
+            pk: server.get_public_key().0,
+        };
+        let result = dishonest
+            .blind_evaluate(&mut rng, &client_blind_result.message, info)
+            .unwrap();
+        assert_eq!(
+            client_blind_result.state.finalize(
+                b"input",
+                &result.message,
+                &result.proof,
+                &server.get_public_key(),
+                info,
+            ),
+            Err(Error::ProofVerification)
+        );
+
+        let long_info = [0; u16::MAX as usize + 1];
+        let calls = remote.provider().calls.get();
+        assert_eq!(
+            remote
+                .blind_evaluate(&mut rng, &client_blind_result.message, Some(&long_info))
+                .err(),
+            Some(Error::Info)
+        );
+        assert_eq!(remote.provider().calls.get(), calls);
+
+        remote.provider().offline.set(true);
+        assert_eq!(
+            remote
+                .blind_evaluate(&mut rng, &client_blind_result.message, info)
+                .err(),
+            Some(Error::KeyProvider)
+        );
+    }
+
+    #[test]
+    fn test_functionality() {
+        use p256::NistP256;
+        use p384::NistP384;
+        use p521::NistP521;
+
+        #[cfg(feature = "ristretto255")]
+        {
+            use crate::Ristretto255;
+
+            oprf::<Ristretto255>();
+            voprf::<Ristretto255>();
+            poprf::<Ristretto255>();
+        }
+
+        oprf::<NistP256>();
+        voprf::<NistP256>();
+        poprf::<NistP256>();
+
+        oprf::<NistP384>();
+        voprf::<NistP384>();
+        poprf::<NistP384>();
+
+        oprf::<NistP521>();
+        voprf::<NistP521>();
+        poprf::<NistP521>();
+    }
+}
@@ -0,0 +1,711 @@
+// Copyright (c) Meta Platforms, Inc. and affiliates.
+//
+// This source code is dual-licensed under either the MIT license found in the
+// LICENSE-MIT file in the root directory of this source tree or the Apache
+// License, Version 2.0 found in the LICENSE-APACHE file in the root directory
+// of this source tree. You may select, at your option, one of the above-listed
+// licenses.
+
+//! Key sets for rotating the keys of verifiable servers
+//!
+//! A [`ServerKeySet`] holds several servers, each identified by its key ID,
+//! the [`PublicKey::fingerprint()`], and valid in a [`KeyValidity`] window.
+//! New evaluations use the [current](ServerKeySet::current) key, while older
+//! keys can still be used until they expire. Clients receive the matching
+//! [`PublicKeySet`] and select the key by the key ID the server responds with.
+//!
+//! Times are passed in by the caller, usually the seconds since the UNIX
+//! epoch.
+
+use alloc::vec::Vec;
+
+use derive_where::derive_where;
+use digest::{Output, OutputSizeUser};
+use generic_array::ArrayLength;
+use rand_core::{TryCryptoRng, TryRngCore};
+
+use crate::poprf::PoprfServerEvaluateResult;
+use crate::{
+    BlindedElement, CipherSuite, Error, EvaluationElement, Group, OprfOutput, PoprfClient,
+    PoprfMode, PoprfServer, Proof, PublicKey, Result, VoprfClient, VoprfMode, VoprfServer,
+    VoprfServerEvaluateResult,
+};
+
+////////////////////////////
+// High-level API Structs //
+// ====================== //
+////////////////////////////
+
+/// The time window in which a key is active: from `activates_at` inclusive
+/// until `expires_at` exclusive. Key sets reject windows in which the key is
+/// never active, i.e. `expires_at <= activates_at`.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct KeyValidity {
+    /// The time the key is activated at.
+    pub activates_at: u64,
+    /// The time the key expires at, or [`None`] if it doesn't expire.
+    pub expires_at: Option<u64>,
+}
+
+/// Servers of a verifiable mode, identified by their key ID and each active in
+/// a [`KeyValidity`] window. `S` is either [`VoprfServer`] or
+/// [`PoprfServer`].
+#[derive_where(Clone; S)]
+#[derive_where(Debug; S)]
+pub struct ServerKeySet<CS: CipherSuite, S = VoprfServer<CS>>(KeySet<CS, S>);
+
+/// The [`PublicKey`]s of a [`ServerKeySet`], used by clients to select the key
+/// matching the key ID the server responds with.
+#[derive_where(Clone)]
+#[derive_where(Debug; <CS::Group as Group>::Elem)]
+pub struct PublicKeySet<CS: CipherSuite>(KeySet<CS, PublicKey<CS>>);
+
+#[derive_where(Clone; K)]
+#[derive_where(Debug; K)]
+struct KeySet<CS: CipherSuite, K> {
+    entries: Vec<KeySetEntry<CS, K>>,
+}
+
+#[derive_where(Clone; K)]
+#[derive_where(Debug; K)]
+struct KeySetEntry<CS: CipherSuite, K> {
+    key_id: Output<CS::Hash>,
+    validity: KeyValidity,
+    key: K,
+}
+
+/////////////////////////
+// API Implementations //
+// =================== //
+/////////////////////////
+
+impl KeyValidity {
+    /// Returns if the key is active at `now`.
+    pub fn is_active_at(&self, now: u64) -> bool {
+        self.activates_at <= now && !self.is_expired_at(now)
+    }
+
+    /// Returns if the key is expired at `now`, after which it can't become
+    /// active again.
+    pub fn is_expired_at(&self, now: u64) -> bool {
+        match self.expires_at {
+            Some(expires_at) => expires_at <= now,
+            None => false,
+        }
+    }
+}
+
+impl<CS: CipherSuite, S> ServerKeySet<CS, S> {
+    /// Creates an empty key set.
+    pub fn new() -> Self {
+        Self(KeySet::new())
+    }
+
+    /// Returns the server with `key_id` if it is active at `now`.
+    ///
+    /// # Errors
+    /// - [`Error::UnknownKeyId`] if no server has `key_id`.
+    /// - [`Error::InactiveKey`] if the server isn't active at `now`.
+    pub fn get(&self, key_id: &[u8], now: u64) -> Result<&S> {
+        self.0.get(key_id, now)
+    }
+
+    /// Returns the key ID and server to use for new evaluations at `now`, the
+    /// most recently activated of the active servers.
+    ///
+    /// # Errors
+    /// [`Error::InactiveKey`] if no server is active at `now`.
+    pub fn current(&self, now: u64) -> Result<(&Output<CS::Hash>, &S)> {
+        self.0.current(now)
+    }
+
+    /// Returns the [`KeyValidity`] of the server with `key_id`.
+    pub fn validity(&self, key_id: &[u8]) -> Option<KeyValidity> {
+        self.0.validity(key_id)
+    }
+
+    /// Removes and returns the server with `key_id`.
+    pub fn remove(&mut self, key_id: &[u8]) -> Option<S> {
+        self.0.remove(key_id)
+    }
+
+    /// Removes all servers expired at `now`.
+    pub fn remove_expired(&mut self, now: u64) {
+        self.0.remove_expired(now);
+    }
+
+    /// Returns an iterator over the key IDs of all servers, including inactive
+    /// ones.
+    pub fn key_ids(&self) -> impl Iterator<Item = &Output<CS::Hash>> {
+        self.0.key_ids()
+    }
+
+    /// Returns the number of servers.
+    pub fn len(&self) -> usize {
+        self.0.entries.len()
+    }
+
+    /// Returns if there are no servers.
+    pub fn is_empty(&self) -> bool {
+        self.0.entries.is_empty()
+    }
+}
+
+impl<CS: CipherSuite, S> Default for ServerKeySet<CS, S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<CS: CipherSuite> ServerKeySet<CS, VoprfServer<CS>> {
+    /// Adds `server`, active in `validity`, and returns its key ID.
+    ///
+    /// # Errors
+    /// - [`Error::DuplicateKeyId`] if a server with the same key is already in
+    ///   the key set.
+    /// - [`Error::KeyValidity`] if the server would never be active in
+    ///   `validity`.
+    pub fn insert(
+        &mut self,
+        server: VoprfServer<CS>,
+        validity: KeyValidity,
+    ) -> Result<Output<CS::Hash>> {
+        let key_id = server.get_public_key().fingerprint();
+        self.0.insert(key_id.clone(), validity, server)?;
+
+        Ok(key_id)
+    }
+
+    /// Returns the [`PublicKeySet`] to hand out to clients.
+    pub fn public_key_set(&self) -> PublicKeySet<CS> {
+        PublicKeySet(self.0.map(VoprfServer::get_public_key))
+    }
+
+    /// Evaluates `blinded_element` with the server with `key_id`, see
+    /// [`VoprfServer::blind_evaluate`].
+    ///
+    /// # Errors
+    /// - [`Error::UnknownKeyId`] if no server has `key_id`.
+    /// - [`Error::InactiveKey`] if the server isn't active at `now`.
+    pub fn blind_evaluate<R: TryRngCore + TryCryptoRng>(
+        &self,
+        key_id: &[u8],
+        now: u64,
+        rng: &mut R,
+        blinded_element: &BlindedElement<CS, VoprfMode>,
+    ) -> Result<VoprfServerEvaluateResult<CS>> {
+        Ok(self.get(key_id, now)?.blind_evaluate(rng, blinded_element))
+    }
+
+    /// Computes the VOPRF output of `input` with the server with `key_id`, see
+    /// [`VoprfServer::evaluate`].
+    ///
+    /// # Errors
+    /// - [`Error::UnknownKeyId`] if no server has `key_id`.
+    /// - [`Error::InactiveKey`] if the server isn't active at `now`.
+    /// - [`Error::Input`] if the `input` is empty or longer then [`u16::MAX`].
+    pub fn evaluate(&self, key_id: &[u8], now: u64, input: &[u8]) -> Result<OprfOutput<CS>> {
+        self.get(key_id, now)?.evaluate(input)
+    }
+}
+
+impl<CS: CipherSuite> ServerKeySet<CS, PoprfServer<CS>> {
+    /// Adds `server`, active in `validity`, and returns its key ID.
+    ///
+    /// # Errors
+    /// - [`Error::DuplicateKeyId`] if a server with the same key is already in
+    ///   the key set.
+    /// - [`Error::KeyValidity`] if the server would never be active in
+    ///   `validity`.
+    pub fn insert(
+        &mut self,
+        server: PoprfServer<CS>,
+        validity: KeyValidity,
+    ) -> Result<Output<CS::Hash>> {
+        let key_id = server.get_public_key().fingerprint();
+        self.0.insert(key_id.clone(), validity, server)?;
+
+        Ok(key_id)
+    }
+
+    /// Returns the [`PublicKeySet`] to hand out to clients.
+    pub fn public_key_set(&self) -> PublicKeySet<CS> {
+        PublicKeySet(self.0.map(PoprfServer::get_public_key))
+    }
+
+    /// Evaluates `blinded_element` with the server with `key_id`, see
+    /// [`PoprfServer::blind_evaluate`].
+    ///
+    /// # Errors
+    /// - [`Error::UnknownKeyId`] if no server has `key_id`.
+    /// - [`Error::InactiveKey`] if the server isn't active at `now`.
+    /// - [`Error::Info`] if the `info` is longer then `u16::MAX`.
+    /// - [`Error::Protocol`] if the protocol fails and can't be completed.
+    pub fn blind_evaluate<R: TryRngCore + TryCryptoRng>(
+        &self,
+        key_id: &[u8],
+        now: u64,
+        rng: &mut R,
+        blinded_element: &BlindedElement<CS, PoprfMode>,
+        info: Option<&[u8]>,
+    ) -> Result<PoprfServerEvaluateResult<CS>> {
+        self.get(key_id, now)?
+            .blind_evaluate(rng, blinded_element, info)
+    }
+
+    /// Computes the POPRF output of `input` with the server with `key_id`, see
+    /// [`PoprfServer::evaluate`].
+    ///
+    /// # Errors
+    /// - [`Error::UnknownKeyId`] if no server has `key_id`.
+    /// - [`Error::InactiveKey`] if the server isn't active at `now`.
+    /// - [`Error::Input`] if the `input` is empty or longer then [`u16::MAX`].
+    /// - [`Error::Info`] if the `info` is longer then `u16::MAX`.
+    /// - [`Error::Protocol`] if the protocol fails and can't be completed.
+    pub fn evaluate(
+        &self,
+        key_id: &[u8],
+        now: u64,
+        input: &[u8],
+        info: Option<&[u8]>,
+    ) -> Result<OprfOutput<CS>> {
+        self.get(key_id, now)?.evaluate(input, info)
+    }
+}
+
+impl<CS: CipherSuite> PublicKeySet<CS> {
+    /// Creates an empty key set.
+    pub fn new() -> Self {
+        Self(KeySet::new())
+    }
+
+    /// Adds `public_key`, active in `validity`, and returns its key ID.
+    ///
+    /// # Errors
+    /// - [`Error::DuplicateKeyId`] if the key is already in the key set.
+    /// - [`Error::KeyValidity`] if the key would never be active in
+    ///   `validity`.
+    pub fn insert(
+        &mut self,
+        public_key: PublicKey<CS>,
+        validity: KeyValidity,
+    ) -> Result<Output<CS::Hash>> {
+        let key_id = public_key.fingerprint();
+        self.0.insert(key_id.clone(), validity, public_key)?;
+
+        Ok(key_id)
+    }
+
+    /// Returns the key with `key_id` if it is active at `now`.
+    ///
+    /// # Errors
+    /// - [`Error::UnknownKeyId`] if no key has `key_id`.
+    /// - [`Error::InactiveKey`] if the key isn't active at `now`.
+    pub fn get(&self, key_id: &[u8], now: u64) -> Result<&PublicKey<CS>> {
+        self.0.get(key_id, now)
+    }
+
+    /// Returns the key ID and key the server uses for new evaluations at
+    /// `now`, see [`ServerKeySet::current`].
+    ///
+    /// # Errors
+    /// [`Error::InactiveKey`] if no key is active at `now`.
+    pub fn current(&self, now: u64) -> Result<(&Output<CS::Hash>, &PublicKey<CS>)> {
+        self.0.current(now)
+    }
+
+    /// Returns the [`KeyValidity`] of the key with `key_id`.
+    pub fn validity(&self, key_id: &[u8]) -> Option<KeyValidity> {
+        self.0.validity(key_id)
+    }
+
+    /// Removes and returns the key with `key_id`.
+    pub fn remove(&mut self, key_id: &[u8]) -> Option<PublicKey<CS>> {
+        self.0.remove(key_id)
+    }
+
+    /// Removes all keys expired at `now`.
+    pub fn remove_expired(&mut self, now: u64) {
+        self.0.remove_expired(now);
+    }
+
+    /// Returns an iterator over the key IDs of all keys, including inactive
+    /// ones.
+    pub fn key_ids(&self) -> impl Iterator<Item = &Output<CS::Hash>> {
+        self.0.key_ids()
+    }
+
+    /// Returns the number of keys.
+    pub fn len(&self) -> usize {
+        self.0.entries.len()
+    }
+
+    /// Returns if there are no keys.
+    pub fn is_empty(&self) -> bool {
+        self.0.entries.is_empty()
+    }
+
+    /// Finalizes `client` against the key with `key_id`, see
+    /// [`VoprfClient::finalize`].
+    ///
+    /// # Errors
+    /// - [`Error::UnknownKeyId`] if no key has `key_id`.
+    /// - [`Error::InactiveKey`] if the key isn't active at `now`.
+    /// - [`Error::Input`] if the `input` is empty or longer then [`u16::MAX`].
+    /// - [`Error::ProofVerification`] if the `proof` failed to verify.
+    pub fn finalize_voprf(
+        &self,
+        key_id: &[u8],
+        now: u64,
+        client: &VoprfClient<CS>,
+        input: &[u8],
+        evaluation_element: &EvaluationElement<CS, VoprfMode>,
+        proof: &Proof<CS>,
+    ) -> Result<OprfOutput<CS>> {
+        client.finalize(input, evaluation_element, proof, self.get(key_id, now)?)
+    }
+
+    /// Finalizes `client` against the key with `key_id`, see
+    /// [`PoprfClient::finalize`].
+    ///
+    /// # Errors
+    /// - [`Error::UnknownKeyId`] if no key has `key_id`.
+    /// - [`Error::InactiveKey`] if the key isn't active at `now`.
+    /// - [`Error::Input`] if the `input` is empty or longer then [`u16::MAX`].
+    /// - [`Error::Info`] if the `info` is longer then `u16::MAX`.
+    /// - [`Error::Protocol`] if the protocol fails and can't be completed.
+    /// - [`Error::ProofVerification`] if the `proof` failed to verify.
+    #[allow(clippy::too_many_arguments)]
+    pub fn finalize_poprf(
+        &self,
+        key_id: &[u8],
+        now: u64,
+        client: &PoprfClient<CS>,
+        input: &[u8],
+        evaluation_element: &EvaluationElement<CS, PoprfMode>,
+        proof: &Proof<CS>,
+        info: Option<&[u8]>,
+    ) -> Result<OprfOutput<CS>>
+    where
+        <<CS as CipherSuite>::Hash as OutputSizeUser>::OutputSize: ArrayLength,
+    {
+        client.finalize(
+            input,
+            evaluation_element,
+            proof,
+            self.get(key_id, now)?,
+            info,
+        )
+    }
+}
+
+impl<CS: CipherSuite> Default for PublicKeySet<CS> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/////////////////////
+// Inner functions //
+// =============== //
+/////////////////////
+
+impl<CS: CipherSuite, K> KeySet<CS, K> {
+    fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+        }
+    }
+
+    fn insert(&mut self, key_id: Output<CS::Hash>, validity: KeyValidity, key: K) -> Result<()> {
+        if validity
+            .expires_at
+            .is_some_and(|expires_at| expires_at <= validity.activates_at)
+        {
+            return Err(Error::KeyValidity);
+        }
+
+        if self.entry(&key_id).is_some() {
+            return Err(Error::DuplicateKeyId);
+        }
+
+        self.entries.push(KeySetEntry {
+            key_id,
+            validity,
+            key,
+        });
+
+        Ok(())
+    }
+
+    fn entry(&self, key_id: &[u8]) -> Option<&KeySetEntry<CS, K>> {
+        self.entries
+            .iter()
+            .find(|entry| entry.key_id[..] == *key_id)
+    }
+
+    fn get(&self, key_id: &[u8], now: u64) -> Result<&K> {
+        let entry = self.entry(key_id).ok_or(Error::UnknownKeyId)?;
+
+        if entry.validity.is_active_at(now) {
+            Ok(&entry.key)
+        } else {
+            Err(Error::InactiveKey)
+        }
+    }
+
+    fn current(&self, now: u64) -> Result<(&Output<CS::Hash>, &K)> {
+        // On equal activation times, the key inserted last wins.
+        self.entries
+            .iter()
+            .filter(|entry| entry.validity.is_active_at(now))
+            .max_by_key(|entry| entry.validity.activates_at)
+            .map(|entry| (&entry.key_id, &entry.key))
+            .ok_or(Error::InactiveKey)
+    }
+
+    fn validity(&self, key_id: &[u8]) -> Option<KeyValidity> {
+        self.entry(key_id).map(|entry| entry.validity)
+    }
+
+    fn remove(&mut self, key_id: &[u8]) -> Option<K> {
+        let index = self
+            .entries
+            .iter()
+            .position(|entry| entry.key_id[..] == *key_id)?;
+
+        Some(self.entries.remove(index).key)
+    }
+
+    fn remove_expired(&mut self, now: u64) {
+        self.entries
+            .retain(|entry| !entry.validity.is_expired_at(now));
+    }
+
+    fn key_ids(&self) -> impl Iterator<Item = &Output<CS::Hash>> {
+        self.entries.iter().map(|entry| &entry.key_id)
+    }
+
+    fn map<T>(&self, mut f: impl FnMut(&K) -> T) -> KeySet<CS, T> {
+        KeySet {
+            entries: self
+                .entries
+                .iter()
+                .map(|entry| KeySetEntry {
+                    key_id: entry.key_id.clone(),
+                    validity: entry.validity,
+                    key: f(&entry.key),
+                })
+                .collect(),
+        }
+    }
+}
+
+///////////
+// Tests //
+// ===== //
+///////////
+
+#[cfg(test)]
+mod tests {
+    use core::fmt::Debug;
+
+    use rand::rngs::OsRng;
+
+    use super::*;
+
+    const HOUR: u64 = 60 * 60;
+
+    fn validity(activates_at: u64, expires_at: u64) -> KeyValidity {
+        KeyValidity {
+            activates_at,
+            expires_at: Some(expires_at),
+        }
+    }
+
+    fn voprf<CS: CipherSuite>()
+    where
+        <CS::Group as Group>::Elem: Debug,
+    {
+        let mut rng = OsRng;
+        let old = VoprfServer::<CS>::new(&mut rng).unwrap();
+        let new = VoprfServer::<CS>::new(&mut rng).unwrap();
+
+        let mut servers = ServerKeySet::<CS, VoprfServer<CS>>::new();
+        let old_id = servers.insert(old.clone(), validity(0, 3 * HOUR)).unwrap();
+        let new_id = servers
+            .insert(
+                new.clone(),
+                KeyValidity {
+                    activates_at: 2 * HOUR,
+                    expires_at: None,
+                },
+            )
+            .unwrap();
+        assert_eq!(old_id, old.get_public_key().fingerprint());
+        assert_eq!(servers.len(), 2);
+        assert_eq!(
+            servers.insert(old.clone(), validity(0, HOUR)).err(),
+            Some(Error::DuplicateKeyId)
+        );
+
+        let keys = servers.public_key_set();
+        assert_eq!(keys.len(), 2);
+        assert_eq!(keys.validity(&old_id), servers.validity(&old_id));
+
+        // Before the rotation only the old key is current, afterwards the new
+        // one while the old one stays usable until it expires.
+        for (now, current_id) in [(HOUR, &old_id), (2 * HOUR, &new_id), (4 * HOUR, &new_id)] {
+            let (key_id, _) = servers.current(now).unwrap();
+            assert_eq!(key_id, current_id);
+            assert_eq!(keys.current(now).unwrap().0, current_id);
+
+            let client_blind_result = VoprfClient::<CS>::blind(b"input", &mut rng).unwrap();
+            let result = servers
+                .blind_evaluate(key_id, now, &mut rng, &client_blind_result.message)
+                .unwrap();
+            let output = keys
+                .finalize_voprf(
+                    key_id,
+                    now,
+                    &client_blind_result.state,
+                    b"input",
+                    &result.message,
+                    &result.proof,
+                )
+                .unwrap();
+            assert_eq!(output, servers.evaluate(key_id, now, b"input").unwrap());
+
+            // The proof doesn't verify against the other key.
+            let other_id = if key_id == &old_id { &new_id } else { &old_id };
+            if keys.get(other_id, now).is_ok() {
+                assert_eq!(
+                    keys.finalize_voprf(
+                        other_id,
+                        now,
+                        &client_blind_result.state,
+                        b"input",
+                        &result.message,
+                        &result.proof,
+                    ),
+                    Err(Error::ProofVerification)
+                );
+            }
+        }
+
+        assert!(servers.get(&old_id, 2 * HOUR).is_ok());
+        assert_eq!(
+            servers.evaluate(&old_id, 3 * HOUR, b"input"),
+            Err(Error::InactiveKey)
+        );
+        assert_eq!(keys.get(&old_id, 3 * HOUR), Err(Error::InactiveKey));
+        assert_eq!(
+            servers.evaluate(&new_id, HOUR, b"input"),
+            Err(Error::InactiveKey)
+        );
+        assert_eq!(
+            servers.evaluate(&[0; 32], HOUR, b"input"),
+            Err(Error::UnknownKeyId)
+        );
+        assert_eq!(keys.get(&new_id[1..], HOUR), Err(Error::UnknownKeyId));
+
+        servers.remove_expired(3 * HOUR);
+        assert_eq!(servers.key_ids().collect::<Vec<_>>(), [&new_id]);
+        assert_eq!(
+            servers.remove(&new_id).unwrap().get_public_key(),
+            new.get_public_key()
+        );
+        assert!(servers.is_empty());
+        assert_eq!(servers.current(HOUR).err(), Some(Error::InactiveKey));
+
+        let mut keys = keys;
+        assert_eq!(keys.remove(&old_id), Some(old.get_public_key()));
+        assert_eq!(
+            keys.insert(new.get_public_key(), validity(0, HOUR)),
+            Err(Error::DuplicateKeyId)
+        );
+
+        // Windows in which the key is never active are rejected.
+        let other = VoprfServer::<CS>::new(&mut rng).unwrap();
+        for (activates_at, expires_at) in [(HOUR, HOUR), (2 * HOUR, HOUR)] {
+            let validity = validity(activates_at, expires_at);
+            assert_eq!(
+                servers.insert(other.clone(), validity).err(),
+                Some(Error::KeyValidity)
+            );
+            assert_eq!(
+                keys.insert(other.get_public_key(), validity).err(),
+                Some(Error::KeyValidity)
+            );
+        }
+        assert!(servers.is_empty());
+    }
+
+    fn poprf<CS: CipherSuite>() {
+        let mut rng = OsRng;
+        let old = PoprfServer::<CS>::new(&mut rng).unwrap();
+        let new = PoprfServer::<CS>::new(&mut rng).unwrap();
+        let info = Some(b"info".as_slice());
+
+        let mut servers = ServerKeySet::<CS, PoprfServer<CS>>::new();
+        let old_id = servers.insert(old, validity(0, 3 * HOUR)).unwrap();
+        let new_id = servers.insert(new, validity(2 * HOUR, 5 * HOUR)).unwrap();
+        let keys = servers.public_key_set();
+
+        for (now, current_id) in [(HOUR, &old_id), (2 * HOUR, &new_id)] {
+            let (key_id, _) = servers.current(now).unwrap();
+            assert_eq!(key_id, current_id);
+
+            let client_blind_result = PoprfClient::<CS>::blind(b"input", &mut rng).unwrap();
+            let result = servers
+                .blind_evaluate(key_id, now, &mut rng, &client_blind_result.message, info)
+                .unwrap();
+            let output = keys
+                .finalize_poprf(
+                    key_id,
+                    now,
+                    &client_blind_result.state,
+                    b"input",
+                    &result.message,
+                    &result.proof,
+                    info,
+                )
+                .unwrap();
+            assert!(output == servers.evaluate(key_id, now, b"input", info).unwrap());
+        }
+
+        assert!(servers.evaluate(&old_id, 2 * HOUR, b"input", info).is_ok());
+        assert_eq!(
+            servers.evaluate(&old_id, 3 * HOUR, b"input", info),
+            Err(Error::InactiveKey)
+        );
+        assert_eq!(servers.current(5 * HOUR).err(), Some(Error::InactiveKey));
+
+        servers.remove_expired(5 * HOUR);
+        assert!(servers.is_empty());
+    }
+
+    #[test]
+    fn test_functionality() {
+        use p256::NistP256;
+        use p384::NistP384;
+        use p521::NistP521;
+
+        #[cfg(feature = "ristretto255")]
+        {
+            use crate::Ristretto255;
+
+            voprf::<Ristretto255>();
+            poprf::<Ristretto255>();
+        }
+
+        voprf::<NistP256>();
+        poprf::<NistP256>();
+
+        voprf::<NistP384>();
+        poprf::<NistP384>();
+
+        voprf::<NistP521>();
+        poprf::<NistP521>();
+    }
+}
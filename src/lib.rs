@@ -137,7 +137,7 @@
 //!     .finalize(b"input", &message)
 //!     .expect("Unable to perform client finalization");
 //!
-//! println!("VOPRF output: {:?}", client_finalize_result.to_vec());
+//! println!("VOPRF output: {:?}", client_finalize_result.serialize());
 //! ```
 //!
 //! ### Server Evaluation
@@ -297,11 +297,11 @@
 //!         b"input",
 //!         &server_evaluate_result.message,
 //!         &server_evaluate_result.proof,
-//!         server.get_public_key(),
+//!         &server.get_public_key(),
 //!     )
 //!     .expect("Unable to perform client finalization");
 //!
-//! println!("VOPRF output: {:?}", client_finalize_result.to_vec());
+//! println!("VOPRF output: {:?}", client_finalize_result.serialize());
 //! ```
 //!
 //! ### Server Evaluation
@@ -338,7 +338,7 @@
 //!         b"input",
 //!         &server_evaluate_result.message,
 //!         &server_evaluate_result.proof,
-//!         server.get_public_key(),
+//!         &server.get_public_key(),
 //!     )
 //!     .expect("Unable to perform client finalization");
 //!
@@ -488,7 +488,7 @@
 //!     &client_states,
 //!     &messages,
 //!     &proof,
-//!     server.get_public_key(),
+//!     &server.get_public_key(),
 //! )
 //! .expect("Unable to perform client batch finalization")
 //! .collect::<Vec<_>>();
@@ -497,6 +497,11 @@
 //! # }
 //! ```
 //!
+//! A single proof can cover at most [`MAX_BATCH_SIZE`] elements. Larger
+//! batches can be streamed through `VoprfServer::batch_blind_evaluate_chunked`,
+//! which returns one [Proof] per chunk, and finalized in the same order by
+//! `VoprfClient::batch_finalize_chunked`. Both require the `alloc` feature.
+//!
 //! ## Metadata
 //!
 //! The optional metadata parameter included in the POPRF mode allows clients
@@ -513,13 +518,70 @@
 //! <https://www.rfc-editor.org/rfc/rfc9497#name-poprf-public-input>
 //! for more detailed information on how this public input should be used.
 //!
+//! ## External Keys
+//!
+//! The private key of a server can be held outside of the process, e.g. in an
+//! HSM, by implementing [OprfKeyProvider] and using an [OprfProviderServer] or
+//! a [VoprfProviderServer]. For the POPRF, the provider additionally
+//! implements [PoprfKeyProvider], to be used with a [PoprfProviderServer].
+//!
+//! ## ECVRF
+//!
+//! Independently of the OPRF modes, an [EcvrfProver] computes the verifiable
+//! random function specified in
+//! [RFC 9381](https://www.rfc-editor.org/rfc/rfc9381) over the same [Group]
+//! implementations. The prover produces an [EcvrfProof] for an input, from
+//! which anyone holding the prover's public key can verify and derive the VRF
+//! output.
+//!
+//! RFC 9381 only defines ciphersuites over NIST P-256 among the supported
+//! groups, which are available with the `p256` feature.
+//!
+//! ```
+//! # #[cfg(feature = "p256")]
+//! # {
+//! use rand::rngs::OsRng;
+//! use voprf::{EcvrfP256Sha256Sswu, EcvrfProver};
+//!
+//! let mut rng = OsRng;
+//! let prover =
+//!     EcvrfProver::<EcvrfP256Sha256Sswu>::new(&mut rng).expect("Unable to construct prover");
+//! let proof = prover.prove(b"input").expect("Unable to compute proof");
+//!
+//! let output = proof
+//!     .verify(prover.get_public_key(), b"input")
+//!     .expect("Unable to verify proof");
+//! # }
+//! ```
+//!
 //! # Features
 //!
 //! - The `alloc` feature requires Rust's `alloc` crate and enables batching
-//!   VOPRF evaluations.
+//!   VOPRF evaluations, versioned key files, e.g.
+//!   `VoprfServer::to_key_file()`, and key sets for rotating keys, e.g.
+//!   `ServerKeySet`.
+//!
+//! - The `cbor` feature provides CBOR encodings of messages and COSE_Key
+//!   encodings of public keys, e.g. `PublicKey::to_cose_key()`. Requires the
+//!   `alloc` feature.
+//!
+//! - The `jwk` feature provides JSON Web Key encodings of public and private
+//!   keys for the NIST suites, e.g. `PrivateKey::to_jwk()`. Requires the
+//!   `alloc` feature.
+//!
+//! - The `pkcs8` feature provides PKCS#8 encodings of private keys and
+//!   SubjectPublicKeyInfo encodings of public keys, in DER and PEM, for the
+//!   NIST suites, e.g. `PrivateKey::to_pkcs8_pem()`. Requires the `alloc`
+//!   feature.
+//!
+//! - The `seal` feature provides password-sealed exports of servers, encrypted
+//!   with ChaCha20-Poly1305 under a key derived with Argon2id, e.g.
+//!   `VoprfServer::seal()`. Requires the `alloc` feature.
 //!
 //! - The `serde` feature, enabled by default, provides convenience functions
 //!   for serializing and deserializing with [serde](https://serde.rs/).
+//!   Human-readable formats, e.g. JSON, encode elements and scalars as hex
+//!   strings, other formats as raw bytes.
 //!
 //! - The `danger` feature, disabled by default, exposes functions for setting
 //!   and getting internal values not available in the default API. These
@@ -527,6 +589,12 @@
 //!   that need access to these raw values and are able to perform the necessary
 //!   validations on them (such as being valid group elements).
 //!
+//! - The `p256` feature enables the `ECVRF-P256-SHA256-TAI` and
+//!   `ECVRF-P256-SHA256-SSWU` ECVRF ciphersuites.
+//!
+//! - The `rayon` feature, disabled by default, enables parallel server-side
+//!   evaluation of many inputs with `par_evaluate_many`.
+//!
 //! - The `ristretto255-ciphersuite` features enables using [`Ristretto255`] as
 //!   a [`CipherSuite`].
 //!
@@ -554,12 +622,28 @@ extern crate alloc;
 #[cfg(feature = "std")]
 extern crate std;
 
+#[cfg(feature = "cbor")]
+mod cbor;
 mod ciphersuite;
 mod common;
+mod ecvrf;
+#[cfg(feature = "alloc")]
+mod envelope;
 mod error;
 mod group;
+#[cfg(feature = "jwk")]
+mod jwk;
+#[cfg(feature = "alloc")]
+mod key_file;
+mod key_provider;
+#[cfg(feature = "alloc")]
+mod key_set;
 mod oprf;
+#[cfg(feature = "pkcs8")]
+mod pkcs8;
 mod poprf;
+#[cfg(feature = "seal")]
+mod seal;
 mod serialization;
 mod voprf;
 
@@ -570,30 +654,70 @@ mod tests;
 
 pub use crate::ciphersuite::CipherSuite;
 #[cfg(feature = "danger")]
-pub use crate::common::derive_key;
+pub use crate::common::{derive_key, InsecureDebug};
+#[cfg(feature = "alloc")]
+pub use crate::common::{BatchIndexMap, BatchIndexMapIndices};
 pub use crate::common::{
-    BlindedElement, EvaluationElement, Mode, PreparedEvaluationElement, Proof,
+    BlindedElement, EvaluationElement, Mode, OprfMode, OprfOutput, PoprfMode,
+    PreparedEvaluationElement, PrivateKey, Proof, ProtocolMode, PublicKey, VoprfMode,
+    MAX_BATCH_SIZE,
+};
+pub use crate::ecvrf::{EcvrfChallengeLen, EcvrfProof, EcvrfProofLen, EcvrfProver, EcvrfSuite};
+#[cfg(feature = "p256")]
+pub use crate::ecvrf::{EcvrfP256Sha256Sswu, EcvrfP256Sha256Tai};
+#[cfg(feature = "alloc")]
+pub use crate::envelope::{
+    decode_request, decode_response, encode_request, encode_response, EnvelopeHeader,
+    EnvelopeRequest, EnvelopeResponse, MessageType, ENVELOPE_VERSION,
+};
+pub use crate::error::{
+    BatchError, DeserializationError, DeserializationReason, Error, InputError, InternalError,
+    ProtocolError, Result,
 };
-pub use crate::error::{Error, InternalError, Result};
 pub use crate::group::Group;
 #[cfg(feature = "ristretto255")]
 pub use crate::group::Ristretto255;
-pub use crate::oprf::{OprfClient, OprfClientBlindResult, OprfServer};
+#[cfg(feature = "jwk")]
+pub use crate::jwk::Jwk;
+#[cfg(feature = "alloc")]
+pub use crate::key_file::{KeyFileMetadata, KEY_FILE_VERSION};
+pub use crate::key_provider::{
+    OprfKeyProvider, OprfProviderServer, PoprfKeyProvider, PoprfProviderServer, VoprfProviderServer,
+};
 #[cfg(feature = "alloc")]
-pub use crate::poprf::PoprfServerBatchEvaluateResult;
+pub use crate::key_set::{KeyValidity, PublicKeySet, ServerKeySet};
+pub use crate::oprf::{
+    OprfClient, OprfClientBlindResult, OprfServer, OprfServerEvaluateManyResult,
+};
 pub use crate::poprf::{
-    PoprfClient, PoprfClientBatchFinalizeResult, PoprfPreparedTweak, PoprfServer,
-    PoprfServerBatchEvaluateFinishResult, PoprfServerBatchEvaluateFinishedMessages,
+    PoprfClient, PoprfClientBatchFinalizeResult, PoprfPreparedTweak, PoprfPreparedTweakedKey,
+    PoprfServer, PoprfServerBatchEvaluateFinishResult, PoprfServerBatchEvaluateFinishedMessages,
     PoprfServerBatchEvaluatePrepareResult, PoprfServerBatchEvaluatePreparedEvaluationElements,
+    PoprfServerEvaluateManyResult,
+};
+#[cfg(feature = "alloc")]
+pub use crate::poprf::{
+    PoprfClientBatchFinalizeChunks, PoprfClientBatchFinalizeFilteredResult,
+    PoprfServerBatchEvaluateChunks, PoprfServerBatchEvaluateFilteredResult,
+    PoprfServerBatchEvaluateMixedInfoResult, PoprfServerBatchEvaluateResult, PoprfTweakCache,
 };
+#[cfg(feature = "seal")]
+pub use crate::seal::{SealParams, SealedKeyMetadata, SEALED_KEY_VERSION};
 pub use crate::serialization::{
-    BlindedElementLen, EvaluationElementLen, OprfClientLen, OprfServerLen, PoprfClientLen,
-    PoprfServerLen, ProofLen, VoprfClientLen, VoprfServerLen,
+    BlindedElementLen, EvaluationElementLen, OprfClientLen, OprfOutputLen, OprfServerLen,
+    PoprfClientLen, PoprfServerLen, PrivateKeyLen, ProofLen, PublicKeyLen, Serializable,
+    VerifiedEvaluationLen, VoprfClientLen, VoprfServerLen,
 };
-#[cfg(feature = "alloc")]
-pub use crate::voprf::VoprfServerBatchEvaluateResult;
 pub use crate::voprf::{
-    VoprfClient, VoprfClientBatchFinalizeResult, VoprfClientBlindResult, VoprfServer,
+    verify_evaluation, VerifiedEvaluation, VoprfClient, VoprfClientBatchFinalizeResult,
+    VoprfClientBatchVerifyResult, VoprfClientBlindResult, VoprfServer,
     VoprfServerBatchEvaluateFinishResult, VoprfServerBatchEvaluateFinishedMessages,
-    VoprfServerBatchEvaluatePreparedEvaluationElements, VoprfServerEvaluateResult,
+    VoprfServerBatchEvaluatePreparedEvaluationElements, VoprfServerEvaluateManyResult,
+    VoprfServerEvaluateResult, VoprfServerEvaluateWithProofResult,
+};
+#[cfg(feature = "alloc")]
+pub use crate::voprf::{
+    VoprfClientBatchFinalizeChunks, VoprfClientBatchFinalizeFilteredResult,
+    VoprfServerBatchEvaluateChunks, VoprfServerBatchEvaluateFilteredResult,
+    VoprfServerBatchEvaluateResult,
 };
@@ -8,21 +8,30 @@
 
 //! Contains the main OPRF API
 
-use core::iter::{self, Map};
+#[cfg(feature = "rayon")]
+use alloc::vec::Vec;
+use core::fmt::{self, Debug, Formatter};
+use core::iter::{self, Map, Repeat, Zip};
+use core::marker::PhantomData;
 
 use derive_where::derive_where;
-use digest::{Digest, Output};
+use digest::Digest;
 use generic_array::typenum::Unsigned;
 use generic_array::GenericArray;
 use rand_core::{TryCryptoRng, TryRngCore};
+#[cfg(feature = "rayon")]
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
 
+#[cfg(feature = "danger")]
+use crate::common::InsecureDebug;
 use crate::common::{
     derive_key_internal, deterministic_blind_unchecked, hash_to_group, i2osp_2,
-    server_evaluate_hash_input, BlindedElement, EvaluationElement, Mode, STR_FINALIZE,
+    server_evaluate_hash_input, BlindedElement, EvaluationElement, Mode, OprfMode, OprfOutput,
+    PrivateKey, STR_FINALIZE,
 };
 #[cfg(feature = "serde")]
 use crate::serialization::serde::Scalar;
-use crate::{CipherSuite, Error, Group, Result};
+use crate::{CipherSuite, Error, Group, InputError, Result};
 
 ///////////////
 // Constants //
@@ -37,7 +46,7 @@ use crate::{CipherSuite, Error, Group, Result};
 /// A client which engages with a [OprfServer] in base mode, meaning
 /// that the OPRF outputs are not verifiable.
 #[derive_where(Clone, ZeroizeOnDrop)]
-#[derive_where(Debug, Eq, Hash, Ord, PartialEq, PartialOrd; <CS::Group as Group>::Scalar)]
+#[derive_where(Eq, Hash, Ord, PartialEq, PartialOrd; <CS::Group as Group>::Scalar)]
 #[cfg_attr(
     feature = "serde",
     derive(serde::Deserialize, serde::Serialize),
@@ -51,7 +60,7 @@ pub struct OprfClient<CS: CipherSuite> {
 /// A server which engages with a [OprfClient] in base mode, meaning
 /// that the OPRF outputs are not verifiable.
 #[derive_where(Clone, ZeroizeOnDrop)]
-#[derive_where(Debug, Eq, Hash, Ord, PartialEq, PartialOrd; <CS::Group as Group>::Scalar)]
+#[derive_where(Eq, Hash, Ord, PartialEq, PartialOrd; <CS::Group as Group>::Scalar)]
 #[cfg_attr(
     feature = "serde",
     derive(serde::Deserialize, serde::Serialize),
@@ -108,7 +117,7 @@ impl<CS: CipherSuite> OprfClient<CS> {
         let blinded_element = deterministic_blind_unchecked::<CS>(input, &blind, Mode::Oprf)?;
         Ok(OprfClientBlindResult {
             state: Self { blind },
-            message: BlindedElement(blinded_element),
+            message: BlindedElement(blinded_element, PhantomData),
         })
     }
 
@@ -120,8 +129,8 @@ impl<CS: CipherSuite> OprfClient<CS> {
     pub fn finalize(
         &self,
         input: &[u8],
-        evaluation_element: &EvaluationElement<CS>,
-    ) -> Result<Output<CS::Hash>> {
+        evaluation_element: &EvaluationElement<CS, OprfMode>,
+    ) -> Result<OprfOutput<CS>> {
         let unblinded_element = evaluation_element.0 * &CS::Group::invert_scalar(self.blind);
         let mut outputs =
             finalize_after_unblind::<CS, _, _>(iter::once((input, unblinded_element)), &[]);
@@ -145,10 +154,11 @@ impl<CS: CipherSuite> OprfServer<CS> {
     /// Produces a new instance of a [OprfServer] using a supplied RNG
     ///
     /// # Errors
-    /// [`Error::Protocol`] if the protocol fails and can't be completed.
+    /// - [`Error::Rng`] if the random number generator fails.
+    /// - [`Error::Protocol`] if the protocol fails and can't be completed.
     pub fn new<R: TryRngCore + TryCryptoRng>(rng: &mut R) -> Result<Self> {
         let mut seed = GenericArray::<_, <CS::Group as Group>::ScalarLen>::default();
-        rng.try_fill_bytes(&mut seed).map_err(|_| Error::Protocol)?;
+        rng.try_fill_bytes(&mut seed).map_err(|_| Error::Rng)?;
         Self::new_from_seed(&seed, &[])
     }
 
@@ -177,27 +187,36 @@ impl<CS: CipherSuite> OprfServer<CS> {
         Ok(Self { sk })
     }
 
-    /// Only used for tests
-    #[cfg(test)]
-    pub fn get_private_key(&self) -> <CS::Group as Group>::Scalar {
-        self.sk
+    /// Produces a new instance of a [OprfServer] from the server's
+    /// [`PrivateKey`].
+    pub fn new_with_private_key(private_key: &PrivateKey<CS>) -> Self {
+        Self { sk: private_key.0 }
+    }
+
+    /// Retrieves the server's private key
+    #[cfg(any(feature = "danger", test))]
+    pub fn get_private_key(&self) -> PrivateKey<CS> {
+        PrivateKey(self.sk)
     }
 
     /// Computes the second step for the multiplicative blinding version of
     /// DH-OPRF. This message is sent from the server (who holds the OPRF key)
     /// to the client.
-    pub fn blind_evaluate(&self, blinded_element: &BlindedElement<CS>) -> EvaluationElement<CS> {
-        EvaluationElement(blinded_element.0 * &self.sk)
+    pub fn blind_evaluate(
+        &self,
+        blinded_element: &BlindedElement<CS, OprfMode>,
+    ) -> EvaluationElement<CS, OprfMode> {
+        EvaluationElement(blinded_element.0 * &self.sk, PhantomData)
     }
 
     /// Computes the output of the OPRF on the server side
     ///
     /// # Errors
     /// [`Error::Input`]  if the `input` is longer then [`u16::MAX`].
-    pub fn evaluate(&self, input: &[u8]) -> Result<Output<<CS as CipherSuite>::Hash>> {
+    pub fn evaluate(&self, input: &[u8]) -> Result<OprfOutput<CS>> {
         let input_element = hash_to_group::<CS>(input, Mode::Oprf)?;
         if CS::Group::is_identity_elem(input_element).into() {
-            return Err(Error::Input);
+            return Err(Error::Input(InputError::HashToGroup));
         };
         let evaluated_element = input_element * &self.sk;
 
@@ -205,6 +224,103 @@ impl<CS: CipherSuite> OprfServer<CS> {
 
         server_evaluate_hash_input::<CS>(input, None, issued_element)
     }
+
+    /// Computes the output of the OPRF on the server side for each of the
+    /// `inputs`, in the same order.
+    ///
+    /// The resulting outputs can each fail individually with [`Error::Input`]
+    /// if the `input` is longer then [`u16::MAX`].
+    pub fn evaluate_many<I>(&self, inputs: I) -> OprfServerEvaluateManyResult<'_, CS, I::IntoIter>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<[u8]>,
+    {
+        inputs
+            .into_iter()
+            .zip(iter::repeat(self))
+            // Convert to `fn` pointer to make a return type possible.
+            .map(<fn((I::Item, &Self)) -> _>::from(|(input, server)| {
+                server.evaluate(input.as_ref())
+            }))
+    }
+
+    /// Parallel version of [`evaluate_many`](Self::evaluate_many). The outputs
+    /// are returned in the same order as the `inputs`.
+    ///
+    /// The resulting outputs can each fail individually with [`Error::Input`]
+    /// if the `input` is longer then [`u16::MAX`].
+    #[cfg(feature = "rayon")]
+    pub fn par_evaluate_many<I>(&self, inputs: I) -> Vec<Result<OprfOutput<CS>>>
+    where
+        Self: Sync,
+        I: IntoParallelIterator,
+        I::Item: AsRef<[u8]>,
+    {
+        inputs
+            .into_par_iter()
+            .map(|input| self.evaluate(input.as_ref()))
+            .collect()
+    }
+}
+
+impl<CS: CipherSuite> Debug for OprfClient<CS> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OprfClient")
+            .field("suite", &CS::ID)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "danger")]
+impl<CS: CipherSuite> OprfClient<CS> {
+    /// Includes the blind in the [`Debug`] output. It links the blinded
+    /// element seen by the server to this client's input.
+    pub fn insecure_debug(&self) -> InsecureDebug<'_, Self> {
+        InsecureDebug(self)
+    }
+}
+
+#[cfg(feature = "danger")]
+impl<CS: CipherSuite> Debug for InsecureDebug<'_, OprfClient<CS>>
+where
+    <CS::Group as Group>::Scalar: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OprfClient")
+            .field("blind", &self.0.blind)
+            .finish()
+    }
+}
+
+impl<CS: CipherSuite> Debug for OprfServer<CS> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        // Only the private key is stored, deriving the fingerprint would cost a
+        // scalar multiplication on every call.
+        f.debug_struct("OprfServer")
+            .field("suite", &CS::ID)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "danger")]
+impl<CS: CipherSuite> OprfServer<CS> {
+    /// Includes the private key in the [`Debug`] output, anyone who sees it
+    /// can evaluate the OPRF.
+    pub fn insecure_debug(&self) -> InsecureDebug<'_, Self> {
+        InsecureDebug(self)
+    }
+}
+
+#[cfg(feature = "danger")]
+impl<CS: CipherSuite> Debug for InsecureDebug<'_, OprfServer<CS>>
+where
+    <CS::Group as Group>::Scalar: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OprfServer")
+            .field("sk", &self.0.sk)
+            .finish()
+    }
 }
 
 /////////////////////////
@@ -218,18 +334,22 @@ pub struct OprfClientBlindResult<CS: CipherSuite> {
     /// The state to be persisted on the client
     pub state: OprfClient<CS>,
     /// The message to send to the server
-    pub message: BlindedElement<CS>,
+    pub message: BlindedElement<CS, OprfMode>,
 }
 
+/// Concrete return type for [`OprfServer::evaluate_many`].
+pub type OprfServerEvaluateManyResult<'a, CS, I> = Map<
+    Zip<I, Repeat<&'a OprfServer<CS>>>,
+    fn((<I as Iterator>::Item, &'a OprfServer<CS>)) -> Result<OprfOutput<CS>>,
+>;
+
 /////////////////////
 // Inner functions //
 // =============== //
 /////////////////////
 
-type FinalizeAfterUnblindResult<'a, C, I, IE> = Map<
-    IE,
-    fn((I, <<C as CipherSuite>::Group as Group>::Elem)) -> Result<Output<<C as CipherSuite>::Hash>>,
->;
+type FinalizeAfterUnblindResult<'a, C, I, IE> =
+    Map<IE, fn((I, <<C as CipherSuite>::Group as Group>::Elem)) -> Result<OprfOutput<C>>>;
 
 /// Returned values can only fail with [`Error::Input`].
 fn finalize_after_unblind<
@@ -248,13 +368,18 @@ fn finalize_after_unblind<
         //             I2OSP(len(unblindedElement), 2) || unblindedElement ||
         //             "Finalize"
         // return Hash(hashInput)
-        Ok(CS::Hash::new()
-            .chain_update(i2osp_2(input.as_ref().len()).map_err(|_| Error::Input)?)
-            .chain_update(input.as_ref())
-            .chain_update(elem_len)
-            .chain_update(CS::Group::serialize_elem(unblinded_element))
-            .chain_update(STR_FINALIZE)
-            .finalize())
+        Ok(OprfOutput(
+            CS::Hash::new()
+                .chain_update(
+                    i2osp_2(input.as_ref().len())
+                        .map_err(|_| Error::input_too_long(input.as_ref().len()))?,
+                )
+                .chain_update(input.as_ref())
+                .chain_update(elem_len)
+                .chain_update(CS::Group::serialize_elem(unblinded_element))
+                .chain_update(STR_FINALIZE)
+                .finalize(),
+        ))
     })
 }
 
@@ -267,19 +392,22 @@ fn finalize_after_unblind<
 mod tests {
     use core::ptr;
 
+    use ::alloc::vec;
+    use ::alloc::vec::Vec;
     use rand::rngs::OsRng;
     use rand::TryRngCore;
+    use subtle::ConstantTimeEq;
 
     use super::*;
     use crate::common::{Dst, STR_HASH_TO_GROUP};
-    use crate::Group;
+    use crate::{Group, OprfOutputLen};
 
     fn prf<CS: CipherSuite>(
         input: &[u8],
         key: <CS::Group as Group>::Scalar,
         info: &[u8],
         mode: Mode,
-    ) -> Output<CS::Hash> {
+    ) -> OprfOutput<CS> {
         let dst = Dst::new::<CS, _, _>(STR_HASH_TO_GROUP, mode);
         let point = CS::Group::hash_to_curve::<CS::Hash>(&[input], &dst.as_dst()).unwrap();
 
@@ -298,7 +426,7 @@ mod tests {
         let server = OprfServer::<CS>::new(&mut rng).unwrap();
         let message = server.blind_evaluate(&client_blind_result.message);
         let client_finalize_result = client_blind_result.state.finalize(input, &message).unwrap();
-        let res2 = prf::<CS>(input, server.get_private_key(), &[], Mode::Oprf);
+        let res2 = prf::<CS>(input, server.get_private_key().0, &[], Mode::Oprf);
         assert_eq!(client_finalize_result, res2);
     }
 
@@ -309,7 +437,10 @@ mod tests {
         let client_blind_result = OprfClient::<CS>::blind(&input, &mut rng).unwrap();
         let client_finalize_result = client_blind_result
             .state
-            .finalize(&input, &EvaluationElement(client_blind_result.message.0))
+            .finalize(
+                &input,
+                &EvaluationElement(client_blind_result.message.0, PhantomData),
+            )
             .unwrap();
 
         let dst = Dst::new::<CS, _, _>(STR_HASH_TO_GROUP, Mode::Oprf);
@@ -344,6 +475,76 @@ mod tests {
         let wrong_input = b"wrong input";
         let server_evaluate = server.evaluate(wrong_input).unwrap();
         assert!(client_finalize != server_evaluate);
+
+        // We expect batched outputs to match the individual ones in order
+        let inputs = [input.as_slice(), wrong_input];
+        let server_evaluate_many = server
+            .evaluate_many(inputs)
+            .collect::<Result<Vec<_>>>()
+            .unwrap();
+        assert_eq!(server_evaluate_many, [client_finalize, server_evaluate]);
+    }
+
+    #[cfg(feature = "rayon")]
+    fn par_server_evaluate<CS: CipherSuite>()
+    where
+        OprfServer<CS>: Sync,
+        <CS::Group as Group>::Scalar: Send + Sync,
+    {
+        let inputs = [b"input 1".as_slice(), b"input 2", b"input 3"];
+        let mut rng = OsRng;
+        let server = OprfServer::<CS>::new(&mut rng).unwrap();
+
+        let server_evaluate_many = server
+            .evaluate_many(inputs)
+            .collect::<Result<Vec<_>>>()
+            .unwrap();
+        let par_server_evaluate_many = server
+            .par_evaluate_many(inputs)
+            .into_iter()
+            .collect::<Result<Vec<_>>>()
+            .unwrap();
+        assert_eq!(server_evaluate_many, par_server_evaluate_many);
+    }
+
+    fn output_expand<CS: CipherSuite>() {
+        let mut rng = OsRng;
+        let server = OprfServer::<CS>::new(&mut rng).unwrap();
+        let output = server.evaluate(b"input").unwrap();
+        let hash_len = OprfOutputLen::<CS>::USIZE;
+
+        let output = OprfOutput::<CS>::deserialize(&output.serialize()).unwrap();
+        assert!(bool::from(
+            output.ct_eq(&server.evaluate(b"input").unwrap())
+        ));
+        assert!(output != server.evaluate(b"other input").unwrap());
+
+        // Subkeys of different labels are independent
+        let mut first = [0; 100];
+        let mut second = [0; 100];
+        output.expand_into(b"first", &mut first).unwrap();
+        output.expand_into(b"second", &mut second).unwrap();
+        assert_ne!(first, second);
+
+        // Shorter subkeys are a prefix of longer ones
+        let mut short = [0; 10];
+        output.expand_into(b"first", &mut short).unwrap();
+        assert_eq!(short, first[..10]);
+
+        let mut okm = vec![0; 255 * hash_len];
+        output.expand_into(b"label", &mut okm).unwrap();
+        let mut okm = vec![0; 255 * hash_len + 1];
+        let result = output.expand_into(b"label", &mut okm);
+        assert_eq!(result, Err(Error::ExpandLength));
+    }
+
+    fn zeroize_oprf_output<CS: CipherSuite>() {
+        let mut rng = OsRng;
+        let server = OprfServer::<CS>::new(&mut rng).unwrap();
+        let mut output = server.evaluate(b"input").unwrap();
+
+        unsafe { ptr::drop_in_place(&mut output) };
+        assert!(output.serialize().iter().all(|&x| x == 0));
     }
 
     fn zeroize_oprf_client<CS: CipherSuite>() {
@@ -375,6 +576,27 @@ mod tests {
         assert!(message.serialize().iter().all(|&x| x == 0));
     }
 
+    #[test]
+    fn test_hkdf_expand() {
+        use p256::NistP256;
+
+        // RFC 5869, A.1. Test Case 1
+        let prk = hex::decode("077709362c2e32df0ddc3f0dc47bba6390b6c73bb50f9c3122ec844ad7c2b3e5")
+            .unwrap();
+        let info = hex::decode("f0f1f2f3f4f5f6f7f8f9").unwrap();
+        let okm = hex::decode(
+            "3cb25f25faacd57a90434f64d0362f2a2d2d0a90cf1a5a4c5db02d56ecc4c5bf34007208d5b887185865",
+        )
+        .unwrap();
+
+        let output = OprfOutput::<NistP256>::deserialize(&prk).unwrap();
+        let mut result = [0; 42];
+        output.expand_into(&info, &mut result).unwrap();
+        assert_eq!(result.as_slice(), okm);
+        #[cfg(feature = "alloc")]
+        assert_eq!(*output.expand(&info, 42).unwrap(), okm);
+    }
+
     #[test]
     fn test_functionality() -> Result<()> {
         use p256::NistP256;
@@ -388,31 +610,47 @@ mod tests {
             base_retrieval::<Ristretto255>();
             base_inversion_unsalted::<Ristretto255>();
             server_evaluate::<Ristretto255>();
+            output_expand::<Ristretto255>();
+            #[cfg(feature = "rayon")]
+            par_server_evaluate::<Ristretto255>();
 
             zeroize_oprf_client::<Ristretto255>();
             zeroize_oprf_server::<Ristretto255>();
+            zeroize_oprf_output::<Ristretto255>();
         }
 
         base_retrieval::<NistP256>();
         base_inversion_unsalted::<NistP256>();
         server_evaluate::<NistP256>();
+        output_expand::<NistP256>();
+        #[cfg(feature = "rayon")]
+        par_server_evaluate::<NistP256>();
 
         zeroize_oprf_client::<NistP256>();
         zeroize_oprf_server::<NistP256>();
+        zeroize_oprf_output::<NistP256>();
 
         base_retrieval::<NistP384>();
         base_inversion_unsalted::<NistP384>();
         server_evaluate::<NistP384>();
+        output_expand::<NistP384>();
+        #[cfg(feature = "rayon")]
+        par_server_evaluate::<NistP384>();
 
         zeroize_oprf_client::<NistP384>();
         zeroize_oprf_server::<NistP384>();
+        zeroize_oprf_output::<NistP384>();
 
         base_retrieval::<NistP521>();
         base_inversion_unsalted::<NistP521>();
         server_evaluate::<NistP521>();
+        output_expand::<NistP521>();
+        #[cfg(feature = "rayon")]
+        par_server_evaluate::<NistP521>();
 
         zeroize_oprf_client::<NistP521>();
         zeroize_oprf_server::<NistP521>();
+        zeroize_oprf_output::<NistP521>();
 
         Ok(())
     }
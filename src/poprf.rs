@@ -8,24 +8,41 @@
 
 //! Contains the main POPRF API
 
+#[cfg(feature = "alloc")]
+use alloc::collections::btree_map::{BTreeMap, Entry};
+#[cfg(feature = "alloc")]
+use alloc::collections::VecDeque;
+#[cfg(feature = "alloc")]
+use alloc::vec;
 #[cfg(feature = "alloc")]
 use alloc::vec::Vec;
-use core::iter::{self, Map, Repeat, Zip};
+use core::fmt::{self, Debug, Formatter};
+use core::iter::{self, Enumerate, Map, Repeat, Zip};
+use core::marker::PhantomData;
 
 use derive_where::derive_where;
 use digest::{Digest, Output, OutputSizeUser};
 use generic_array::typenum::Unsigned;
 use generic_array::{ArrayLength, GenericArray};
 use rand_core::{TryCryptoRng, TryRngCore};
+#[cfg(feature = "rayon")]
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+#[cfg(feature = "alloc")]
+use subtle::ConstantTimeEq;
 
+#[cfg(feature = "danger")]
+use crate::common::InsecureDebug;
 use crate::common::{
-    derive_keypair, deterministic_blind_unchecked, generate_proof, hash_to_group, i2osp_2,
-    server_evaluate_hash_input, verify_proof, BlindedElement, Dst, EvaluationElement, Mode,
-    PreparedEvaluationElement, Proof, STR_FINALIZE, STR_HASH_TO_SCALAR, STR_INFO,
+    check_inputs, derive_keypair, deterministic_blind_unchecked, generate_proof, hash_to_group,
+    i2osp_2, proof_randomness, server_evaluate_hash_input, verify_proof, BlindedElement, Dst,
+    EvaluationElement, Hex, Mode, OprfOutput, PoprfMode, PreparedEvaluationElement, PrivateKey,
+    Proof, ProtocolMode, PublicKey, STR_FINALIZE, STR_HASH_TO_SCALAR, STR_INFO,
 };
+#[cfg(feature = "alloc")]
+use crate::common::{next_chunk, BatchIndexMap, MAX_BATCH_SIZE};
 #[cfg(feature = "serde")]
 use crate::serialization::serde::{Element, Scalar};
-use crate::{CipherSuite, Error, Group, Result};
+use crate::{BatchError, CipherSuite, Error, Group, InputError, ProtocolError, Result};
 
 ////////////////////////////
 // High-level API Structs //
@@ -35,7 +52,7 @@ use crate::{CipherSuite, Error, Group, Result};
 /// A client which engages with a [PoprfServer] in verifiable mode, meaning
 /// that the OPRF outputs can be checked against a server public key.
 #[derive_where(Clone, ZeroizeOnDrop)]
-#[derive_where(Debug, Eq, Hash, Ord, PartialEq, PartialOrd; <CS::Group as Group>::Scalar, <CS::Group as Group>::Elem)]
+#[derive_where(Eq, Hash, Ord, PartialEq, PartialOrd; <CS::Group as Group>::Scalar, <CS::Group as Group>::Elem)]
 #[cfg_attr(
     feature = "serde",
     derive(serde::Deserialize, serde::Serialize),
@@ -51,7 +68,7 @@ pub struct PoprfClient<CS: CipherSuite> {
 /// A server which engages with a [PoprfClient] in verifiable mode, meaning
 /// that the OPRF outputs can be checked against a server public key.
 #[derive_where(Clone, ZeroizeOnDrop)]
-#[derive_where(Debug, Eq, Hash, Ord, PartialEq, PartialOrd; <CS::Group as Group>::Scalar, <CS::Group as Group>::Elem)]
+#[derive_where(Eq, Hash, Ord, PartialEq, PartialOrd; <CS::Group as Group>::Scalar, <CS::Group as Group>::Elem)]
 #[cfg_attr(
     feature = "serde",
     derive(serde::Deserialize, serde::Serialize),
@@ -113,7 +130,7 @@ impl<CS: CipherSuite> PoprfClient<CS> {
                 blind,
                 blinded_element,
             },
-            message: BlindedElement(blinded_element),
+            message: BlindedElement(blinded_element, PhantomData),
         })
     }
 
@@ -128,11 +145,11 @@ impl<CS: CipherSuite> PoprfClient<CS> {
     pub fn finalize(
         &self,
         input: &[u8],
-        evaluation_element: &EvaluationElement<CS>,
+        evaluation_element: &EvaluationElement<CS, PoprfMode>,
         proof: &Proof<CS>,
-        pk: <CS::Group as Group>::Elem,
+        pk: &PublicKey<CS>,
         info: Option<&[u8]>,
-    ) -> Result<Output<CS::Hash>>
+    ) -> Result<OprfOutput<CS>>
     where
         <<CS as CipherSuite>::Hash as OutputSizeUser>::OutputSize: ArrayLength,
     {
@@ -141,7 +158,7 @@ impl<CS: CipherSuite> PoprfClient<CS> {
 
         let mut batch_result =
             Self::batch_finalize(iter::once(input), clients, messages, proof, pk, info)?;
-        batch_result.next().unwrap()
+        batch_result.next().unwrap().map_err(|error| error.error)
     }
 
     /// Allows for batching of the finalization of multiple [PoprfClient]
@@ -154,29 +171,360 @@ impl<CS: CipherSuite> PoprfClient<CS> {
     ///   don't match or is longer than [`u16::MAX`].
     /// - [`Error::ProofVerification`] if the `proof` failed to verify.
     ///
-    /// The resulting messages can each fail individually with [`Error::Input`]
-    /// if the `input` is empty or longer than [`u16::MAX`].
+    /// The resulting messages can each fail individually with a [`BatchError`]
+    /// of [`Error::Input`] and its index if the `input` is empty or longer than
+    /// [`u16::MAX`].
     pub fn batch_finalize<'a, II: 'a + Iterator<Item = &'a [u8]> + ExactSizeIterator, IC, IM>(
         inputs: II,
         clients: &'a IC,
         messages: &'a IM,
         proof: &Proof<CS>,
-        pk: <CS::Group as Group>::Elem,
+        pk: &PublicKey<CS>,
         info: Option<&'a [u8]>,
     ) -> Result<PoprfClientBatchFinalizeResult<'a, CS, II, IC, IM>>
     where
         CS: 'a,
         &'a IC: 'a + IntoIterator<Item = &'a PoprfClient<CS>>,
         <&'a IC as IntoIterator>::IntoIter: ExactSizeIterator,
-        &'a IM: 'a + IntoIterator<Item = &'a EvaluationElement<CS>>,
+        &'a IM: 'a + IntoIterator<Item = &'a EvaluationElement<CS, PoprfMode>>,
+        <&'a IM as IntoIterator>::IntoIter: ExactSizeIterator,
+        <<CS as CipherSuite>::Hash as OutputSizeUser>::OutputSize: ArrayLength,
+    {
+        let tweaked_key = compute_tweaked_key::<CS>(pk.0, info)?;
+        let unblinded_elements = poprf_unblind(clients, messages, tweaked_key, proof)?;
+
+        finalize_after_unblind::<'a, CS, _, _>(unblinded_elements, inputs, info)
+    }
+
+    /// Same as [`batch_finalize`](Self::batch_finalize), but checks all
+    /// `inputs` before verifying the `proof`, so the resulting messages can't
+    /// fail individually.
+    ///
+    /// # Errors
+    /// - [`Error::Input`] with the index of the first `input` which is empty
+    ///   or longer than [`u16::MAX`].
+    /// - [`Error::Info`] if the `info` is longer than `u16::MAX`.
+    /// - [`Error::Protocol`] if the protocol fails and can't be completed.
+    /// - [`Error::Batch`] if the number of `inputs`, `clients` and `messages`
+    ///   don't match or is longer than [`u16::MAX`].
+    /// - [`Error::ProofVerification`] if the `proof` failed to verify.
+    pub fn batch_finalize_checked<
+        'a,
+        II: 'a + Iterator<Item = &'a [u8]> + ExactSizeIterator + Clone,
+        IC,
+        IM,
+    >(
+        inputs: II,
+        clients: &'a IC,
+        messages: &'a IM,
+        proof: &Proof<CS>,
+        pk: &PublicKey<CS>,
+        info: Option<&'a [u8]>,
+    ) -> Result<PoprfClientBatchFinalizeResult<'a, CS, II, IC, IM>, BatchError>
+    where
+        CS: 'a,
+        &'a IC: 'a + IntoIterator<Item = &'a PoprfClient<CS>>,
+        <&'a IC as IntoIterator>::IntoIter: ExactSizeIterator,
+        &'a IM: 'a + IntoIterator<Item = &'a EvaluationElement<CS, PoprfMode>>,
         <&'a IM as IntoIterator>::IntoIter: ExactSizeIterator,
         <<CS as CipherSuite>::Hash as OutputSizeUser>::OutputSize: ArrayLength,
     {
-        let unblinded_elements = poprf_unblind(clients, messages, pk, proof, info)?;
+        check_inputs(inputs.clone())?;
+
+        Ok(Self::batch_finalize(
+            inputs, clients, messages, proof, pk, info,
+        )?)
+    }
+
+    /// Computes the tweaked key from the server public key and `info`. It can
+    /// be reused for every finalization under the same `info` with
+    /// [`finalize_with_tweaked_key`](Self::finalize_with_tweaked_key) and
+    /// [`batch_finalize_with_tweaked_key`](Self::batch_finalize_with_tweaked_key).
+    ///
+    /// # Errors
+    /// - [`Error::Info`] if the `info` is longer than `u16::MAX`.
+    /// - [`Error::Protocol`] if the protocol fails and can't be completed.
+    pub fn prepare_tweaked_key(
+        pk: &PublicKey<CS>,
+        info: Option<&[u8]>,
+    ) -> Result<PoprfPreparedTweakedKey<CS>> {
+        let tweaked_key = compute_tweaked_key::<CS>(pk.0, info)?;
+        let info_digest = info_digest::<CS>(info)?;
+
+        Ok(PoprfPreparedTweakedKey {
+            tweaked_key,
+            info_digest,
+        })
+    }
+
+    /// Same as [`finalize`](Self::finalize), but with a tweaked key prepared
+    /// by [`prepare_tweaked_key`](Self::prepare_tweaked_key).
+    ///
+    /// # Errors
+    /// - [`Error::Info`] if the `info` is longer than `u16::MAX`.
+    /// - [`Error::TweakMismatch`] if the `info` doesn't match the `info` the
+    ///   `tweaked_key` was prepared with.
+    /// - [`Error::Input`] if the `input` is empty or longer than [`u16::MAX`].
+    /// - [`Error::ProofVerification`] if the `proof` failed to verify.
+    pub fn finalize_with_tweaked_key(
+        &self,
+        input: &[u8],
+        evaluation_element: &EvaluationElement<CS, PoprfMode>,
+        proof: &Proof<CS>,
+        tweaked_key: &PoprfPreparedTweakedKey<CS>,
+        info: Option<&[u8]>,
+    ) -> Result<OprfOutput<CS>> {
+        let clients = core::array::from_ref(self);
+        let messages = core::array::from_ref(evaluation_element);
+
+        let mut batch_result = Self::batch_finalize_with_tweaked_key(
+            iter::once(input),
+            clients,
+            messages,
+            proof,
+            tweaked_key,
+            info,
+        )?;
+        batch_result.next().unwrap().map_err(|error| error.error)
+    }
+
+    /// Same as [`batch_finalize`](Self::batch_finalize), but with a tweaked
+    /// key prepared by [`prepare_tweaked_key`](Self::prepare_tweaked_key).
+    ///
+    /// # Errors
+    /// - [`Error::Info`] if the `info` is longer than `u16::MAX`.
+    /// - [`Error::TweakMismatch`] if the `info` doesn't match the `info` the
+    ///   `tweaked_key` was prepared with.
+    /// - [`Error::Batch`] if the number of `inputs`, `clients` and `messages`
+    ///   don't match or is longer than [`u16::MAX`].
+    /// - [`Error::ProofVerification`] if the `proof` failed to verify.
+    ///
+    /// The resulting messages can each fail individually with a [`BatchError`]
+    /// of [`Error::Input`] and its index if the `input` is empty or longer than
+    /// [`u16::MAX`].
+    pub fn batch_finalize_with_tweaked_key<
+        'a,
+        II: 'a + Iterator<Item = &'a [u8]> + ExactSizeIterator,
+        IC,
+        IM,
+    >(
+        inputs: II,
+        clients: &'a IC,
+        messages: &'a IM,
+        proof: &Proof<CS>,
+        tweaked_key: &PoprfPreparedTweakedKey<CS>,
+        info: Option<&'a [u8]>,
+    ) -> Result<PoprfClientBatchFinalizeResult<'a, CS, II, IC, IM>>
+    where
+        CS: 'a,
+        &'a IC: 'a + IntoIterator<Item = &'a PoprfClient<CS>>,
+        <&'a IC as IntoIterator>::IntoIter: ExactSizeIterator,
+        &'a IM: 'a + IntoIterator<Item = &'a EvaluationElement<CS, PoprfMode>>,
+        <&'a IM as IntoIterator>::IntoIter: ExactSizeIterator,
+    {
+        if info_digest::<CS>(info)? != tweaked_key.info_digest {
+            return Err(Error::TweakMismatch);
+        }
+
+        let unblinded_elements = poprf_unblind(clients, messages, tweaked_key.tweaked_key, proof)?;
 
         finalize_after_unblind::<'a, CS, _, _>(unblinded_elements, inputs, info)
     }
 
+    /// Allows for batching of the finalization of multiple [PoprfClient] and
+    /// [EvaluationElement] pairs, where each input was evaluated under its own
+    /// `info` by [`PoprfServer::batch_blind_evaluate_mixed_info`]. The
+    /// `proofs` are verified against the groups of equal `info`, in order of
+    /// their first appearance in `inputs`.
+    ///
+    /// The `inputs`, `clients` and `messages` have to be in the same order,
+    /// and with the same `info`s, as the `blinded_elements` passed to the
+    /// server, otherwise the groups and `proofs` don't line up and
+    /// verification fails. A [`None`] and an empty `info` belong to the same
+    /// group.
+    ///
+    /// Returns one output per input, in the same order.
+    ///
+    /// # Errors
+    /// - [`Error::Info`] if an `info` is longer than `u16::MAX`.
+    /// - [`Error::Protocol`] if the protocol fails and can't be completed.
+    /// - [`Error::Batch`] if the number of `inputs`, `clients` and `messages`
+    ///   don't match, the number of `proofs` doesn't match the number of
+    ///   distinct `info`s or a group is longer than [`u16::MAX`].
+    /// - [`Error::ProofVerification`] if any of the `proofs` failed to verify.
+    ///
+    /// The resulting outputs can each fail individually with a [`BatchError`]
+    /// of [`Error::Input`] and its index in `inputs` if the `input` is empty or
+    /// longer than [`u16::MAX`].
+    #[cfg(feature = "alloc")]
+    pub fn batch_finalize_mixed_info<'a, II, IC, IM>(
+        inputs: II,
+        clients: &'a IC,
+        messages: &'a IM,
+        proofs: &[Proof<CS>],
+        pk: &PublicKey<CS>,
+    ) -> Result<Vec<Result<OprfOutput<CS>, BatchError>>>
+    where
+        CS: 'a,
+        II: IntoIterator<Item = (&'a [u8], Option<&'a [u8]>)>,
+        &'a IC: 'a + IntoIterator<Item = &'a PoprfClient<CS>>,
+        &'a IM: 'a + IntoIterator<Item = &'a EvaluationElement<CS, PoprfMode>>,
+    {
+        let inputs: Vec<_> = inputs.into_iter().collect();
+        let clients: Vec<_> = clients.into_iter().collect();
+        let messages: Vec<_> = messages.into_iter().collect();
+
+        if inputs.len() != clients.len() || inputs.len() != messages.len() {
+            return Err(Error::Batch);
+        }
+
+        let groups = group_by_info(inputs.iter().map(|(_, info)| info.unwrap_or_default()));
+
+        if groups.len() != proofs.len() {
+            return Err(Error::Batch);
+        }
+
+        let g = CS::Group::base_elem();
+        let mut outputs: Vec<_> = iter::repeat_with(|| None).take(inputs.len()).collect();
+
+        for ((info, indices), proof) in groups.into_iter().zip(proofs) {
+            let tweaked_key = compute_tweaked_key::<CS>(pk.0, Some(info))?;
+
+            verify_proof(
+                g,
+                tweaked_key,
+                indices.iter().map(|&index| messages[index].0),
+                indices.iter().map(|&index| clients[index].blinded_element),
+                proof,
+                Mode::Poprf,
+            )?;
+
+            let unblinded_elements = indices
+                .iter()
+                .map(|&index| messages[index].0 * &CS::Group::invert_scalar(clients[index].blind));
+            let group_inputs = indices.iter().map(|&index| inputs[index].0);
+            let group_outputs =
+                finalize_after_unblind::<CS, _, _>(unblinded_elements, group_inputs, Some(info))?;
+
+            for (&index, output) in indices.iter().zip(group_outputs) {
+                outputs[index] = Some(output.map_err(|error| BatchError::new(index, error.error)));
+            }
+        }
+
+        // Every index belongs to exactly one group.
+        Ok(outputs.into_iter().flatten().collect())
+    }
+
+    /// Allows for finalization of a batch evaluated by
+    /// [`PoprfServer::batch_blind_evaluate_filtered`]. The `inputs` and
+    /// `clients` cover the whole batch, while the `messages` and the `proof`
+    /// only cover the elements accepted in `index_map`.
+    ///
+    /// Returns one result per input, in the same order, which is [`None`] if
+    /// the element was rejected by the server.
+    ///
+    /// # Errors
+    /// - [`Error::Info`] if the `info` is longer than `u16::MAX`.
+    /// - [`Error::Protocol`] if the protocol fails and can't be completed.
+    /// - [`Error::Batch`] if the number of `inputs` and `clients` doesn't match
+    ///   the `index_map` or the number of `messages` doesn't match the accepted
+    ///   elements.
+    /// - [`Error::ProofVerification`] if the `proof` failed to verify.
+    ///
+    /// The resulting outputs can each fail individually with a [`BatchError`]
+    /// of [`Error::Input`] and its index in `inputs` if the `input` is empty or
+    /// longer than [`u16::MAX`].
+    #[cfg(feature = "alloc")]
+    pub fn batch_finalize_filtered<'a, II, IC, IM>(
+        inputs: II,
+        clients: &'a IC,
+        messages: &'a IM,
+        proof: &Proof<CS>,
+        pk: &PublicKey<CS>,
+        info: Option<&[u8]>,
+        index_map: &BatchIndexMap,
+    ) -> Result<PoprfClientBatchFinalizeFilteredResult<CS>>
+    where
+        CS: 'a,
+        II: IntoIterator<Item = &'a [u8]>,
+        &'a IC: 'a + IntoIterator<Item = &'a PoprfClient<CS>>,
+        &'a IM: 'a + IntoIterator<Item = &'a EvaluationElement<CS, PoprfMode>>,
+    {
+        let inputs: Vec<_> = inputs.into_iter().collect();
+        let clients: Vec<_> = clients.into_iter().collect();
+        let messages: Vec<_> = messages.into_iter().cloned().collect();
+
+        if inputs.len() != index_map.len() || clients.len() != index_map.len() {
+            return Err(Error::Batch);
+        }
+
+        let accepted_inputs: Vec<_> = index_map.accepted().map(|index| inputs[index]).collect();
+        let accepted_clients: Vec<_> = index_map
+            .accepted()
+            .map(|index| clients[index].clone())
+            .collect();
+        let mut outputs = Self::batch_finalize(
+            accepted_inputs.into_iter(),
+            &accepted_clients,
+            &messages,
+            proof,
+            pk,
+            info,
+        )?;
+
+        Ok((0..index_map.len())
+            .map(|index| {
+                index_map.is_accepted(index).then(|| {
+                    // The number of outputs matches the accepted elements.
+                    outputs
+                        .next()
+                        .unwrap()
+                        .map_err(|error| BatchError::new(index, error.error))
+                })
+            })
+            .collect())
+    }
+
+    /// Allows for finalization of batches of any size, evaluated in chunks by
+    /// [`PoprfServer::batch_blind_evaluate_chunked`]. Each of the `responses`
+    /// is matched in order against the next `inputs` and `clients`, and the
+    /// outputs of a chunk are only returned after its proof was verified.
+    ///
+    /// Only the current chunk is kept in memory.
+    ///
+    /// Each chunk can fail individually with a [`BatchError`] of:
+    /// - [`Error::Info`] if the `info` is longer than `u16::MAX`.
+    /// - [`Error::Protocol`] if the protocol fails and can't be completed.
+    /// - [`Error::Batch`] if `inputs` or `clients` are exhausted before the
+    ///   chunk is complete.
+    /// - [`Error::ProofVerification`] if the `proof` of the chunk failed to
+    ///   verify.
+    /// - [`Error::Input`] and its index, counted over all chunks, if an
+    ///   `input` is empty or longer than [`u16::MAX`].
+    #[cfg(feature = "alloc")]
+    pub fn batch_finalize_chunked<'a, I, II, IC, IR>(
+        inputs: II,
+        clients: IC,
+        responses: IR,
+        pk: &PublicKey<CS>,
+        info: Option<&'a [u8]>,
+    ) -> PoprfClientBatchFinalizeChunks<'a, CS, II::IntoIter, IC::IntoIter, IR::IntoIter>
+    where
+        I: AsRef<[u8]>,
+        II: IntoIterator<Item = I>,
+        IC: IntoIterator<Item = PoprfClient<CS>>,
+        IR: IntoIterator<Item = PoprfServerBatchEvaluateResult<CS>>,
+    {
+        PoprfClientBatchFinalizeChunks {
+            inputs: inputs.into_iter(),
+            clients: clients.into_iter(),
+            responses: responses.into_iter(),
+            pk: pk.0,
+            info,
+            position: 0,
+        }
+    }
+
     /// Only used for test functions
     #[cfg(test)]
     pub fn get_blind(&self) -> <CS::Group as Group>::Scalar {
@@ -188,10 +536,11 @@ impl<CS: CipherSuite> PoprfServer<CS> {
     /// Produces a new instance of a [PoprfServer] using a supplied RNG
     ///
     /// # Errors
-    /// [`Error::Protocol`] if the protocol fails and can't be completed.
+    /// - [`Error::Rng`] if the random number generator fails.
+    /// - [`Error::Protocol`] if the protocol fails and can't be completed.
     pub fn new<R: TryRngCore + TryCryptoRng>(rng: &mut R) -> Result<Self> {
         let mut seed = GenericArray::<_, <CS::Group as Group>::ScalarLen>::default();
-        rng.try_fill_bytes(&mut seed).map_err(|_| Error::Protocol)?;
+        rng.try_fill_bytes(&mut seed).map_err(|_| Error::Rng)?;
 
         Self::new_from_seed(&seed, &[])
     }
@@ -222,48 +571,116 @@ impl<CS: CipherSuite> PoprfServer<CS> {
         Ok(Self { sk, pk })
     }
 
-    /// Only used for tests
-    #[cfg(test)]
-    pub fn get_private_key(&self) -> <CS::Group as Group>::Scalar {
-        self.sk
+    /// Produces a new instance of a [PoprfServer] from the server's
+    /// [`PrivateKey`].
+    pub fn new_with_private_key(private_key: &PrivateKey<CS>) -> Self {
+        Self {
+            sk: private_key.0,
+            pk: CS::Group::base_elem() * &private_key.0,
+        }
+    }
+
+    /// Retrieves the server's private key
+    #[cfg(any(feature = "danger", test))]
+    pub fn get_private_key(&self) -> PrivateKey<CS> {
+        PrivateKey(self.sk)
     }
 
     /// Computes the second step for the multiplicative blinding version of
     /// DH-OPRF. This message is sent from the server (who holds the OPRF key)
     /// to the client.
     ///
+    /// The nonce of the proof is hedged: it is derived from the tweaked private
+    /// key, the proof transcript and fresh randomness from `rng`. If `rng`
+    /// fails, the nonce falls back to the one of
+    /// [`blind_evaluate_deterministic`](Self::blind_evaluate_deterministic).
+    ///
     /// # Errors
     /// - [`Error::Info`] if the `info` is longer than `u16::MAX`.
     /// - [`Error::Protocol`] if the protocol fails and can't be completed.
     pub fn blind_evaluate<R: TryRngCore + TryCryptoRng>(
         &self,
         rng: &mut R,
-        blinded_element: &BlindedElement<CS>,
+        blinded_element: &BlindedElement<CS, PoprfMode>,
         info: Option<&[u8]>,
     ) -> Result<PoprfServerEvaluateResult<CS>> {
-        let PoprfServerBatchEvaluatePrepareResult {
-            mut prepared_evaluation_elements,
+        let prepared_tweak = self.prepare_tweak(info)?;
+
+        Ok(Self::blind_evaluate_with_tweak(
+            rng,
+            blinded_element,
+            &prepared_tweak,
+        ))
+    }
+
+    /// Same as [`blind_evaluate`](Self::blind_evaluate), but fully
+    /// deterministic: the nonce of the proof is derived from the tweaked
+    /// private key and the proof transcript only. Useful for reproducible
+    /// testing.
+    ///
+    /// # Errors
+    /// - [`Error::Info`] if the `info` is longer than `u16::MAX`.
+    /// - [`Error::Protocol`] if the protocol fails and can't be completed.
+    pub fn blind_evaluate_deterministic(
+        &self,
+        blinded_element: &BlindedElement<CS, PoprfMode>,
+        info: Option<&[u8]>,
+    ) -> Result<PoprfServerEvaluateResult<CS>> {
+        let prepared_tweak = self.prepare_tweak(info)?;
+
+        Ok(Self::blind_evaluate_with_tweak_inner(
+            &[],
+            blinded_element,
+            &prepared_tweak,
+        ))
+    }
+
+    /// Same as [`blind_evaluate`](Self::blind_evaluate), but with a tweak
+    /// prepared by [`prepare_tweak`](Self::prepare_tweak).
+    pub fn blind_evaluate_with_tweak<R: TryRngCore + TryCryptoRng>(
+        rng: &mut R,
+        blinded_element: &BlindedElement<CS, PoprfMode>,
+        prepared_tweak: &PoprfPreparedTweak<CS>,
+    ) -> PoprfServerEvaluateResult<CS> {
+        let randomness = proof_randomness::<CS, _>(rng);
+
+        Self::blind_evaluate_with_tweak_inner(
+            randomness.as_deref().unwrap_or_default(),
+            blinded_element,
+            prepared_tweak,
+        )
+    }
+
+    fn blind_evaluate_with_tweak_inner(
+        randomness: &[u8],
+        blinded_element: &BlindedElement<CS, PoprfMode>,
+        prepared_tweak: &PoprfPreparedTweak<CS>,
+    ) -> PoprfServerEvaluateResult<CS> {
+        let mut prepared_evaluation_elements = Self::batch_blind_evaluate_prepare_with_tweak(
+            iter::once(blinded_element),
             prepared_tweak,
-        } = self.batch_blind_evaluate_prepare(iter::once(blinded_element), info)?;
+        );
 
         let prepared_evaluation_element = prepared_evaluation_elements.next().unwrap();
         let prepared_evaluation_elements = core::array::from_ref(&prepared_evaluation_element);
 
+        // This can't fail because we know the size of the inputs.
         let PoprfServerBatchEvaluateFinishResult {
             mut messages,
             proof,
-        } = Self::batch_blind_evaluate_finish(
-            rng,
+        } = Self::batch_blind_evaluate_finish_inner(
+            randomness,
+            None,
             iter::once(blinded_element),
             prepared_evaluation_elements,
-            &prepared_tweak,
+            prepared_tweak,
         )
         .unwrap();
 
-        Ok(PoprfServerEvaluateResult {
+        PoprfServerEvaluateResult {
             message: messages.next().unwrap(),
             proof,
-        })
+        }
     }
 
     /// Allows for batching of the evaluation of multiple [BlindedElement]
@@ -281,63 +698,274 @@ impl<CS: CipherSuite> PoprfServer<CS> {
     ) -> Result<PoprfServerBatchEvaluateResult<CS>>
     where
         CS: 'a,
-        &'a IE: 'a + IntoIterator<Item = &'a BlindedElement<CS>>,
+        &'a IE: 'a + IntoIterator<Item = &'a BlindedElement<CS, PoprfMode>>,
         <&'a IE as IntoIterator>::IntoIter: ExactSizeIterator,
     {
-        let PoprfServerBatchEvaluatePrepareResult {
-            prepared_evaluation_elements,
-            prepared_tweak,
-        } = self.batch_blind_evaluate_prepare(blinded_elements.into_iter(), info)?;
+        let prepared_tweak = self.prepare_tweak(info)?;
 
-        let prepared_evaluation_elements: Vec<_> = prepared_evaluation_elements.collect();
+        Self::batch_blind_evaluate_with_tweak(rng, blinded_elements, &prepared_tweak)
+    }
+
+    /// Same as [`batch_blind_evaluate`](Self::batch_blind_evaluate), but with a
+    /// tweak prepared by [`prepare_tweak`](Self::prepare_tweak).
+    ///
+    /// # Errors
+    /// [`Error::Batch`] if the number of `blinded_elements` is longer than
+    /// [`u16::MAX`].
+    #[cfg(feature = "alloc")]
+    pub fn batch_blind_evaluate_with_tweak<'a, R: TryRngCore + TryCryptoRng, IE>(
+        rng: &mut R,
+        blinded_elements: &'a IE,
+        prepared_tweak: &PoprfPreparedTweak<CS>,
+    ) -> Result<PoprfServerBatchEvaluateResult<CS>>
+    where
+        CS: 'a,
+        &'a IE: 'a + IntoIterator<Item = &'a BlindedElement<CS, PoprfMode>>,
+        <&'a IE as IntoIterator>::IntoIter: ExactSizeIterator,
+    {
+        let prepared_evaluation_elements: Vec<_> = Self::batch_blind_evaluate_prepare_with_tweak(
+            blinded_elements.into_iter(),
+            prepared_tweak,
+        )
+        .collect();
 
-        // This can't fail because we know the size of the inputs.
         let PoprfServerBatchEvaluateFinishResult { messages, proof } =
             Self::batch_blind_evaluate_finish::<_, _, Vec<_>>(
                 rng,
                 blinded_elements.into_iter(),
                 &prepared_evaluation_elements,
-                &prepared_tweak,
-            )
-            .unwrap();
+                prepared_tweak,
+            )?;
 
         let messages: Vec<_> = messages.collect();
 
         Ok(PoprfServerBatchEvaluateResult { messages, proof })
     }
 
-    /// Alternative version of `batch_blind_evaluate` without
-    /// memory allocation. Returned [`PreparedEvaluationElement`] have to
-    /// be [`collect`](Iterator::collect)ed and passed into
-    /// [`batch_blind_evaluate_finish`](Self::batch_blind_evaluate_finish).
+    /// Allows for batching of the evaluation of multiple [BlindedElement]
+    /// messages from a [PoprfClient], each with its own `info`. The
+    /// `blinded_elements` are grouped by `info` and every group is evaluated
+    /// with its own [`Proof`], in order of the first appearance of its `info`.
+    /// A [`None`] and an empty `info` belong to the same group.
+    ///
+    /// The grouping isn't part of the result, the client reconstructs it from
+    /// its own inputs and therefore has to keep them in the same order as the
+    /// `blinded_elements`.
+    ///
+    /// The returned messages are in the same order as the `blinded_elements`
+    /// and can be finalized with [`PoprfClient::batch_finalize_mixed_info`].
     ///
     /// # Errors
-    /// - [`Error::Info`] if the `info` is longer than `u16::MAX`.
+    /// - [`Error::Info`] if an `info` is longer than `u16::MAX`.
     /// - [`Error::Protocol`] if the protocol fails and can't be completed.
-    pub fn batch_blind_evaluate_prepare<'a, I: Iterator<Item = &'a BlindedElement<CS>>>(
+    /// - [`Error::Batch`] if a group is longer than [`u16::MAX`].
+    #[cfg(feature = "alloc")]
+    pub fn batch_blind_evaluate_mixed_info<'a, R: TryRngCore + TryCryptoRng, I>(
         &self,
+        rng: &mut R,
         blinded_elements: I,
-        info: Option<&[u8]>,
-    ) -> Result<PoprfServerBatchEvaluatePrepareResult<CS, I>>
+    ) -> Result<PoprfServerBatchEvaluateMixedInfoResult<CS>>
     where
         CS: 'a,
+        I: IntoIterator<Item = (&'a BlindedElement<CS, PoprfMode>, Option<&'a [u8]>)>,
     {
-        let tweak = compute_tweak::<CS>(self.sk, info)?;
+        let blinded_elements: Vec<_> = blinded_elements.into_iter().collect();
+        let groups = group_by_info(
+            blinded_elements
+                .iter()
+                .map(|(_, info)| info.unwrap_or_default()),
+        );
 
-        Ok(PoprfServerBatchEvaluatePrepareResult {
-            prepared_evaluation_elements: blinded_elements.zip(iter::repeat(tweak)).map(
-                |(blinded_element, tweak)| {
-                    PreparedEvaluationElement(EvaluationElement(
-                        blinded_element.0 * &CS::Group::invert_scalar(tweak),
-                    ))
-                },
-            ),
-            prepared_tweak: PoprfPreparedTweak(tweak),
+        let mut messages: Vec<_> = iter::repeat_with(|| None)
+            .take(blinded_elements.len())
+            .collect();
+        let mut proofs = Vec::with_capacity(groups.len());
+
+        for (info, indices) in groups {
+            let group = indices.iter().map(|&index| blinded_elements[index].0);
+
+            let PoprfServerBatchEvaluatePrepareResult {
+                prepared_evaluation_elements,
+                prepared_tweak,
+            } = self.batch_blind_evaluate_prepare(group.clone(), Some(info))?;
+            let prepared_evaluation_elements: Vec<_> = prepared_evaluation_elements.collect();
+
+            let PoprfServerBatchEvaluateFinishResult {
+                messages: group_messages,
+                proof,
+            } = Self::batch_blind_evaluate_finish(
+                rng,
+                group,
+                &prepared_evaluation_elements,
+                &prepared_tweak,
+            )?;
+
+            for (&index, message) in indices.iter().zip(group_messages) {
+                messages[index] = Some(message);
+            }
+            proofs.push(proof);
+        }
+
+        Ok(PoprfServerBatchEvaluateMixedInfoResult {
+            // Every index belongs to exactly one group.
+            messages: messages.into_iter().flatten().collect(),
+            proofs,
         })
     }
 
-    /// See [`batch_blind_evaluate_prepare`](Self::batch_blind_evaluate_prepare)
-    /// for more details.
+    /// Allows for batching of the evaluation of any number of
+    /// [BlindedElement] messages from a [PoprfClient]. The `blinded_elements`
+    /// are split into chunks of at most [`MAX_BATCH_SIZE`] elements, each
+    /// evaluated with its own [`Proof`], so only the current chunk is kept in
+    /// memory.
+    ///
+    /// The returned chunks can be finalized with
+    /// [`PoprfClient::batch_finalize_chunked`].
+    ///
+    /// Each chunk can fail individually with:
+    /// - [`Error::Info`] if the `info` is longer than `u16::MAX`.
+    /// - [`Error::Protocol`] if the protocol fails and can't be completed.
+    #[cfg(feature = "alloc")]
+    pub fn batch_blind_evaluate_chunked<'a, R: TryRngCore + TryCryptoRng, I>(
+        &'a self,
+        rng: &'a mut R,
+        blinded_elements: I,
+        info: Option<&'a [u8]>,
+    ) -> PoprfServerBatchEvaluateChunks<'a, CS, R, I::IntoIter>
+    where
+        I: IntoIterator<Item = BlindedElement<CS, PoprfMode>>,
+    {
+        self.batch_blind_evaluate_chunks_of(rng, blinded_elements, info, MAX_BATCH_SIZE)
+    }
+
+    /// Same as [`batch_blind_evaluate_chunked`](Self::batch_blind_evaluate_chunked),
+    /// but splits the `blinded_elements` into chunks of `chunk_size` elements.
+    #[cfg(feature = "alloc")]
+    pub(crate) fn batch_blind_evaluate_chunks_of<'a, R: TryRngCore + TryCryptoRng, I>(
+        &'a self,
+        rng: &'a mut R,
+        blinded_elements: I,
+        info: Option<&'a [u8]>,
+        chunk_size: usize,
+    ) -> PoprfServerBatchEvaluateChunks<'a, CS, R, I::IntoIter>
+    where
+        I: IntoIterator<Item = BlindedElement<CS, PoprfMode>>,
+    {
+        PoprfServerBatchEvaluateChunks {
+            server: self,
+            rng,
+            blinded_elements: blinded_elements.into_iter(),
+            info,
+            chunk_size,
+        }
+    }
+
+    /// Same as [`batch_blind_evaluate`](Self::batch_blind_evaluate), but only
+    /// evaluates the `blinded_elements` accepted by `filter`, which receives
+    /// each element with its index. The proof only covers the accepted
+    /// elements and the returned [`BatchIndexMap`] records which elements were
+    /// accepted, so the client can match the messages with
+    /// [`PoprfClient::batch_finalize_filtered`].
+    ///
+    /// # Errors
+    /// - [`Error::Info`] if the `info` is longer than `u16::MAX`.
+    /// - [`Error::Protocol`] if the protocol fails and can't be completed.
+    /// - [`Error::Batch`] if no element was accepted or the number of accepted
+    ///   elements is longer than [`u16::MAX`].
+    #[cfg(feature = "alloc")]
+    pub fn batch_blind_evaluate_filtered<'a, R: TryRngCore + TryCryptoRng, IE, F>(
+        &self,
+        rng: &mut R,
+        blinded_elements: &'a IE,
+        info: Option<&[u8]>,
+        mut filter: F,
+    ) -> Result<PoprfServerBatchEvaluateFilteredResult<CS>>
+    where
+        CS: 'a,
+        &'a IE: IntoIterator<Item = &'a BlindedElement<CS, PoprfMode>>,
+        F: FnMut(usize, &BlindedElement<CS, PoprfMode>) -> bool,
+    {
+        let blinded_elements: Vec<_> = blinded_elements.into_iter().collect();
+        let index_map: BatchIndexMap = blinded_elements
+            .iter()
+            .enumerate()
+            .map(|(index, blinded_element)| filter(index, blinded_element))
+            .collect();
+        let accepted_elements: Vec<_> = index_map
+            .accepted()
+            .map(|index| blinded_elements[index].clone())
+            .collect();
+
+        if accepted_elements.is_empty() {
+            return Err(Error::Batch);
+        }
+
+        let PoprfServerBatchEvaluateResult { messages, proof } =
+            self.batch_blind_evaluate::<_, Vec<_>>(rng, &accepted_elements, info)?;
+
+        Ok(PoprfServerBatchEvaluateFilteredResult {
+            messages,
+            proof,
+            index_map,
+        })
+    }
+
+    /// Alternative version of `batch_blind_evaluate` without
+    /// memory allocation. Returned [`PreparedEvaluationElement`] have to
+    /// be [`collect`](Iterator::collect)ed and passed into
+    /// [`batch_blind_evaluate_finish`](Self::batch_blind_evaluate_finish).
+    ///
+    /// # Errors
+    /// - [`Error::Info`] if the `info` is longer than `u16::MAX`.
+    /// - [`Error::Protocol`] if the protocol fails and can't be completed.
+    pub fn batch_blind_evaluate_prepare<'a, I: Iterator<Item = &'a BlindedElement<CS, PoprfMode>>>(
+        &self,
+        blinded_elements: I,
+        info: Option<&[u8]>,
+    ) -> Result<PoprfServerBatchEvaluatePrepareResult<CS, I>>
+    where
+        CS: 'a,
+    {
+        let prepared_tweak = self.prepare_tweak(info)?;
+
+        Ok(PoprfServerBatchEvaluatePrepareResult {
+            prepared_evaluation_elements: Self::batch_blind_evaluate_prepare_with_tweak(
+                blinded_elements,
+                &prepared_tweak,
+            ),
+            prepared_tweak,
+        })
+    }
+
+    /// Same as
+    /// [`batch_blind_evaluate_prepare`](Self::batch_blind_evaluate_prepare),
+    /// but with a tweak prepared by [`prepare_tweak`](Self::prepare_tweak).
+    pub fn batch_blind_evaluate_prepare_with_tweak<
+        'a,
+        I: Iterator<Item = &'a BlindedElement<CS, PoprfMode>>,
+    >(
+        blinded_elements: I,
+        prepared_tweak: &PoprfPreparedTweak<CS>,
+    ) -> PoprfServerBatchEvaluatePreparedEvaluationElements<CS, I>
+    where
+        CS: 'a,
+    {
+        blinded_elements
+            .zip(iter::repeat(prepared_tweak.0))
+            .map(|(blinded_element, tweak)| {
+                PreparedEvaluationElement(EvaluationElement(
+                    blinded_element.0 * &CS::Group::invert_scalar(tweak),
+                    PhantomData,
+                ))
+            })
+    }
+
+    /// See [`batch_blind_evaluate_prepare`](Self::batch_blind_evaluate_prepare)
+    /// for more details.
+    ///
+    /// The nonce of the proof is hedged: it is derived from the tweaked private
+    /// key, the proof transcript and fresh randomness from `rng`. If `rng`
+    /// fails, the nonce is derived without fresh randomness instead.
     ///
     /// # Errors
     /// [`Error::Batch`] if the number of `blinded_elements` and
@@ -347,7 +975,7 @@ impl<CS: CipherSuite> PoprfServer<CS> {
         'a,
         'b,
         R: TryRngCore + TryCryptoRng,
-        IB: Iterator<Item = &'a BlindedElement<CS>> + ExactSizeIterator,
+        IB: Iterator<Item = &'a BlindedElement<CS, PoprfMode>> + ExactSizeIterator,
         IE,
     >(
         rng: &mut R,
@@ -357,7 +985,109 @@ impl<CS: CipherSuite> PoprfServer<CS> {
     ) -> Result<PoprfServerBatchEvaluateFinishResult<'b, CS, IE>>
     where
         CS: 'a,
-        &'b IE: IntoIterator<Item = &'b PreparedEvaluationElement<CS>>,
+        &'b IE: IntoIterator<Item = &'b PreparedEvaluationElement<CS, PoprfMode>>,
+        <&'b IE as IntoIterator>::IntoIter: ExactSizeIterator,
+    {
+        let randomness = proof_randomness::<CS, _>(rng);
+
+        Self::batch_blind_evaluate_finish_inner(
+            randomness.as_deref().unwrap_or_default(),
+            None,
+            blinded_elements,
+            prepared_evaluation_elements,
+            prepared_tweak,
+        )
+    }
+
+    /// Same as
+    /// [`batch_blind_evaluate_finish`](Self::batch_blind_evaluate_finish), but
+    /// fully deterministic: the nonce of the proof is derived from the tweaked
+    /// private key and the proof transcript only. Useful for reproducible
+    /// testing.
+    ///
+    /// # Errors
+    /// [`Error::Batch`] if the number of `blinded_elements` and
+    /// `prepared_evaluation_elements` don't match or is longer then
+    /// [`u16::MAX`]
+    pub fn batch_blind_evaluate_finish_deterministic<
+        'a,
+        'b,
+        IB: Iterator<Item = &'a BlindedElement<CS, PoprfMode>> + ExactSizeIterator,
+        IE,
+    >(
+        blinded_elements: IB,
+        prepared_evaluation_elements: &'b IE,
+        prepared_tweak: &PoprfPreparedTweak<CS>,
+    ) -> Result<PoprfServerBatchEvaluateFinishResult<'b, CS, IE>>
+    where
+        CS: 'a,
+        &'b IE: IntoIterator<Item = &'b PreparedEvaluationElement<CS, PoprfMode>>,
+        <&'b IE as IntoIterator>::IntoIter: ExactSizeIterator,
+    {
+        Self::batch_blind_evaluate_finish_inner(
+            &[],
+            None,
+            blinded_elements,
+            prepared_evaluation_elements,
+            prepared_tweak,
+        )
+    }
+
+    /// Same as
+    /// [`batch_blind_evaluate_finish`](Self::batch_blind_evaluate_finish), but
+    /// taking the nonce of the proof as input instead of deriving it.
+    ///
+    /// # Caution
+    ///
+    /// This should be used with caution, since reusing a nonce for different
+    /// inputs reveals the tweaked private key!
+    ///
+    /// # Errors
+    /// [`Error::Batch`] if the number of `blinded_elements` and
+    /// `prepared_evaluation_elements` don't match or is longer then
+    /// [`u16::MAX`]
+    #[cfg(any(feature = "danger", test))]
+    pub fn batch_blind_evaluate_finish_unchecked<
+        'a,
+        'b,
+        IB: Iterator<Item = &'a BlindedElement<CS, PoprfMode>> + ExactSizeIterator,
+        IE,
+    >(
+        proof_random_scalar: <CS::Group as Group>::Scalar,
+        blinded_elements: IB,
+        prepared_evaluation_elements: &'b IE,
+        prepared_tweak: &PoprfPreparedTweak<CS>,
+    ) -> Result<PoprfServerBatchEvaluateFinishResult<'b, CS, IE>>
+    where
+        CS: 'a,
+        &'b IE: IntoIterator<Item = &'b PreparedEvaluationElement<CS, PoprfMode>>,
+        <&'b IE as IntoIterator>::IntoIter: ExactSizeIterator,
+    {
+        Self::batch_blind_evaluate_finish_inner(
+            &[],
+            Some(proof_random_scalar),
+            blinded_elements,
+            prepared_evaluation_elements,
+            prepared_tweak,
+        )
+    }
+
+    /// Can only fail with [`Error::Batch`].
+    fn batch_blind_evaluate_finish_inner<
+        'a,
+        'b,
+        IB: Iterator<Item = &'a BlindedElement<CS, PoprfMode>> + ExactSizeIterator,
+        IE,
+    >(
+        randomness: &[u8],
+        proof_random_scalar: Option<<CS::Group as Group>::Scalar>,
+        blinded_elements: IB,
+        prepared_evaluation_elements: &'b IE,
+        prepared_tweak: &PoprfPreparedTweak<CS>,
+    ) -> Result<PoprfServerBatchEvaluateFinishResult<'b, CS, IE>>
+    where
+        CS: 'a,
+        &'b IE: IntoIterator<Item = &'b PreparedEvaluationElement<CS, PoprfMode>>,
         <&'b IE as IntoIterator>::IntoIter: ExactSizeIterator,
     {
         let g = CS::Group::base_elem();
@@ -365,7 +1095,8 @@ impl<CS: CipherSuite> PoprfServer<CS> {
         let tweaked_key = g * &tweak;
 
         let proof = generate_proof(
-            rng,
+            randomness,
+            proof_random_scalar,
             tweak,
             g,
             tweaked_key,
@@ -377,44 +1108,169 @@ impl<CS: CipherSuite> PoprfServer<CS> {
         )?;
 
         let messages = prepared_evaluation_elements.into_iter().map(<fn(
-            &PreparedEvaluationElement<CS>,
+            &PreparedEvaluationElement<CS, PoprfMode>,
         ) -> _>::from(
-            |element| EvaluationElement(element.0 .0),
+            |element| EvaluationElement(element.0 .0, PhantomData),
         ));
 
         Ok(PoprfServerBatchEvaluateFinishResult { messages, proof })
     }
 
+    /// Computes the tweak of the server's private key for `info`. It can be
+    /// reused for every evaluation under the same `info`, see
+    /// [`blind_evaluate_with_tweak`](Self::blind_evaluate_with_tweak) and
+    /// `PoprfTweakCache`.
+    ///
+    /// # Errors
+    /// - [`Error::Info`] if the `info` is longer than `u16::MAX`.
+    /// - [`Error::Protocol`] if the protocol fails and can't be completed.
+    pub fn prepare_tweak(&self, info: Option<&[u8]>) -> Result<PoprfPreparedTweak<CS>> {
+        compute_tweak::<CS>(self.sk, info).map(PoprfPreparedTweak)
+    }
+
     /// Computes the output of the VOPRF on the server side
     ///
     /// # Errors
     /// [`Error::Input`]  if the `input` is longer then [`u16::MAX`].
-    pub fn evaluate(
+    pub fn evaluate(&self, input: &[u8], info: Option<&[u8]>) -> Result<OprfOutput<CS>> {
+        let tweak = compute_tweak::<CS>(self.sk, info)?;
+
+        evaluate_with_inverted_tweak::<CS>(input, info, CS::Group::invert_scalar(tweak))
+    }
+
+    /// Computes the output of the POPRF on the server side for each of the
+    /// `inputs`, in the same order.
+    ///
+    /// # Errors
+    /// - [`Error::Info`] if the `info` is longer than `u16::MAX`.
+    /// - [`Error::Protocol`] if the protocol fails and can't be completed.
+    ///
+    /// The resulting outputs can each fail individually with [`Error::Input`]
+    /// if the `input` is longer then [`u16::MAX`].
+    pub fn evaluate_many<'a, I>(
         &self,
-        input: &[u8],
+        inputs: I,
+        info: Option<&'a [u8]>,
+    ) -> Result<PoprfServerEvaluateManyResult<'a, CS, I::IntoIter>>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<[u8]>,
+    {
+        let inverted_tweak = CS::Group::invert_scalar(compute_tweak::<CS>(self.sk, info)?);
+
+        Ok(PoprfServerEvaluateManyResult {
+            inputs: inputs.into_iter(),
+            inverted_tweak,
+            info,
+        })
+    }
+
+    /// Parallel version of [`evaluate_many`](Self::evaluate_many). The outputs
+    /// are returned in the same order as the `inputs`.
+    ///
+    /// # Errors
+    /// - [`Error::Info`] if the `info` is longer than `u16::MAX`.
+    /// - [`Error::Protocol`] if the protocol fails and can't be completed.
+    ///
+    /// The resulting outputs can each fail individually with [`Error::Input`]
+    /// if the `input` is longer then [`u16::MAX`].
+    #[cfg(feature = "rayon")]
+    pub fn par_evaluate_many<I>(
+        &self,
+        inputs: I,
         info: Option<&[u8]>,
-    ) -> Result<Output<<CS as CipherSuite>::Hash>> {
-        let input_element = hash_to_group::<CS>(input, Mode::Poprf)?;
-        if CS::Group::is_identity_elem(input_element).into() {
-            return Err(Error::Input);
-        };
+    ) -> Result<Vec<Result<OprfOutput<CS>>>>
+    where
+        <CS::Group as Group>::Scalar: Send + Sync,
+        I: IntoParallelIterator,
+        I::Item: AsRef<[u8]>,
+    {
+        let inverted_tweak = CS::Group::invert_scalar(compute_tweak::<CS>(self.sk, info)?);
 
-        let tweak = compute_tweak::<CS>(self.sk, info)?;
+        Ok(inputs
+            .into_par_iter()
+            .map(|input| evaluate_with_inverted_tweak::<CS>(input.as_ref(), info, inverted_tweak))
+            .collect())
+    }
 
-        let evaluated_element = input_element * &CS::Group::invert_scalar(tweak);
+    /// Verifies a batch response of a [PoprfServer] for `info` against its
+    /// public key `pk`, without any client state. Allows a third party which
+    /// only sees the messages exchanged between client and server to audit the
+    /// server.
+    ///
+    /// # Errors
+    /// - [`Error::Info`] if the `info` is longer than `u16::MAX`.
+    /// - [`Error::Protocol`] if the protocol fails and can't be completed.
+    /// - [`Error::Batch`] if the number of `blinded_elements` and
+    ///   `evaluation_elements` don't match or is longer then [`u16::MAX`].
+    /// - [`Error::ProofVerification`] if the `proof` failed to verify.
+    pub fn verify_server_response<'a, IB, IE>(
+        pk: &PublicKey<CS>,
+        blinded_elements: &'a IB,
+        evaluation_elements: &'a IE,
+        proof: &Proof<CS>,
+        info: Option<&[u8]>,
+    ) -> Result<()>
+    where
+        CS: 'a,
+        IB: ?Sized,
+        IE: ?Sized,
+        &'a IB: IntoIterator<Item = &'a BlindedElement<CS, PoprfMode>>,
+        <&'a IB as IntoIterator>::IntoIter: ExactSizeIterator,
+        &'a IE: IntoIterator<Item = &'a EvaluationElement<CS, PoprfMode>>,
+        <&'a IE as IntoIterator>::IntoIter: ExactSizeIterator,
+    {
+        let tweaked_key = compute_tweaked_key::<CS>(pk.0, info)?;
 
-        let issued_element = CS::Group::serialize_elem(evaluated_element);
+        verify_proof(
+            CS::Group::base_elem(),
+            tweaked_key,
+            evaluation_elements.into_iter().map(|element| element.0),
+            blinded_elements.into_iter().map(|element| element.0),
+            proof,
+            Mode::Poprf,
+        )
+    }
 
-        server_evaluate_hash_input::<CS>(input, info, issued_element)
+    /// Verifies multiple batch responses of a [PoprfServer], e.g. the chunks of
+    /// `batch_blind_evaluate_chunked`, with
+    /// [`verify_server_response`](Self::verify_server_response).
+    ///
+    /// # Errors
+    /// A [`BatchError`] with the error returned by
+    /// [`verify_server_response`](Self::verify_server_response). Its
+    /// [`index`](BatchError::index) is the index of the first response in
+    /// `responses` which failed to verify, not of an element.
+    pub fn verify_server_responses<'a, I>(
+        pk: &PublicKey<CS>,
+        responses: I,
+    ) -> Result<(), BatchError>
+    where
+        CS: 'a,
+        I: IntoIterator<
+            Item = (
+                &'a [BlindedElement<CS, PoprfMode>],
+                &'a [EvaluationElement<CS, PoprfMode>],
+                &'a Proof<CS>,
+                Option<&'a [u8]>,
+            ),
+        >,
+    {
+        responses.into_iter().enumerate().try_for_each(
+            |(index, (blinded_elements, evaluation_elements, proof, info))| {
+                Self::verify_server_response(pk, blinded_elements, evaluation_elements, proof, info)
+                    .map_err(|error| BatchError::new(index, error))
+            },
+        )
     }
 
     /// Retrieves the server's public key
-    pub fn get_public_key(&self) -> <CS::Group as Group>::Elem {
-        self.pk
+    pub fn get_public_key(&self) -> PublicKey<CS> {
+        PublicKey(self.pk)
     }
 }
 
-impl<CS: CipherSuite> BlindedElement<CS> {
+impl<CS: CipherSuite, M: ProtocolMode> BlindedElement<CS, M> {
     /// Creates a [BlindedElement] from a raw group element.
     ///
     /// # Caution
@@ -423,7 +1279,7 @@ impl<CS: CipherSuite> BlindedElement<CS> {
     /// on the validity of the value itself!
     #[cfg(feature = "danger")]
     pub fn from_value_unchecked(value: <CS::Group as Group>::Elem) -> Self {
-        Self(value)
+        Self(value, PhantomData)
     }
 
     /// Exposes the internal value
@@ -431,9 +1287,20 @@ impl<CS: CipherSuite> BlindedElement<CS> {
     pub fn value(&self) -> <CS::Group as Group>::Elem {
         self.0
     }
+
+    /// Converts this message into a message of another [`ProtocolMode`].
+    ///
+    /// # Caution
+    ///
+    /// This should be used with caution, a server evaluating a message of a
+    /// different mode produces an output the client can't finalize correctly!
+    #[cfg(feature = "danger")]
+    pub fn into_mode<N: ProtocolMode>(self) -> BlindedElement<CS, N> {
+        BlindedElement(self.0, PhantomData)
+    }
 }
 
-impl<CS: CipherSuite> EvaluationElement<CS> {
+impl<CS: CipherSuite, M: ProtocolMode> EvaluationElement<CS, M> {
     /// Creates an [EvaluationElement] from a raw group element.
     ///
     /// # Caution
@@ -442,7 +1309,7 @@ impl<CS: CipherSuite> EvaluationElement<CS> {
     /// on the validity of the value itself!
     #[cfg(feature = "danger")]
     pub fn from_value_unchecked(value: <CS::Group as Group>::Elem) -> Self {
-        Self(value)
+        Self(value, PhantomData)
     }
 
     /// Exposes the internal value
@@ -450,6 +1317,98 @@ impl<CS: CipherSuite> EvaluationElement<CS> {
     pub fn value(&self) -> <CS::Group as Group>::Elem {
         self.0
     }
+
+    /// Converts this message into a message of another [`ProtocolMode`].
+    ///
+    /// # Caution
+    ///
+    /// This should be used with caution, a client finalizing a message of a
+    /// different mode produces an incorrect output!
+    #[cfg(feature = "danger")]
+    pub fn into_mode<N: ProtocolMode>(self) -> EvaluationElement<CS, N> {
+        EvaluationElement(self.0, PhantomData)
+    }
+}
+
+impl<CS: CipherSuite> Debug for PoprfClient<CS> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PoprfClient")
+            .field("suite", &CS::ID)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "danger")]
+impl<CS: CipherSuite> PoprfClient<CS> {
+    /// Includes the blind and the blinded element in the [`Debug`] output,
+    /// which together link the request seen by the server to this client's
+    /// input.
+    pub fn insecure_debug(&self) -> InsecureDebug<'_, Self> {
+        InsecureDebug(self)
+    }
+}
+
+#[cfg(feature = "danger")]
+impl<CS: CipherSuite> Debug for InsecureDebug<'_, PoprfClient<CS>>
+where
+    <CS::Group as Group>::Scalar: Debug,
+    <CS::Group as Group>::Elem: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PoprfClient")
+            .field("blind", &self.0.blind)
+            .field("blinded_element", &self.0.blinded_element)
+            .finish()
+    }
+}
+
+impl<CS: CipherSuite> Debug for PoprfServer<CS> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PoprfServer")
+            .field("suite", &CS::ID)
+            .field("fingerprint", &Hex(&PublicKey::<CS>(self.pk).fingerprint()))
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "danger")]
+impl<CS: CipherSuite> PoprfServer<CS> {
+    /// Includes the untweaked private key next to the public key in the
+    /// [`Debug`] output, anyone who sees it can evaluate the POPRF for any
+    /// `info`.
+    pub fn insecure_debug(&self) -> InsecureDebug<'_, Self> {
+        InsecureDebug(self)
+    }
+}
+
+#[cfg(feature = "danger")]
+impl<CS: CipherSuite> Debug for InsecureDebug<'_, PoprfServer<CS>>
+where
+    <CS::Group as Group>::Scalar: Debug,
+    <CS::Group as Group>::Elem: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PoprfServer")
+            .field("sk", &self.0.sk)
+            .field("pk", &self.0.pk)
+            .finish()
+    }
+}
+
+impl<CS: CipherSuite, I> Debug for PoprfServerEvaluateManyResult<'_, CS, I> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PoprfServerEvaluateManyResult")
+            .field("suite", &CS::ID)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<CS: CipherSuite> Debug for PoprfPreparedTweak<CS> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PoprfPreparedTweak")
+            .field("suite", &CS::ID)
+            .finish_non_exhaustive()
+    }
 }
 
 /////////////////////////
@@ -463,7 +1422,7 @@ pub struct PoprfClientBlindResult<CS: CipherSuite> {
     /// The state to be persisted on the client
     pub state: PoprfClient<CS>,
     /// The message to send to the server
-    pub message: BlindedElement<CS>,
+    pub message: BlindedElement<CS, PoprfMode>,
 }
 
 /// Concrete return type for [`PoprfClient::batch_finalize`].
@@ -474,45 +1433,198 @@ pub type PoprfClientBatchFinalizeResult<'a, CS, II, IC, IM> =
 #[derive_where(Debug; <CS::Group as Group>::Scalar, <CS::Group as Group>::Elem)]
 pub struct PoprfServerEvaluateResult<CS: CipherSuite> {
     /// The message to send to the client
-    pub message: EvaluationElement<CS>,
+    pub message: EvaluationElement<CS, PoprfMode>,
+    /// The proof for the client to verify
+    pub proof: Proof<CS>,
+}
+
+/// Contains the fields that are returned by a verifiable server batch evaluate
+#[derive_where(Debug; <CS::Group as Group>::Scalar, <CS::Group as Group>::Elem)]
+#[cfg(feature = "alloc")]
+pub struct PoprfServerBatchEvaluateResult<CS: CipherSuite> {
+    /// The messages to send to the client
+    pub messages: Vec<EvaluationElement<CS, PoprfMode>>,
     /// The proof for the client to verify
     pub proof: Proof<CS>,
 }
 
-/// Contains the fields that are returned by a verifiable server batch evaluate
-#[derive_where(Debug; <CS::Group as Group>::Scalar, <CS::Group as Group>::Elem)]
-#[cfg(feature = "alloc")]
-pub struct PoprfServerBatchEvaluateResult<CS: CipherSuite> {
-    /// The messages to send to the client
-    pub messages: Vec<EvaluationElement<CS>>,
-    /// The proof for the client to verify
-    pub proof: Proof<CS>,
-}
+/// Concrete return type for [`PoprfClient::batch_finalize_filtered`].
+#[cfg(feature = "alloc")]
+pub type PoprfClientBatchFinalizeFilteredResult<CS> =
+    Vec<Option<Result<OprfOutput<CS>, BatchError>>>;
+
+/// Contains the fields that are returned by
+/// [`PoprfServer::batch_blind_evaluate_filtered`]
+#[derive_where(Debug; <CS::Group as Group>::Scalar, <CS::Group as Group>::Elem)]
+#[cfg(feature = "alloc")]
+pub struct PoprfServerBatchEvaluateFilteredResult<CS: CipherSuite> {
+    /// The messages to send to the client, one per accepted element
+    pub messages: Vec<EvaluationElement<CS, PoprfMode>>,
+    /// The proof for the client to verify
+    pub proof: Proof<CS>,
+    /// The accepted and rejected elements, to send to the client
+    pub index_map: BatchIndexMap,
+}
+
+/// Contains the fields that are returned by a verifiable server batch evaluate
+/// with mixed `info`s
+#[derive_where(Debug; <CS::Group as Group>::Scalar, <CS::Group as Group>::Elem)]
+#[cfg(feature = "alloc")]
+pub struct PoprfServerBatchEvaluateMixedInfoResult<CS: CipherSuite> {
+    /// The messages to send to the client
+    pub messages: Vec<EvaluationElement<CS, PoprfMode>>,
+    /// The proofs for the client to verify, one per distinct `info` in order
+    /// of first appearance
+    pub proofs: Vec<Proof<CS>>,
+}
+
+/// Concrete type of [`EvaluationElement`]s in
+/// [`PoprfServerBatchEvaluatePrepareResult`].
+pub type PoprfServerBatchEvaluatePreparedEvaluationElements<CS, I> = Map<
+    Zip<I, Repeat<<<CS as CipherSuite>::Group as Group>::Scalar>>,
+    fn(
+        (
+            &BlindedElement<CS, PoprfMode>,
+            <<CS as CipherSuite>::Group as Group>::Scalar,
+        ),
+    ) -> PreparedEvaluationElement<CS, PoprfMode>,
+>;
+
+/// Prepared tweak by a partially verifiable server batch evaluate prepare.
+#[derive_where(Clone, ZeroizeOnDrop)]
+#[derive_where(Eq, Hash, Ord, PartialEq, PartialOrd; <CS::Group as Group>::Scalar)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize, serde::Serialize),
+    serde(bound = "")
+)]
+pub struct PoprfPreparedTweak<CS: CipherSuite>(
+    #[cfg_attr(feature = "serde", serde(with = "Scalar::<CS::Group>"))]
+    <CS::Group as Group>::Scalar,
+);
+
+/// Iterator returned by [`PoprfServer::evaluate_many`], yielding the output
+/// of each input. The inverted tweaked key it holds is zeroized on drop.
+#[derive_where(ZeroizeOnDrop)]
+pub struct PoprfServerEvaluateManyResult<'a, CS: CipherSuite, I> {
+    #[derive_where(skip(Zeroize))]
+    inputs: I,
+    inverted_tweak: <CS::Group as Group>::Scalar,
+    #[derive_where(skip(Zeroize))]
+    info: Option<&'a [u8]>,
+}
+
+impl<CS: CipherSuite, I: Iterator<Item: AsRef<[u8]>>> Iterator
+    for PoprfServerEvaluateManyResult<'_, CS, I>
+{
+    type Item = Result<OprfOutput<CS>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let input = self.inputs.next()?;
+
+        Some(evaluate_with_inverted_tweak::<CS>(
+            input.as_ref(),
+            self.info,
+            self.inverted_tweak,
+        ))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inputs.size_hint()
+    }
+}
+
+impl<CS: CipherSuite, I: ExactSizeIterator<Item: AsRef<[u8]>>> ExactSizeIterator
+    for PoprfServerEvaluateManyResult<'_, CS, I>
+{
+}
+
+/// Tweaked key prepared by [`PoprfClient::prepare_tweaked_key`].
+#[derive_where(Clone)]
+#[derive_where(Debug, Eq, Hash, Ord, PartialEq, PartialOrd; <CS::Group as Group>::Elem)]
+pub struct PoprfPreparedTweakedKey<CS: CipherSuite> {
+    tweaked_key: <CS::Group as Group>::Elem,
+    info_digest: Output<CS::Hash>,
+}
+
+/// A bounded cache of [`PoprfPreparedTweak`]s keyed by `info`, for servers
+/// evaluating under a small set of `info`s. Once full, the oldest entry is
+/// evicted.
+#[cfg(feature = "alloc")]
+#[derive_where(Clone)]
+#[derive_where(Debug; <CS::Group as Group>::Scalar, <CS::Group as Group>::Elem)]
+pub struct PoprfTweakCache<CS: CipherSuite> {
+    capacity: usize,
+    pk: Option<<CS::Group as Group>::Elem>,
+    entries: VecDeque<(Vec<u8>, PoprfPreparedTweak<CS>)>,
+}
+
+#[cfg(feature = "alloc")]
+impl<CS: CipherSuite> PoprfTweakCache<CS> {
+    /// Creates an empty cache holding at most `capacity` tweaks.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            pk: None,
+            entries: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    /// Returns the tweak of `server` for `info`, computing and caching it if
+    /// it isn't cached yet. The cache is cleared if it is used with a
+    /// different `server` than before.
+    ///
+    /// # Errors
+    /// - [`Error::Info`] if the `info` is longer than `u16::MAX`.
+    /// - [`Error::Protocol`] if the protocol fails and can't be completed.
+    pub fn get_or_prepare(
+        &mut self,
+        server: &PoprfServer<CS>,
+        info: Option<&[u8]>,
+    ) -> Result<PoprfPreparedTweak<CS>> {
+        let info = info.unwrap_or_default();
+
+        match self.pk {
+            Some(pk) if bool::from(pk.ct_eq(&server.pk)) => (),
+            _ => {
+                self.clear();
+                self.pk = Some(server.pk);
+            }
+        }
+
+        if let Some((_, prepared_tweak)) = self.entries.iter().find(|(key, _)| key == info) {
+            return Ok(prepared_tweak.clone());
+        }
+
+        let prepared_tweak = server.prepare_tweak(Some(info))?;
+
+        if self.capacity > 0 {
+            if self.entries.len() == self.capacity {
+                self.entries.pop_front();
+            }
 
-/// Concrete type of [`EvaluationElement`]s in
-/// [`PoprfServerBatchEvaluatePrepareResult`].
-pub type PoprfServerBatchEvaluatePreparedEvaluationElements<CS, I> = Map<
-    Zip<I, Repeat<<<CS as CipherSuite>::Group as Group>::Scalar>>,
-    fn(
-        (
-            &BlindedElement<CS>,
-            <<CS as CipherSuite>::Group as Group>::Scalar,
-        ),
-    ) -> PreparedEvaluationElement<CS>,
->;
+            self.entries
+                .push_back((info.to_vec(), prepared_tweak.clone()));
+        }
 
-/// Prepared tweak by a partially verifiable server batch evaluate prepare.
-#[derive_where(Clone, ZeroizeOnDrop)]
-#[derive_where(Debug, Eq, Hash, Ord, PartialEq, PartialOrd; <CS::Group as Group>::Scalar)]
-#[cfg_attr(
-    feature = "serde",
-    derive(serde::Deserialize, serde::Serialize),
-    serde(bound = "")
-)]
-pub struct PoprfPreparedTweak<CS: CipherSuite>(
-    #[cfg_attr(feature = "serde", serde(with = "Scalar::<CS::Group>"))]
-    <CS::Group as Group>::Scalar,
-);
+        Ok(prepared_tweak)
+    }
+
+    /// Returns the number of cached tweaks.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if no tweaks are cached.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Removes all cached tweaks.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
 
 /// Contains the fields that are returned by a partially verifiable server batch
 /// evaluate prepare
@@ -528,7 +1640,7 @@ pub struct PoprfServerBatchEvaluatePrepareResult<CS: CipherSuite, I> {
 /// [`PoprfServerBatchEvaluateFinishResult`].
 pub type PoprfServerBatchEvaluateFinishedMessages<'a, CS, I> = Map<
     <&'a I as IntoIterator>::IntoIter,
-    fn(&PreparedEvaluationElement<CS>) -> EvaluationElement<CS>,
+    fn(&PreparedEvaluationElement<CS, PoprfMode>) -> EvaluationElement<CS, PoprfMode>,
 >;
 
 /// Contains the fields that are returned by a verifiable server batch evaluate
@@ -536,7 +1648,7 @@ pub type PoprfServerBatchEvaluateFinishedMessages<'a, CS, I> = Map<
 #[derive_where(Debug; <&'a I as IntoIterator>::IntoIter, <CS::Group as Group>::Scalar)]
 pub struct PoprfServerBatchEvaluateFinishResult<'a, CS: 'a + CipherSuite, I>
 where
-    &'a I: IntoIterator<Item = &'a PreparedEvaluationElement<CS>>,
+    &'a I: IntoIterator<Item = &'a PreparedEvaluationElement<CS, PoprfMode>>,
 {
     /// The [`EvaluationElement`]s to send to the client
     pub messages: PoprfServerBatchEvaluateFinishedMessages<'a, CS, I>,
@@ -544,6 +1656,96 @@ where
     pub proof: Proof<CS>,
 }
 
+/// Iterator returned by [`PoprfServer::batch_blind_evaluate_chunked`],
+/// yielding one [`PoprfServerBatchEvaluateResult`] per chunk.
+#[cfg(feature = "alloc")]
+#[derive_where(Debug; R, I, <CS::Group as Group>::Scalar, <CS::Group as Group>::Elem)]
+pub struct PoprfServerBatchEvaluateChunks<'a, CS: CipherSuite, R, I> {
+    server: &'a PoprfServer<CS>,
+    rng: &'a mut R,
+    blinded_elements: I,
+    info: Option<&'a [u8]>,
+    chunk_size: usize,
+}
+
+#[cfg(feature = "alloc")]
+impl<
+        CS: CipherSuite,
+        R: TryRngCore + TryCryptoRng,
+        I: Iterator<Item = BlindedElement<CS, PoprfMode>>,
+    > Iterator for PoprfServerBatchEvaluateChunks<'_, CS, R, I>
+{
+    type Item = Result<PoprfServerBatchEvaluateResult<CS>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let blinded_elements = next_chunk(&mut self.blinded_elements, self.chunk_size)?;
+
+        Some(
+            self.server
+                .batch_blind_evaluate(self.rng, &blinded_elements, self.info),
+        )
+    }
+}
+
+/// Iterator returned by [`PoprfClient::batch_finalize_chunked`], yielding the
+/// outputs of each chunk.
+#[cfg(feature = "alloc")]
+#[derive_where(Debug; II, IC, IR, <CS::Group as Group>::Elem)]
+pub struct PoprfClientBatchFinalizeChunks<'a, CS: CipherSuite, II, IC, IR> {
+    inputs: II,
+    clients: IC,
+    responses: IR,
+    pk: <CS::Group as Group>::Elem,
+    info: Option<&'a [u8]>,
+    position: usize,
+}
+
+#[cfg(feature = "alloc")]
+impl<CS, I, II, IC, IR> Iterator for PoprfClientBatchFinalizeChunks<'_, CS, II, IC, IR>
+where
+    CS: CipherSuite,
+    I: AsRef<[u8]>,
+    II: Iterator<Item = I>,
+    IC: Iterator<Item = PoprfClient<CS>>,
+    IR: Iterator<Item = PoprfServerBatchEvaluateResult<CS>>,
+{
+    type Item = Result<Vec<OprfOutput<CS>>, BatchError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let PoprfServerBatchEvaluateResult { messages, proof } = self.responses.next()?;
+        let inputs: Vec<_> = self.inputs.by_ref().take(messages.len()).collect();
+        let clients: Vec<_> = self.clients.by_ref().take(messages.len()).collect();
+        let position = self.position;
+        self.position += messages.len();
+
+        if inputs.len() != messages.len() || clients.len() != messages.len() {
+            return Some(Err(Error::Batch.into()));
+        }
+
+        Some(
+            PoprfClient::batch_finalize(
+                inputs.iter().map(AsRef::as_ref),
+                &clients,
+                &messages,
+                &proof,
+                &PublicKey(self.pk),
+                self.info,
+            )
+            .map_err(BatchError::from)
+            .and_then(|outputs| {
+                outputs
+                    .map(|output| {
+                        output.map_err(|error| BatchError {
+                            index: error.index.map(|index| position + index),
+                            ..error
+                        })
+                    })
+                    .collect()
+            }),
+        )
+    }
+}
+
 /////////////////////
 // Inner functions //
 // =============== //
@@ -553,7 +1755,7 @@ where
 /// public key and info.
 ///
 /// Can only fail with [`Error::Info`] or [`Error::Protocol`]
-fn compute_tweaked_key<CS: CipherSuite>(
+pub(crate) fn compute_tweaked_key<CS: CipherSuite>(
     pk: <CS::Group as Group>::Elem,
     info: Option<&[u8]>,
 ) -> Result<<CS::Group as Group>::Elem> {
@@ -578,16 +1780,51 @@ fn compute_tweaked_key<CS: CipherSuite>(
 
     // Check if resulting element
     match bool::from(CS::Group::is_identity_elem(tweaked_key)) {
-        true => Err(Error::Protocol),
+        true => Err(Error::Protocol(ProtocolError::IdentityTweakedKey)),
         false => Ok(tweaked_key),
     }
 }
 
+/// Inner function for POPRF evaluate. Computes the output with the already
+/// inverted tweak.
+///
+/// Can only fail with [`Error::Input`].
+fn evaluate_with_inverted_tweak<CS: CipherSuite>(
+    input: &[u8],
+    info: Option<&[u8]>,
+    inverted_tweak: <CS::Group as Group>::Scalar,
+) -> Result<OprfOutput<CS>> {
+    let input_element = hash_to_group::<CS>(input, Mode::Poprf)?;
+    if CS::Group::is_identity_elem(input_element).into() {
+        return Err(Error::Input(InputError::HashToGroup));
+    };
+
+    let evaluated_element = input_element * &inverted_tweak;
+
+    let issued_element = CS::Group::serialize_elem(evaluated_element);
+
+    server_evaluate_hash_input::<CS>(input, info, issued_element)
+}
+
+/// Inner function for [`PoprfPreparedTweakedKey`]. Computes a digest of `info`
+/// to check it against the `info` used during finalization.
+///
+/// Can only fail with [`Error::Info`].
+fn info_digest<CS: CipherSuite>(info: Option<&[u8]>) -> Result<Output<CS::Hash>> {
+    // None for info is treated the same as empty bytes
+    let info = info.unwrap_or_default();
+
+    Ok(CS::Hash::new()
+        .chain_update(i2osp_2(info.len()).map_err(|_| Error::Info)?)
+        .chain_update(info)
+        .finalize())
+}
+
 /// Inner function for POPRF evaluate. Computes the tweak from the server
 /// private key and info.
 ///
 /// Can only fail with [`Error::Info`] and [`Error::Protocol`].
-fn compute_tweak<CS: CipherSuite>(
+pub(crate) fn compute_tweak<CS: CipherSuite>(
     sk: <CS::Group as Group>::Scalar,
     info: Option<&[u8]>,
 ) -> Result<<CS::Group as Group>::Scalar> {
@@ -610,11 +1847,30 @@ fn compute_tweak<CS: CipherSuite>(
 
     // Check if resulting element is equal to zero
     match bool::from(CS::Group::is_zero_scalar(t)) {
-        true => Err(Error::Protocol),
+        true => Err(Error::Protocol(ProtocolError::ZeroTweak)),
         false => Ok(t),
     }
 }
 
+/// Groups the indices of `infos` by value, in order of first appearance.
+#[cfg(feature = "alloc")]
+fn group_by_info<'a>(infos: impl Iterator<Item = &'a [u8]>) -> Vec<(&'a [u8], Vec<usize>)> {
+    let mut groups: Vec<(&[u8], Vec<usize>)> = Vec::new();
+    let mut positions = BTreeMap::<_, usize>::new();
+
+    for (index, info) in infos.enumerate() {
+        match positions.entry(info) {
+            Entry::Occupied(entry) => groups[*entry.get()].1.push(index),
+            Entry::Vacant(entry) => {
+                entry.insert(groups.len());
+                groups.push((info, vec![index]));
+            }
+        }
+    }
+
+    groups
+}
+
 type PoprfUnblindResult<'a, CS, IC, IM> = Map<
     Zip<
         Map<
@@ -626,29 +1882,24 @@ type PoprfUnblindResult<'a, CS, IC, IM> = Map<
     fn(
         (
             <<CS as CipherSuite>::Group as Group>::Scalar,
-            &'a EvaluationElement<CS>,
+            &'a EvaluationElement<CS, PoprfMode>,
         ),
     ) -> <<CS as CipherSuite>::Group as Group>::Elem,
 >;
 
-/// Can only fail with [`Error::Info`], [`Error::Protocol`], [`Error::Batch] or
-/// [`Error::ProofVerification`].
+/// Can only fail with [`Error::Batch] or [`Error::ProofVerification`].
 fn poprf_unblind<'a, CS: 'a + CipherSuite, IC, IM>(
     clients: &'a IC,
     messages: &'a IM,
-    pk: <CS::Group as Group>::Elem,
+    tweaked_key: <CS::Group as Group>::Elem,
     proof: &Proof<CS>,
-    info: Option<&[u8]>,
 ) -> Result<PoprfUnblindResult<'a, CS, IC, IM>>
 where
     &'a IC: 'a + IntoIterator<Item = &'a PoprfClient<CS>>,
     <&'a IC as IntoIterator>::IntoIter: ExactSizeIterator,
-    &'a IM: 'a + IntoIterator<Item = &'a EvaluationElement<CS>>,
+    &'a IM: 'a + IntoIterator<Item = &'a EvaluationElement<CS, PoprfMode>>,
     <&'a IM as IntoIterator>::IntoIter: ExactSizeIterator,
 {
-    let info = info.unwrap_or_default();
-    let tweaked_key = compute_tweaked_key::<CS>(pk, Some(info))?;
-
     let g = CS::Group::base_elem();
 
     let blinds = clients
@@ -673,14 +1924,18 @@ where
 }
 
 type FinalizeAfterUnblindResult<'a, CS, IE, II> = Map<
-    Zip<Zip<IE, II>, Repeat<&'a [u8]>>,
+    Enumerate<Zip<Zip<IE, II>, Repeat<&'a [u8]>>>,
     fn(
-        ((<<CS as CipherSuite>::Group as Group>::Elem, &[u8]), &[u8]),
-    ) -> Result<Output<<CS as CipherSuite>::Hash>>,
+        (
+            usize,
+            ((<<CS as CipherSuite>::Group as Group>::Elem, &[u8]), &[u8]),
+        ),
+    ) -> Result<OprfOutput<CS>, BatchError>,
 >;
 
 /// Can only fail with [`Error::Batch`] and returned values can only fail with
-/// [`Error::Info`] or [`Error::Input`] individually.
+/// [`Error::Info`] or [`Error::Input`] individually, together with their
+/// index.
 fn finalize_after_unblind<
     'a,
     CS: CipherSuite,
@@ -700,9 +1955,16 @@ where
 
     let info = info.unwrap_or_default();
 
-    Ok(unblinded_elements.zip(inputs).zip(iter::repeat(info)).map(
-        |((unblinded_element, input), info)| {
+    Ok(unblinded_elements
+        .zip(inputs)
+        .zip(iter::repeat(info))
+        .enumerate()
+        .map(|(index, ((unblinded_element, input), info))| {
             let elem_len = <CS::Group as Group>::ElemLen::U16.to_be_bytes();
+            let input_len = i2osp_2(input.as_ref().len())
+                .map_err(|_| BatchError::new(index, Error::input_too_long(input.as_ref().len())))?;
+            let info_len =
+                i2osp_2(info.as_ref().len()).map_err(|_| BatchError::new(index, Error::Info))?;
 
             // hashInput = I2OSP(len(input), 2) || input ||
             //             I2OSP(len(info), 2) || info ||
@@ -710,18 +1972,17 @@ where
             //             "Finalize"
             // return Hash(hashInput)
             let output = CS::Hash::new()
-                .chain_update(i2osp_2(input.as_ref().len()).map_err(|_| Error::Input)?)
+                .chain_update(input_len)
                 .chain_update(input.as_ref())
-                .chain_update(i2osp_2(info.as_ref().len()).map_err(|_| Error::Info)?)
+                .chain_update(info_len)
                 .chain_update(info.as_ref())
                 .chain_update(elem_len)
                 .chain_update(CS::Group::serialize_elem(unblinded_element))
                 .chain_update(STR_FINALIZE)
                 .finalize();
 
-            Ok(output)
-        },
-    ))
+            Ok(OprfOutput(output))
+        }))
 }
 
 ///////////
@@ -731,12 +1992,14 @@ where
 
 #[cfg(test)]
 mod tests {
+    use ::alloc::format;
     use core::ptr;
 
     use rand::rngs::OsRng;
 
     use super::*;
     use crate::common::STR_HASH_TO_GROUP;
+    use crate::tests::mock_rng::FailingRng;
     use crate::Group;
 
     fn prf<CS: CipherSuite>(
@@ -744,7 +2007,7 @@ mod tests {
         key: <CS::Group as Group>::Scalar,
         info: &[u8],
         mode: Mode,
-    ) -> Output<CS::Hash> {
+    ) -> OprfOutput<CS> {
         let t = compute_tweak::<CS>(key, Some(info)).unwrap();
 
         let dst = Dst::new::<CS, _, _>(STR_HASH_TO_GROUP, mode);
@@ -775,12 +2038,485 @@ mod tests {
                 input,
                 &server_result.message,
                 &server_result.proof,
-                server.get_public_key(),
+                &server.get_public_key(),
+                Some(info),
+            )
+            .unwrap();
+        let res2 = prf::<CS>(input, server.get_private_key().0, info, Mode::Poprf);
+        assert_eq!(client_finalize_result, res2);
+    }
+
+    fn deterministic_proof<CS: CipherSuite>() {
+        let input = b"input";
+        let info = b"info";
+        let mut rng = OsRng;
+        let server = PoprfServer::<CS>::new(&mut rng).unwrap();
+        let client_blind_result = PoprfClient::<CS>::blind(input, &mut rng).unwrap();
+
+        let server_result = server
+            .blind_evaluate_deterministic(&client_blind_result.message, Some(info))
+            .unwrap();
+        let repeated_result = server
+            .blind_evaluate_deterministic(&client_blind_result.message, Some(info))
+            .unwrap();
+        assert_eq!(
+            server_result.proof.serialize(),
+            repeated_result.proof.serialize()
+        );
+
+        // Hedged proofs differ between evaluations
+        let hedged_result = server
+            .blind_evaluate(&mut rng, &client_blind_result.message, Some(info))
+            .unwrap();
+        assert!(server_result.proof.serialize() != hedged_result.proof.serialize());
+
+        // A failing RNG falls back to the deterministic proof
+        let fallback_result = server
+            .blind_evaluate(&mut FailingRng, &client_blind_result.message, Some(info))
+            .unwrap();
+        assert_eq!(
+            server_result.proof.serialize(),
+            fallback_result.proof.serialize()
+        );
+
+        let client_finalize_result = client_blind_result
+            .state
+            .finalize(
+                input,
+                &server_result.message,
+                &server_result.proof,
+                &server.get_public_key(),
+                Some(info),
+            )
+            .unwrap();
+        let res2 = prf::<CS>(input, server.get_private_key().0, info, Mode::Poprf);
+        assert_eq!(client_finalize_result, res2);
+    }
+
+    #[cfg(feature = "alloc")]
+    fn verifiable_server_response<CS: CipherSuite>() {
+        let infos = [b"info 1".as_slice(), b"info 2"];
+        let mut rng = OsRng;
+        let server = PoprfServer::<CS>::new(&mut rng).unwrap();
+        let pk = &server.get_public_key();
+        let batches = [[b"input 1", b"input 2"], [b"input 3", b"input 4"]].map(|inputs| {
+            inputs.map(|input| PoprfClient::<CS>::blind(input, &mut rng).unwrap().message)
+        });
+        let results = [0, 1].map(|index| {
+            server
+                .batch_blind_evaluate(&mut rng, &batches[index], Some(infos[index]))
+                .unwrap()
+        });
+
+        // An auditor only needs the exchanged messages, the public key and `info`
+        PoprfServer::<CS>::verify_server_response(
+            pk,
+            &batches[0],
+            &results[0].messages,
+            &results[0].proof,
+            Some(infos[0]),
+        )
+        .unwrap();
+        let result = PoprfServer::<CS>::verify_server_response(
+            pk,
+            &batches[0],
+            &results[0].messages,
+            &results[0].proof,
+            Some(infos[1]),
+        );
+        assert!(matches!(result, Err(Error::ProofVerification)));
+        let result = PoprfServer::<CS>::verify_server_response(
+            &PublicKey(CS::Group::base_elem()),
+            &batches[0],
+            &results[0].messages,
+            &results[0].proof,
+            Some(infos[0]),
+        );
+        assert!(matches!(result, Err(Error::ProofVerification)));
+        let result = PoprfServer::<CS>::verify_server_response(
+            pk,
+            &batches[1],
+            &results[0].messages,
+            &results[0].proof,
+            Some(infos[0]),
+        );
+        assert!(matches!(result, Err(Error::ProofVerification)));
+
+        let responses = [0, 1].map(|index| {
+            (
+                batches[index].as_slice(),
+                results[index].messages.as_slice(),
+                &results[index].proof,
+                Some(infos[index]),
+            )
+        });
+        PoprfServer::<CS>::verify_server_responses(pk, responses).unwrap();
+
+        // The index of the first failing batch is reported
+        let responses = [0, 1].map(|index| {
+            (
+                batches[index].as_slice(),
+                results[index].messages.as_slice(),
+                &results[index].proof,
+                Some(infos[0]),
+            )
+        });
+        let result = PoprfServer::<CS>::verify_server_responses(pk, responses);
+        assert!(matches!(
+            result,
+            Err(BatchError {
+                index: Some(1),
+                error: Error::ProofVerification
+            })
+        ));
+    }
+
+    #[cfg(feature = "alloc")]
+    fn verifiable_chunked_retrieval<CS: CipherSuite>() {
+        use ::alloc::vec;
+        use ::alloc::vec::Vec;
+        use rand::TryRngCore;
+
+        let info = b"info";
+        let mut rng = OsRng;
+        let mut inputs = vec![];
+        let mut client_states = vec![];
+        let mut client_messages = vec![];
+        for _ in 0..10 {
+            let mut input = [0u8; 32];
+            rng.try_fill_bytes(&mut input).unwrap();
+            let client_blind_result = PoprfClient::<CS>::blind(&input, &mut rng).unwrap();
+            inputs.push(input);
+            client_states.push(client_blind_result.state);
+            client_messages.push(client_blind_result.message);
+        }
+        let server = PoprfServer::<CS>::new(&mut rng).unwrap();
+        let mut server_rng = OsRng;
+        let responses = server
+            .batch_blind_evaluate_chunks_of(&mut server_rng, client_messages, Some(info), 4)
+            .collect::<Result<Vec<_>>>()
+            .unwrap();
+        assert_eq!(responses.len(), 3);
+
+        let client_finalize_result = PoprfClient::batch_finalize_chunked(
+            &inputs,
+            client_states,
+            responses,
+            &server.get_public_key(),
+            Some(info),
+        )
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap()
+        .concat();
+        let res2: Vec<_> = inputs
+            .iter()
+            .map(|input| prf::<CS>(input, server.get_private_key().0, info, Mode::Poprf))
+            .collect();
+        assert_eq!(client_finalize_result, res2);
+    }
+
+    #[cfg(feature = "alloc")]
+    fn verifiable_mixed_info_retrieval<CS: CipherSuite>() {
+        use ::alloc::vec::Vec;
+
+        let inputs: [&[u8]; 5] = [b"input 1", b"input 2", b"input 3", b"input 4", b"input 5"];
+        let infos: [Option<&[u8]>; 5] = [Some(b"a"), None, Some(b"b"), Some(b"a"), Some(b"")];
+        let mut rng = OsRng;
+        let server = PoprfServer::<CS>::new(&mut rng).unwrap();
+        let (client_states, client_messages): (Vec<_>, Vec<_>) = inputs
+            .iter()
+            .map(|input| {
+                let result = PoprfClient::<CS>::blind(input, &mut rng).unwrap();
+                (result.state, result.message)
+            })
+            .unzip();
+
+        let PoprfServerBatchEvaluateMixedInfoResult {
+            messages,
+            mut proofs,
+        } = server
+            .batch_blind_evaluate_mixed_info(&mut rng, client_messages.iter().zip(infos))
+            .unwrap();
+        // `None` and an empty `info` share a group.
+        assert_eq!(proofs.len(), 3);
+
+        let client_finalize_result = PoprfClient::batch_finalize_mixed_info(
+            inputs.into_iter().zip(infos),
+            &client_states,
+            &messages,
+            &proofs,
+            &server.get_public_key(),
+        )
+        .unwrap()
+        .into_iter()
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+        let res2: Vec<_> = inputs
+            .iter()
+            .zip(infos)
+            .map(|(input, info)| {
+                prf::<CS>(
+                    input,
+                    server.get_private_key().0,
+                    info.unwrap_or_default(),
+                    Mode::Poprf,
+                )
+            })
+            .collect();
+        assert_eq!(client_finalize_result, res2);
+
+        proofs.swap(0, 2);
+        let client_finalize_result = PoprfClient::batch_finalize_mixed_info(
+            inputs.into_iter().zip(infos),
+            &client_states,
+            &messages,
+            &proofs,
+            &server.get_public_key(),
+        );
+        assert_eq!(
+            client_finalize_result.unwrap_err(),
+            Error::ProofVerification
+        );
+
+        let client_finalize_result = PoprfClient::batch_finalize_mixed_info(
+            inputs.into_iter().zip(infos),
+            &client_states,
+            &messages,
+            &proofs[..2],
+            &server.get_public_key(),
+        );
+        assert_eq!(client_finalize_result.unwrap_err(), Error::Batch);
+
+        // A different input order changes the grouping, so the proofs no
+        // longer match.
+        proofs.swap(0, 2);
+        let order = [1, 0, 2, 3, 4];
+        let client_finalize_result = PoprfClient::batch_finalize_mixed_info(
+            order.map(|index| (inputs[index], infos[index])),
+            &order.map(|index| client_states[index].clone()),
+            &order.map(|index| messages[index].clone()),
+            &proofs,
+            &server.get_public_key(),
+        );
+        assert_eq!(
+            client_finalize_result.unwrap_err(),
+            Error::ProofVerification
+        );
+    }
+
+    #[cfg(feature = "alloc")]
+    fn verifiable_filtered_retrieval<CS: CipherSuite>() {
+        let inputs = [b"input 1".as_slice(), b"input 2", b"input 3"];
+        let info = b"info";
+        let mut rng = OsRng;
+        let client_blind_results =
+            inputs.map(|input| PoprfClient::<CS>::blind(input, &mut rng).unwrap());
+        let clients = client_blind_results
+            .each_ref()
+            .map(|result| result.state.clone());
+        let client_messages = client_blind_results
+            .each_ref()
+            .map(|result| result.message.clone());
+        let server = PoprfServer::<CS>::new(&mut rng).unwrap();
+        let PoprfServerBatchEvaluateFilteredResult {
+            messages,
+            proof,
+            index_map,
+        } = server
+            .batch_blind_evaluate_filtered(&mut rng, &client_messages, Some(info), |index, _| {
+                index != 0
+            })
+            .unwrap();
+        assert!(index_map.rejected().eq([0]));
+
+        let outputs = PoprfClient::batch_finalize_filtered(
+            inputs,
+            &clients,
+            &messages,
+            &proof,
+            &server.get_public_key(),
+            Some(info),
+            &index_map,
+        )
+        .unwrap();
+        assert!(outputs[0].is_none());
+        for index in index_map.accepted() {
+            let res2 = prf::<CS>(inputs[index], server.get_private_key().0, info, Mode::Poprf);
+            assert_eq!(outputs[index], Some(Ok(res2)));
+        }
+
+        let result = PoprfClient::batch_finalize_filtered(
+            inputs,
+            &clients,
+            &messages,
+            &proof,
+            &server.get_public_key(),
+            Some(b"wrong info"),
+            &index_map,
+        );
+        assert_eq!(result.unwrap_err(), Error::ProofVerification);
+    }
+
+    #[cfg(feature = "alloc")]
+    fn verifiable_batch_errors<CS: CipherSuite>() {
+        let info = b"info";
+        let mut rng = OsRng;
+        let client_blind_results = [b"input 1", b"input 2", b"input 3"]
+            .map(|input| PoprfClient::<CS>::blind(input, &mut rng).unwrap());
+        let clients = client_blind_results
+            .each_ref()
+            .map(|result| result.state.clone());
+        let client_messages = client_blind_results
+            .each_ref()
+            .map(|result| result.message.clone());
+        let server = PoprfServer::<CS>::new(&mut rng).unwrap();
+        let PoprfServerBatchEvaluatePrepareResult {
+            prepared_evaluation_elements,
+            prepared_tweak,
+        } = server
+            .batch_blind_evaluate_prepare(client_messages.iter(), Some(info))
+            .unwrap();
+        let prepared_evaluation_elements: Vec<_> = prepared_evaluation_elements.collect();
+        let PoprfServerBatchEvaluateFinishResult { messages, proof } =
+            PoprfServer::batch_blind_evaluate_finish(
+                &mut rng,
+                client_messages.iter(),
+                &prepared_evaluation_elements,
+                &prepared_tweak,
+            )
+            .unwrap();
+        let messages: Vec<_> = messages.collect();
+        let long_input = [0; 1 << 16];
+        let inputs = [b"input 1".as_slice(), &long_input, b""];
+
+        // Invalid inputs are reported with their index
+        let outputs: Vec<_> = PoprfClient::batch_finalize(
+            inputs.into_iter(),
+            &clients,
+            &messages,
+            &proof,
+            &server.get_public_key(),
+            Some(info),
+        )
+        .unwrap()
+        .collect();
+        assert!(outputs[0].is_ok());
+        assert_eq!(
+            outputs[1],
+            Err(BatchError::new(1, Error::input_too_long(65536)))
+        );
+
+        // All inputs are checked before the proof
+        let result = PoprfClient::batch_finalize_checked(
+            inputs.into_iter(),
+            &clients,
+            &messages,
+            &proof,
+            &PublicKey(CS::Group::base_elem()),
+            Some(info),
+        );
+        assert_eq!(
+            result.err(),
+            Some(BatchError::new(1, Error::input_too_long(65536)))
+        );
+        let inputs = [b"input 1".as_slice(), b"input 2", b"input 3"];
+        let result = PoprfClient::batch_finalize_checked(
+            inputs.into_iter(),
+            &clients,
+            &messages,
+            &proof,
+            &PublicKey(CS::Group::base_elem()),
+            Some(info),
+        );
+        assert_eq!(result.err(), Some(Error::ProofVerification.into()));
+        let result = PoprfClient::batch_finalize_checked(
+            inputs.into_iter(),
+            &clients,
+            &messages,
+            &proof,
+            &server.get_public_key(),
+            Some(info),
+        );
+        assert!(result.unwrap().all(|output| output.is_ok()));
+        let inputs = [b"input 1".as_slice(), b"input 2", b""];
+        let result = PoprfClient::batch_finalize_checked(
+            inputs.into_iter(),
+            &clients,
+            &messages,
+            &proof,
+            &server.get_public_key(),
+            Some(info),
+        );
+        assert_eq!(
+            result.err(),
+            Some(BatchError::new(2, Error::Input(InputError::Empty)))
+        );
+    }
+
+    fn verifiable_prepared_tweak_retrieval<CS: CipherSuite>() {
+        let input = b"input";
+        let info = b"info";
+        let mut rng = OsRng;
+        let server = PoprfServer::<CS>::new(&mut rng).unwrap();
+        let prepared_tweak = server.prepare_tweak(Some(info)).unwrap();
+        let tweaked_key =
+            PoprfClient::<CS>::prepare_tweaked_key(&server.get_public_key(), Some(info)).unwrap();
+        let client_blind_result = PoprfClient::<CS>::blind(input, &mut rng).unwrap();
+        let server_result = PoprfServer::blind_evaluate_with_tweak(
+            &mut rng,
+            &client_blind_result.message,
+            &prepared_tweak,
+        );
+        let client_finalize_result = client_blind_result
+            .state
+            .finalize_with_tweaked_key(
+                input,
+                &server_result.message,
+                &server_result.proof,
+                &tweaked_key,
                 Some(info),
             )
             .unwrap();
-        let res2 = prf::<CS>(input, server.get_private_key(), info, Mode::Poprf);
+        let res2 = prf::<CS>(input, server.get_private_key().0, info, Mode::Poprf);
         assert_eq!(client_finalize_result, res2);
+
+        let client_finalize_result = client_blind_result.state.finalize_with_tweaked_key(
+            input,
+            &server_result.message,
+            &server_result.proof,
+            &tweaked_key,
+            Some(b"wrong info"),
+        );
+        assert_eq!(client_finalize_result.unwrap_err(), Error::TweakMismatch);
+    }
+
+    #[cfg(feature = "alloc")]
+    fn tweak_cache<CS: CipherSuite>() {
+        let mut rng = OsRng;
+        let server = PoprfServer::<CS>::new(&mut rng).unwrap();
+        let mut cache = PoprfTweakCache::new(2);
+
+        let prepared_tweak = cache.get_or_prepare(&server, Some(b"a")).unwrap();
+        let expected = server.prepare_tweak(Some(b"a")).unwrap();
+        assert!(bool::from(prepared_tweak.0.ct_eq(&expected.0)));
+        cache.get_or_prepare(&server, Some(b"a")).unwrap();
+        assert_eq!(cache.len(), 1);
+
+        // `None` and an empty `info` share an entry.
+        cache.get_or_prepare(&server, None).unwrap();
+        cache.get_or_prepare(&server, Some(b"")).unwrap();
+        assert_eq!(cache.len(), 2);
+
+        let prepared_tweak = cache.get_or_prepare(&server, Some(b"b")).unwrap();
+        let expected = server.prepare_tweak(Some(b"b")).unwrap();
+        assert!(bool::from(prepared_tweak.0.ct_eq(&expected.0)));
+        assert_eq!(cache.len(), 2);
+
+        let other_server = PoprfServer::<CS>::new(&mut rng).unwrap();
+        let prepared_tweak = cache.get_or_prepare(&other_server, Some(b"b")).unwrap();
+        let expected = other_server.prepare_tweak(Some(b"b")).unwrap();
+        assert!(bool::from(prepared_tweak.0.ct_eq(&expected.0)));
+        assert_eq!(cache.len(), 1);
     }
 
     fn verifiable_bad_public_key<CS: CipherSuite>() {
@@ -795,13 +2531,13 @@ mod tests {
         let wrong_pk = {
             let dst = Dst::new::<CS, _, _>(STR_HASH_TO_GROUP, Mode::Oprf);
             // Choose a group element that is unlikely to be the right public key
-            CS::Group::hash_to_curve::<CS::Hash>(&[b"msg"], &dst.as_dst()).unwrap()
+            PublicKey(CS::Group::hash_to_curve::<CS::Hash>(&[b"msg"], &dst.as_dst()).unwrap())
         };
         let client_finalize_result = client_blind_result.state.finalize(
             input,
             &server_result.message,
             &server_result.proof,
-            wrong_pk,
+            &wrong_pk,
             Some(info),
         );
         assert!(client_finalize_result.is_err());
@@ -823,7 +2559,7 @@ mod tests {
                 input,
                 &server_result.message,
                 &server_result.proof,
-                server.get_public_key(),
+                &server.get_public_key(),
                 info,
             )
             .unwrap();
@@ -838,6 +2574,51 @@ mod tests {
         let wrong_input = b"wrong input";
         let server_evaluate = server.evaluate(wrong_input, info).unwrap();
         assert!(client_finalize != server_evaluate);
+
+        // We expect batched outputs to match the individual ones in order
+        let inputs = [input.as_slice(), wrong_input];
+        let mut server_evaluate_many = server.evaluate_many(inputs, info).unwrap();
+        // The inverted tweaked key is redacted
+        assert_eq!(
+            format!("{server_evaluate_many:?}"),
+            format!(
+                "PoprfServerEvaluateManyResult {{ suite: {:?}, .. }}",
+                CS::ID
+            )
+        );
+        assert_eq!(
+            server_evaluate_many.next().unwrap().unwrap(),
+            client_finalize
+        );
+        assert_eq!(
+            server_evaluate_many.next().unwrap().unwrap(),
+            server_evaluate
+        );
+        assert!(server_evaluate_many.next().is_none());
+    }
+
+    #[cfg(feature = "rayon")]
+    fn par_server_evaluate<CS: CipherSuite>()
+    where
+        PoprfServer<CS>: Sync,
+        <CS::Group as Group>::Scalar: Send + Sync,
+    {
+        let inputs = [b"input 1".as_slice(), b"input 2", b"input 3"];
+        let mut rng = OsRng;
+        let server = PoprfServer::<CS>::new(&mut rng).unwrap();
+
+        let server_evaluate_many = server
+            .evaluate_many(inputs, Some(b"info".as_slice()))
+            .unwrap()
+            .collect::<Result<::alloc::vec::Vec<_>>>()
+            .unwrap();
+        let par_server_evaluate_many = server
+            .par_evaluate_many(inputs, Some(b"info".as_slice()))
+            .unwrap()
+            .into_iter()
+            .collect::<Result<::alloc::vec::Vec<_>>>()
+            .unwrap();
+        assert_eq!(server_evaluate_many, par_server_evaluate_many);
     }
 
     fn zeroize_verifiable_client<CS: CipherSuite>() {
@@ -888,30 +2669,94 @@ mod tests {
             use crate::Ristretto255;
 
             verifiable_retrieval::<Ristretto255>();
+            deterministic_proof::<Ristretto255>();
+            #[cfg(feature = "alloc")]
+            verifiable_server_response::<Ristretto255>();
+            #[cfg(feature = "alloc")]
+            verifiable_chunked_retrieval::<Ristretto255>();
+            #[cfg(feature = "alloc")]
+            verifiable_mixed_info_retrieval::<Ristretto255>();
+            verifiable_prepared_tweak_retrieval::<Ristretto255>();
+            #[cfg(feature = "alloc")]
+            verifiable_batch_errors::<Ristretto255>();
+            #[cfg(feature = "alloc")]
+            verifiable_filtered_retrieval::<Ristretto255>();
+            #[cfg(feature = "alloc")]
+            tweak_cache::<Ristretto255>();
             verifiable_bad_public_key::<Ristretto255>();
             verifiable_server_evaluate::<Ristretto255>();
+            #[cfg(feature = "rayon")]
+            par_server_evaluate::<Ristretto255>();
 
             zeroize_verifiable_client::<Ristretto255>();
             zeroize_verifiable_server::<Ristretto255>();
         }
 
         verifiable_retrieval::<NistP256>();
+        deterministic_proof::<NistP256>();
+        #[cfg(feature = "alloc")]
+        verifiable_server_response::<NistP256>();
+        #[cfg(feature = "alloc")]
+        verifiable_chunked_retrieval::<NistP256>();
+        #[cfg(feature = "alloc")]
+        verifiable_mixed_info_retrieval::<NistP256>();
+        verifiable_prepared_tweak_retrieval::<NistP256>();
+        #[cfg(feature = "alloc")]
+        verifiable_batch_errors::<NistP256>();
+        #[cfg(feature = "alloc")]
+        verifiable_filtered_retrieval::<NistP256>();
+        #[cfg(feature = "alloc")]
+        tweak_cache::<NistP256>();
         verifiable_bad_public_key::<NistP256>();
         verifiable_server_evaluate::<NistP256>();
+        #[cfg(feature = "rayon")]
+        par_server_evaluate::<NistP256>();
 
         zeroize_verifiable_client::<NistP256>();
         zeroize_verifiable_server::<NistP256>();
 
         verifiable_retrieval::<NistP384>();
+        deterministic_proof::<NistP384>();
+        #[cfg(feature = "alloc")]
+        verifiable_server_response::<NistP384>();
+        #[cfg(feature = "alloc")]
+        verifiable_chunked_retrieval::<NistP384>();
+        #[cfg(feature = "alloc")]
+        verifiable_mixed_info_retrieval::<NistP384>();
+        verifiable_prepared_tweak_retrieval::<NistP384>();
+        #[cfg(feature = "alloc")]
+        verifiable_batch_errors::<NistP384>();
+        #[cfg(feature = "alloc")]
+        verifiable_filtered_retrieval::<NistP384>();
+        #[cfg(feature = "alloc")]
+        tweak_cache::<NistP384>();
         verifiable_bad_public_key::<NistP384>();
         verifiable_server_evaluate::<NistP384>();
+        #[cfg(feature = "rayon")]
+        par_server_evaluate::<NistP384>();
 
         zeroize_verifiable_client::<NistP384>();
         zeroize_verifiable_server::<NistP384>();
 
         verifiable_retrieval::<NistP521>();
+        deterministic_proof::<NistP521>();
+        #[cfg(feature = "alloc")]
+        verifiable_server_response::<NistP521>();
+        #[cfg(feature = "alloc")]
+        verifiable_chunked_retrieval::<NistP521>();
+        #[cfg(feature = "alloc")]
+        verifiable_mixed_info_retrieval::<NistP521>();
+        verifiable_prepared_tweak_retrieval::<NistP521>();
+        #[cfg(feature = "alloc")]
+        verifiable_batch_errors::<NistP521>();
+        #[cfg(feature = "alloc")]
+        verifiable_filtered_retrieval::<NistP521>();
+        #[cfg(feature = "alloc")]
+        tweak_cache::<NistP521>();
         verifiable_bad_public_key::<NistP521>();
         verifiable_server_evaluate::<NistP521>();
+        #[cfg(feature = "rayon")]
+        par_server_evaluate::<NistP521>();
 
         zeroize_verifiable_client::<NistP521>();
         zeroize_verifiable_server::<NistP521>();
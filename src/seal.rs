@@ -0,0 +1,550 @@
+// Copyright (c) Meta Platforms, Inc. and affiliates.
+//
+// This source code is dual-licensed under either the MIT license found in the
+// LICENSE-MIT file in the root directory of this source tree or the Apache
+// License, Version 2.0 found in the LICENSE-APACHE file in the root directory
+// of this source tree. You may select, at your option, one of the above-listed
+// licenses.
+
+//! Password-sealed export of servers
+//!
+//! The key is derived from the password with Argon2id
+//! ([RFC 9106](https://www.rfc-editor.org/rfc/rfc9106)) and the serialized
+//! server is encrypted with ChaCha20-Poly1305
+//! ([RFC 8439](https://www.rfc-editor.org/rfc/rfc8439)). All other fields,
+//! including the cipher suite, mode and public key, are authenticated as
+//! associated data. The format is described in [`SealedKeyMetadata`].
+
+use alloc::vec::Vec;
+
+use argon2::{Algorithm, Argon2, Params, Version};
+use chacha20poly1305::aead::AeadInPlace;
+use chacha20poly1305::{ChaCha20Poly1305, Key, KeyInit, Nonce, Tag};
+use generic_array::typenum::Unsigned;
+use rand_core::{TryCryptoRng, TryRngCore};
+use zeroize::Zeroizing;
+
+use crate::serialization::take_field;
+use crate::{
+    CipherSuite, DeserializationReason, Error, Mode, OprfMode, OprfServer, PoprfMode, PoprfServer,
+    PrivateKey, ProtocolMode, PublicKey, PublicKeyLen, Result, Serializable, VoprfMode,
+    VoprfServer,
+};
+
+///////////////
+// Constants //
+// ========= //
+///////////////
+
+/// The current version of the sealed key format.
+pub const SEALED_KEY_VERSION: u8 = 1;
+
+const KEY_LEN: usize = 32;
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+const TAG_LEN: usize = 16;
+
+////////////////////////////
+// High-level API Structs //
+// ====================== //
+////////////////////////////
+
+/// The Argon2id parameters used to derive the key from the password.
+///
+/// The [`Default`] follows the recommendation of the
+/// [OWASP](https://cheatsheetseries.owasp.org/cheatsheets/Password_Storage_Cheat_Sheet.html)
+/// of 19 MiB of memory, 2 iterations and 1 degree of parallelism.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct SealParams {
+    /// Memory size in KiB.
+    pub memory_kib: u32,
+    /// Number of iterations.
+    pub iterations: u32,
+    /// Degree of parallelism.
+    pub parallelism: u32,
+}
+
+impl SealParams {
+    /// The largest parameters accepted when unsealing, which bound the
+    /// resources an untrusted sealed key can consume: 1 GiB of memory, 64
+    /// iterations and 16 degrees of parallelism.
+    pub const MAX: Self = Self {
+        memory_kib: 1 << 20,
+        iterations: 64,
+        parallelism: 16,
+    };
+
+    fn to_argon2(self) -> Result<Argon2<'static>> {
+        let params = Params::new(
+            self.memory_kib,
+            self.iterations,
+            self.parallelism,
+            Some(KEY_LEN),
+        )
+        .map_err(|_| Error::SealParams)?;
+
+        Ok(Argon2::new(Algorithm::Argon2id, Version::V0x13, params))
+    }
+}
+
+impl Default for SealParams {
+    fn default() -> Self {
+        Self {
+            memory_kib: Params::DEFAULT_M_COST,
+            iterations: Params::DEFAULT_T_COST,
+            parallelism: Params::DEFAULT_P_COST,
+        }
+    }
+}
+
+/// The metadata of a sealed key, which can be inspected without the password,
+/// e.g. to compare the public key with the expected one before unsealing.
+///
+/// The encoding is:
+///
+/// ```text
+/// version:     u8 (currently `1`)
+/// suite_len:   u8
+/// suite:       [u8; suite_len] (`CipherSuite::ID`)
+/// mode:        u8 (`Mode::to_u8()`)
+/// memory_kib:  u32 (big-endian)
+/// iterations:  u32 (big-endian)
+/// parallelism: u32 (big-endian)
+/// salt:        [u8; 16]
+/// nonce:       [u8; 12]
+/// public_key:  [u8; PublicKeyLen]
+/// ciphertext:  [u8; ServerLen + 16]
+/// ```
+///
+/// Everything before the ciphertext is the associated data. The metadata isn't
+/// authenticated until the key is unsealed.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct SealedKeyMetadata<'a> {
+    /// The [`CipherSuite::ID`] of the key.
+    pub suite: &'a [u8],
+    /// The mode of the server.
+    pub mode: Mode,
+    /// The parameters the key was derived with.
+    pub params: SealParams,
+    /// The remaining fields, starting with the salt.
+    rest: &'a [u8],
+}
+
+impl<'a> SealedKeyMetadata<'a> {
+    /// Decodes the metadata of a sealed key.
+    ///
+    /// # Errors
+    /// [`Error::Deserialization`] if the metadata is malformed or has an
+    /// unsupported version.
+    pub fn from_sealed_key(mut input: &'a [u8]) -> Result<Self> {
+        let version = take_field(&mut input, 1, "version")?[0];
+
+        if version != SEALED_KEY_VERSION {
+            return Err(Error::deserialization(
+                "version",
+                DeserializationReason::Version,
+            ));
+        }
+
+        let suite_len = take_field(&mut input, 1, "suite")?[0];
+        let suite = take_field(&mut input, suite_len.into(), "suite")?;
+        let mode = Mode::from_u8(take_field(&mut input, 1, "mode")?[0])
+            .ok_or(Error::deserialization("mode", DeserializationReason::Mode))?;
+        let mut take_u32 = || {
+            take_field(&mut input, 4, "params")
+                .map(|bytes| u32::from_be_bytes(bytes.try_into().unwrap()))
+        };
+        let params = SealParams {
+            memory_kib: take_u32()?,
+            iterations: take_u32()?,
+            parallelism: take_u32()?,
+        };
+
+        Ok(Self {
+            suite,
+            mode,
+            params,
+            rest: input,
+        })
+    }
+
+    /// Returns the public key of the sealed server.
+    ///
+    /// # Errors
+    /// [`Error::Deserialization`] if the sealed key doesn't belong to `CS` or
+    /// the public key is malformed.
+    pub fn public_key<CS: CipherSuite>(&self) -> Result<PublicKey<CS>> {
+        self.check_suite::<CS>()?;
+        let mut rest = self.rest;
+        take_field(&mut rest, SALT_LEN + NONCE_LEN, "nonce")?;
+        let public_key = take_field(&mut rest, PublicKeyLen::<CS>::USIZE, "pk")?;
+
+        PublicKey::deserialize(public_key)
+    }
+
+    fn check_suite<CS: CipherSuite>(&self) -> Result<()> {
+        if self.suite != CS::ID.as_bytes() {
+            return Err(Error::deserialization(
+                "suite",
+                DeserializationReason::Suite,
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+impl<CS: CipherSuite> OprfServer<CS> {
+    /// Encrypts this server with a key derived from `password`, see
+    /// [`SealedKeyMetadata`].
+    ///
+    /// # Errors
+    /// - [`Error::SealParams`] if `params` are invalid.
+    /// - [`Error::Rng`] if the `rng` fails.
+    pub fn seal<R: TryRngCore + TryCryptoRng>(
+        &self,
+        password: &[u8],
+        params: SealParams,
+        rng: &mut R,
+    ) -> Result<Vec<u8>> {
+        seal::<CS, OprfMode, _, _>(
+            self,
+            &PrivateKey::<CS>(self.sk).public_key(),
+            password,
+            params,
+            rng,
+        )
+    }
+
+    /// Decrypts a server sealed by [`seal()`](Self::seal).
+    ///
+    /// # Errors
+    /// - [`Error::Deserialization`] if `input` is malformed or belongs to a
+    ///   different [`CipherSuite`] or mode.
+    /// - [`Error::SealParams`] if the parameters exceed [`SealParams::MAX`].
+    /// - [`Error::Unseal`] if the password is wrong or `input` was modified.
+    pub fn unseal(input: &[u8], password: &[u8]) -> Result<Self> {
+        unseal::<CS, OprfMode, _>(input, password, |server: &Self| {
+            PrivateKey::<CS>(server.sk).public_key()
+        })
+    }
+}
+
+impl<CS: CipherSuite> VoprfServer<CS> {
+    /// Encrypts this server with a key derived from `password`, see
+    /// [`SealedKeyMetadata`].
+    ///
+    /// # Errors
+    /// - [`Error::SealParams`] if `params` are invalid.
+    /// - [`Error::Rng`] if the `rng` fails.
+    pub fn seal<R: TryRngCore + TryCryptoRng>(
+        &self,
+        password: &[u8],
+        params: SealParams,
+        rng: &mut R,
+    ) -> Result<Vec<u8>> {
+        seal::<CS, VoprfMode, _, _>(self, &self.get_public_key(), password, params, rng)
+    }
+
+    /// Decrypts a server sealed by [`seal()`](Self::seal).
+    ///
+    /// # Errors
+    /// - [`Error::Deserialization`] if `input` is malformed or belongs to a
+    ///   different [`CipherSuite`] or mode.
+    /// - [`Error::SealParams`] if the parameters exceed [`SealParams::MAX`].
+    /// - [`Error::Unseal`] if the password is wrong or `input` was modified.
+    pub fn unseal(input: &[u8], password: &[u8]) -> Result<Self> {
+        unseal::<CS, VoprfMode, _>(input, password, Self::get_public_key)
+    }
+}
+
+impl<CS: CipherSuite> PoprfServer<CS> {
+    /// Encrypts this server with a key derived from `password`, see
+    /// [`SealedKeyMetadata`].
+    ///
+    /// # Errors
+    /// - [`Error::SealParams`] if `params` are invalid.
+    /// - [`Error::Rng`] if the `rng` fails.
+    pub fn seal<R: TryRngCore + TryCryptoRng>(
+        &self,
+        password: &[u8],
+        params: SealParams,
+        rng: &mut R,
+    ) -> Result<Vec<u8>> {
+        seal::<CS, PoprfMode, _, _>(self, &self.get_public_key(), password, params, rng)
+    }
+
+    /// Decrypts a server sealed by [`seal()`](Self::seal).
+    ///
+    /// # Errors
+    /// - [`Error::Deserialization`] if `input` is malformed or belongs to a
+    ///   different [`CipherSuite`] or mode.
+    /// - [`Error::SealParams`] if the parameters exceed [`SealParams::MAX`].
+    /// - [`Error::Unseal`] if the password is wrong or `input` was modified.
+    pub fn unseal(input: &[u8], password: &[u8]) -> Result<Self> {
+        unseal::<CS, PoprfMode, _>(input, password, Self::get_public_key)
+    }
+}
+
+/////////////////////
+// Inner functions //
+// =============== //
+/////////////////////
+
+fn seal<CS: CipherSuite, M: ProtocolMode, S: Serializable, R: TryRngCore + TryCryptoRng>(
+    server: &S,
+    public_key: &PublicKey<CS>,
+    password: &[u8],
+    params: SealParams,
+    rng: &mut R,
+) -> Result<Vec<u8>> {
+    let argon2 = params.to_argon2()?;
+    let mut salt = [0; SALT_LEN];
+    rng.try_fill_bytes(&mut salt).map_err(|_| Error::Rng)?;
+    let mut nonce = Nonce::default();
+    rng.try_fill_bytes(&mut nonce).map_err(|_| Error::Rng)?;
+
+    let suite = CS::ID.as_bytes();
+    let mut output = Vec::with_capacity(
+        15 + suite.len()
+            + SALT_LEN
+            + NONCE_LEN
+            + PublicKeyLen::<CS>::USIZE
+            + S::Len::USIZE
+            + TAG_LEN,
+    );
+    output.push(SEALED_KEY_VERSION);
+    // Suite IDs are short.
+    output.push(suite.len().try_into().unwrap());
+    output.extend_from_slice(suite);
+    output.push(M::MODE.to_u8());
+    output.extend_from_slice(&params.memory_kib.to_be_bytes());
+    output.extend_from_slice(&params.iterations.to_be_bytes());
+    output.extend_from_slice(&params.parallelism.to_be_bytes());
+    output.extend_from_slice(&salt);
+    output.extend_from_slice(&nonce);
+    output.extend_from_slice(&public_key.serialize());
+
+    let cipher = cipher(&argon2, password, &salt)?;
+    let mut plaintext = Zeroizing::new(Vec::from(server.serialize().as_slice()));
+    let tag = cipher
+        .encrypt_in_place_detached(&nonce, &output, &mut plaintext)
+        .map_err(|_| Error::Unseal)?;
+    output.extend_from_slice(&plaintext);
+    output.extend_from_slice(&tag);
+
+    Ok(output)
+}
+
+fn unseal<CS: CipherSuite, M: ProtocolMode, S: Serializable>(
+    input: &[u8],
+    password: &[u8],
+    public_key: impl FnOnce(&S) -> PublicKey<CS>,
+) -> Result<S> {
+    let metadata = SealedKeyMetadata::from_sealed_key(input)?;
+    metadata.check_suite::<CS>()?;
+
+    if metadata.mode != M::MODE {
+        return Err(Error::deserialization("mode", DeserializationReason::Mode));
+    }
+
+    let params = metadata.params;
+
+    if params.memory_kib > SealParams::MAX.memory_kib
+        || params.iterations > SealParams::MAX.iterations
+        || params.parallelism > SealParams::MAX.parallelism
+    {
+        return Err(Error::SealParams);
+    }
+
+    let expected_public_key = metadata.public_key::<CS>()?;
+    let mut rest = metadata.rest;
+    let salt = take_field(&mut rest, SALT_LEN, "salt")?;
+    let nonce = take_field(&mut rest, NONCE_LEN, "nonce")?;
+    let nonce = Nonce::from(<[u8; NONCE_LEN]>::try_from(nonce).unwrap());
+    take_field(&mut rest, PublicKeyLen::<CS>::USIZE, "pk")?;
+    let associated_data = &input[..input.len() - rest.len()];
+
+    let ciphertext_len = S::Len::USIZE + TAG_LEN;
+
+    if rest.len() != ciphertext_len {
+        return Err(Error::deserialization(
+            "ciphertext",
+            DeserializationReason::Length {
+                expected: ciphertext_len,
+                actual: rest.len(),
+            },
+        ));
+    }
+
+    let (ciphertext, tag) = rest.split_at(S::Len::USIZE);
+    let cipher = cipher(&params.to_argon2()?, password, salt)?;
+    let mut plaintext = Zeroizing::new(Vec::from(ciphertext));
+    cipher
+        .decrypt_in_place_detached(
+            &nonce,
+            associated_data,
+            &mut plaintext,
+            &Tag::from(<[u8; TAG_LEN]>::try_from(tag).unwrap()),
+        )
+        .map_err(|_| Error::Unseal)?;
+
+    let (server, _) = S::deserialize(&plaintext)?;
+
+    // Authenticated by the AEAD, but only a correct writer guarantees that it
+    // belongs to the key.
+    if public_key(&server) != expected_public_key {
+        return Err(Error::Unseal);
+    }
+
+    Ok(server)
+}
+
+fn cipher(argon2: &Argon2<'_>, password: &[u8], salt: &[u8]) -> Result<ChaCha20Poly1305> {
+    let mut key = Zeroizing::new(Key::default());
+    argon2
+        .hash_password_into(password, salt, &mut key)
+        .map_err(|_| Error::SealParams)?;
+
+    Ok(ChaCha20Poly1305::new(&key))
+}
+
+#[cfg(test)]
+mod tests {
+    use core::fmt::Debug;
+
+    use rand::rngs::OsRng;
+
+    use super::*;
+    use crate::{DeserializationError, Group};
+
+    /// Weak parameters to keep the tests fast.
+    const PARAMS: SealParams = SealParams {
+        memory_kib: 8,
+        iterations: 1,
+        parallelism: 1,
+    };
+
+    fn seal<CS: CipherSuite>()
+    where
+        <CS::Group as Group>::Elem: Debug,
+    {
+        let mut rng = OsRng;
+
+        let server = OprfServer::<CS>::new(&mut rng).unwrap();
+        let sealed = server.seal(b"password", PARAMS, &mut rng).unwrap();
+        let metadata = SealedKeyMetadata::from_sealed_key(&sealed).unwrap();
+        assert_eq!(metadata.suite, CS::ID.as_bytes());
+        assert_eq!(metadata.mode, Mode::Oprf);
+        assert_eq!(metadata.params, PARAMS);
+        assert_eq!(
+            metadata.public_key::<CS>().unwrap(),
+            server.get_private_key().public_key()
+        );
+        let unsealed = OprfServer::<CS>::unseal(&sealed, b"password").unwrap();
+        assert_eq!(unsealed.serialize(), server.serialize());
+
+        let server = VoprfServer::<CS>::new(&mut rng).unwrap();
+        let sealed = server.seal(b"password", PARAMS, &mut rng).unwrap();
+        let metadata = SealedKeyMetadata::from_sealed_key(&sealed).unwrap();
+        assert_eq!(
+            metadata.public_key::<CS>().unwrap(),
+            server.get_public_key()
+        );
+        let unsealed = VoprfServer::<CS>::unseal(&sealed, b"password").unwrap();
+        assert_eq!(unsealed.serialize(), server.serialize());
+        assert_eq!(
+            PoprfServer::<CS>::unseal(&sealed, b"password").unwrap_err(),
+            Error::Deserialization(DeserializationError {
+                field: "mode",
+                reason: DeserializationReason::Mode,
+            })
+        );
+
+        let server = PoprfServer::<CS>::new(&mut rng).unwrap();
+        let sealed = server.seal(b"password", PARAMS, &mut rng).unwrap();
+        let unsealed = PoprfServer::<CS>::unseal(&sealed, b"password").unwrap();
+        assert_eq!(unsealed.serialize(), server.serialize());
+
+        // Sealing twice uses a different salt and nonce.
+        assert_ne!(server.seal(b"password", PARAMS, &mut rng).unwrap(), sealed);
+
+        assert_eq!(
+            PoprfServer::<CS>::unseal(&sealed, b"wrong password").unwrap_err(),
+            Error::Unseal
+        );
+
+        // Every modified byte is detected.
+        for index in 0..sealed.len() {
+            let mut bad = sealed.clone();
+            bad[index] ^= 1;
+            assert!(PoprfServer::<CS>::unseal(&bad, b"password").is_err());
+        }
+
+        assert!(PoprfServer::<CS>::unseal(&sealed[..sealed.len() - 1], b"password").is_err());
+        let mut bad = sealed.clone();
+        bad.push(0);
+        assert!(PoprfServer::<CS>::unseal(&bad, b"password").is_err());
+    }
+
+    #[test]
+    fn test_functionality() {
+        use p256::NistP256;
+        use p384::NistP384;
+        use p521::NistP521;
+
+        #[cfg(feature = "ristretto255")]
+        {
+            use crate::Ristretto255;
+
+            seal::<Ristretto255>();
+        }
+
+        seal::<NistP256>();
+        seal::<NistP384>();
+        seal::<NistP521>();
+    }
+
+    #[test]
+    fn test_params() {
+        use p256::NistP256;
+        use p384::NistP384;
+
+        let mut rng = OsRng;
+        let server = VoprfServer::<NistP256>::new(&mut rng).unwrap();
+
+        let sealed = server
+            .seal(b"password", SealParams::default(), &mut rng)
+            .unwrap();
+        let unsealed = VoprfServer::<NistP256>::unseal(&sealed, b"password").unwrap();
+        assert_eq!(unsealed.serialize(), server.serialize());
+
+        assert_eq!(
+            VoprfServer::<NistP384>::unseal(&sealed, b"password").unwrap_err(),
+            Error::Deserialization(DeserializationError {
+                field: "suite",
+                reason: DeserializationReason::Suite,
+            })
+        );
+
+        let invalid = SealParams {
+            memory_kib: 0,
+            ..PARAMS
+        };
+        assert_eq!(
+            server.seal(b"password", invalid, &mut rng).unwrap_err(),
+            Error::SealParams
+        );
+
+        // Parameters above the maximum aren't evaluated.
+        let too_large = SealParams {
+            iterations: SealParams::MAX.iterations + 1,
+            ..PARAMS
+        };
+        let sealed = server.seal(b"password", too_large, &mut rng).unwrap();
+        assert_eq!(
+            VoprfServer::<NistP256>::unseal(&sealed, b"password").unwrap_err(),
+            Error::SealParams
+        );
+    }
+}
@@ -9,13 +9,28 @@
 //! Handles the serialization of each of the components used in the VOPRF
 //! protocol
 
+#[cfg(feature = "alloc")]
+use alloc::vec;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::fmt::{self, Display, Formatter};
+use core::marker::PhantomData;
+use core::ops::Add;
+use core::str::FromStr;
+
+use base16ct::HexDisplay;
+use digest::{Output, OutputSizeUser};
 use generic_array::sequence::Concat;
 use generic_array::typenum::{Sum, Unsigned};
-use generic_array::GenericArray;
+use generic_array::{ArrayLength, GenericArray};
+use zeroize::Zeroize;
 
+#[cfg(feature = "alloc")]
+use crate::BatchIndexMap;
 use crate::{
-    BlindedElement, CipherSuite, Error, EvaluationElement, Group, OprfClient, OprfServer,
-    PoprfClient, PoprfServer, Proof, Result, VoprfClient, VoprfServer,
+    BlindedElement, CipherSuite, DeserializationReason, Error, EvaluationElement, Group,
+    OprfClient, OprfOutput, OprfServer, PoprfClient, PoprfServer, PrivateKey, Proof, ProtocolMode,
+    PublicKey, Result, VerifiedEvaluation, VoprfClient, VoprfServer,
 };
 
 //////////////////////////////////////////////////////////
@@ -37,7 +52,7 @@ impl<CS: CipherSuite> OprfClient<CS> {
     /// # Errors
     /// [`Error::Deserialization`] if failed to deserialize `input`.
     pub fn deserialize(mut input: &[u8]) -> Result<Self> {
-        let blind = deserialize_scalar::<CS::Group>(&mut input)?;
+        let blind = deserialize_scalar::<CS::Group>(&mut input, "blind")?;
 
         Ok(Self { blind })
     }
@@ -61,8 +76,8 @@ impl<CS: CipherSuite> VoprfClient<CS> {
     /// # Errors
     /// [`Error::Deserialization`] if failed to deserialize `input`.
     pub fn deserialize(mut input: &[u8]) -> Result<Self> {
-        let blind = deserialize_scalar::<CS::Group>(&mut input)?;
-        let blinded_element = deserialize_elem::<CS::Group>(&mut input)?;
+        let blind = deserialize_scalar::<CS::Group>(&mut input, "blind")?;
+        let blinded_element = deserialize_elem::<CS::Group>(&mut input, "blinded_element")?;
 
         Ok(Self {
             blind,
@@ -71,6 +86,62 @@ impl<CS: CipherSuite> VoprfClient<CS> {
     }
 }
 
+/// Length of [`VerifiedEvaluation`] in bytes for serialization.
+pub type VerifiedEvaluationLen<CS> = Sum<
+    Sum<Sum<VoprfClientLen<CS>, <<CS as CipherSuite>::Group as Group>::ElemLen>, ProofLen<CS>>,
+    <<CS as CipherSuite>::Group as Group>::ElemLen,
+>;
+
+impl<CS: CipherSuite> VerifiedEvaluation<CS> {
+    /// Serialization into bytes
+    pub fn serialize(&self) -> GenericArray<u8, VerifiedEvaluationLen<CS>>
+    where
+        VoprfClientLen<CS>: Add<<CS::Group as Group>::ElemLen>,
+        Sum<VoprfClientLen<CS>, <CS::Group as Group>::ElemLen>: ArrayLength + Add<ProofLen<CS>>,
+        Sum<Sum<VoprfClientLen<CS>, <CS::Group as Group>::ElemLen>, ProofLen<CS>>:
+            ArrayLength + Add<<CS::Group as Group>::ElemLen>,
+        VerifiedEvaluationLen<CS>: ArrayLength,
+    {
+        CS::Group::serialize_scalar(self.blind)
+            .concat(CS::Group::serialize_elem(self.blinded_element))
+            .concat(CS::Group::serialize_elem(self.evaluation_element))
+            .concat(self.proof.serialize())
+            .concat(CS::Group::serialize_elem(self.pk))
+    }
+
+    /// Deserialization from bytes
+    ///
+    /// # Caution
+    ///
+    /// The proof is not verified again, only deserialize bytes produced by
+    /// [`serialize`](Self::serialize) from trusted storage! Use
+    /// [`deserialize_and_verify`](Self::deserialize_and_verify) otherwise.
+    ///
+    /// # Errors
+    /// [`Error::Deserialization`] if failed to deserialize `input`.
+    #[cfg(feature = "danger")]
+    pub fn deserialize(input: &[u8]) -> Result<Self> {
+        Self::deserialize_unchecked(input)
+    }
+
+    pub(crate) fn deserialize_unchecked(mut input: &[u8]) -> Result<Self> {
+        let blind = deserialize_scalar::<CS::Group>(&mut input, "blind")?;
+        let blinded_element = deserialize_elem::<CS::Group>(&mut input, "blinded_element")?;
+        let evaluation_element = deserialize_elem::<CS::Group>(&mut input, "evaluation_element")?;
+        let c_scalar = deserialize_scalar::<CS::Group>(&mut input, "c_scalar")?;
+        let s_scalar = deserialize_scalar::<CS::Group>(&mut input, "s_scalar")?;
+        let pk = deserialize_elem::<CS::Group>(&mut input, "pk")?;
+
+        Ok(Self {
+            blind,
+            blinded_element,
+            evaluation_element,
+            proof: Proof { c_scalar, s_scalar },
+            pk,
+        })
+    }
+}
+
 /// Length of [`PoprfClient`] in bytes for serialization.
 pub type PoprfClientLen<CS> = Sum<
     <<CS as CipherSuite>::Group as Group>::ScalarLen,
@@ -89,8 +160,8 @@ impl<CS: CipherSuite> PoprfClient<CS> {
     /// # Errors
     /// [`Error::Deserialization`] if failed to deserialize `input`.
     pub fn deserialize(mut input: &[u8]) -> Result<Self> {
-        let blind = deserialize_scalar::<CS::Group>(&mut input)?;
-        let blinded_element = deserialize_elem::<CS::Group>(&mut input)?;
+        let blind = deserialize_scalar::<CS::Group>(&mut input, "blind")?;
+        let blinded_element = deserialize_elem::<CS::Group>(&mut input, "blinded_element")?;
 
         Ok(Self {
             blind,
@@ -113,7 +184,7 @@ impl<CS: CipherSuite> OprfServer<CS> {
     /// # Errors
     /// [`Error::Deserialization`] if failed to deserialize `input`.
     pub fn deserialize(mut input: &[u8]) -> Result<Self> {
-        let sk = deserialize_scalar::<CS::Group>(&mut input)?;
+        let sk = deserialize_scalar::<CS::Group>(&mut input, "sk")?;
 
         Ok(Self { sk })
     }
@@ -136,8 +207,8 @@ impl<CS: CipherSuite> VoprfServer<CS> {
     /// # Errors
     /// [`Error::Deserialization`] if failed to deserialize `input`.
     pub fn deserialize(mut input: &[u8]) -> Result<Self> {
-        let sk = deserialize_scalar::<CS::Group>(&mut input)?;
-        let pk = deserialize_elem::<CS::Group>(&mut input)?;
+        let sk = deserialize_scalar::<CS::Group>(&mut input, "sk")?;
+        let pk = deserialize_elem::<CS::Group>(&mut input, "pk")?;
 
         Ok(Self { sk, pk })
     }
@@ -160,8 +231,8 @@ impl<CS: CipherSuite> PoprfServer<CS> {
     /// # Errors
     /// [`Error::Deserialization`] if failed to deserialize `input`.
     pub fn deserialize(mut input: &[u8]) -> Result<Self> {
-        let sk = deserialize_scalar::<CS::Group>(&mut input)?;
-        let pk = deserialize_elem::<CS::Group>(&mut input)?;
+        let sk = deserialize_scalar::<CS::Group>(&mut input, "sk")?;
+        let pk = deserialize_elem::<CS::Group>(&mut input, "pk")?;
 
         Ok(Self { sk, pk })
     }
@@ -185,8 +256,8 @@ impl<CS: CipherSuite> Proof<CS> {
     /// # Errors
     /// [`Error::Deserialization`] if failed to deserialize `input`.
     pub fn deserialize(mut input: &[u8]) -> Result<Self> {
-        let c_scalar = deserialize_scalar::<CS::Group>(&mut input)?;
-        let s_scalar = deserialize_scalar::<CS::Group>(&mut input)?;
+        let c_scalar = deserialize_scalar::<CS::Group>(&mut input, "c_scalar")?;
+        let s_scalar = deserialize_scalar::<CS::Group>(&mut input, "s_scalar")?;
 
         Ok(Proof { c_scalar, s_scalar })
     }
@@ -195,7 +266,7 @@ impl<CS: CipherSuite> Proof<CS> {
 /// Length of [`BlindedElement`] in bytes for serialization.
 pub type BlindedElementLen<CS> = <<CS as CipherSuite>::Group as Group>::ElemLen;
 
-impl<CS: CipherSuite> BlindedElement<CS> {
+impl<CS: CipherSuite, M: ProtocolMode> BlindedElement<CS, M> {
     /// Serialization into bytes
     pub fn serialize(&self) -> GenericArray<u8, BlindedElementLen<CS>> {
         CS::Group::serialize_elem(self.0)
@@ -206,16 +277,16 @@ impl<CS: CipherSuite> BlindedElement<CS> {
     /// # Errors
     /// [`Error::Deserialization`] if failed to deserialize `input`.
     pub fn deserialize(mut input: &[u8]) -> Result<Self> {
-        let value = deserialize_elem::<CS::Group>(&mut input)?;
+        let value = deserialize_elem::<CS::Group>(&mut input, "blinded_element")?;
 
-        Ok(Self(value))
+        Ok(Self(value, PhantomData))
     }
 }
 
 /// Length of [`EvaluationElement`] in bytes for serialization.
 pub type EvaluationElementLen<CS> = <<CS as CipherSuite>::Group as Group>::ElemLen;
 
-impl<CS: CipherSuite> EvaluationElement<CS> {
+impl<CS: CipherSuite, M: ProtocolMode> EvaluationElement<CS, M> {
     /// Serialization into bytes
     pub fn serialize(&self) -> GenericArray<u8, EvaluationElementLen<CS>> {
         CS::Group::serialize_elem(self.0)
@@ -226,24 +297,466 @@ impl<CS: CipherSuite> EvaluationElement<CS> {
     /// # Errors
     /// [`Error::Deserialization`] if failed to deserialize `input`.
     pub fn deserialize(mut input: &[u8]) -> Result<Self> {
-        let value = deserialize_elem::<CS::Group>(&mut input)?;
+        let value = deserialize_elem::<CS::Group>(&mut input, "evaluation_element")?;
+
+        Ok(Self(value, PhantomData))
+    }
+}
+
+/// Length of [`PublicKey`] in bytes for serialization.
+pub type PublicKeyLen<CS> = <<CS as CipherSuite>::Group as Group>::ElemLen;
+
+impl<CS: CipherSuite> PublicKey<CS> {
+    /// Serialization into bytes
+    pub fn serialize(&self) -> GenericArray<u8, PublicKeyLen<CS>> {
+        CS::Group::serialize_elem(self.0)
+    }
+
+    /// Deserialization from bytes
+    ///
+    /// # Errors
+    /// [`Error::Deserialization`] if failed to deserialize `input`.
+    pub fn deserialize(mut input: &[u8]) -> Result<Self> {
+        let value = deserialize_elem::<CS::Group>(&mut input, "pk")?;
+
+        Ok(Self(value))
+    }
+}
+
+/// Length of [`PrivateKey`] in bytes for serialization.
+pub type PrivateKeyLen<CS> = <<CS as CipherSuite>::Group as Group>::ScalarLen;
+
+impl<CS: CipherSuite> PrivateKey<CS> {
+    /// Serialization into bytes
+    pub fn serialize(&self) -> GenericArray<u8, PrivateKeyLen<CS>> {
+        CS::Group::serialize_scalar(self.0)
+    }
+
+    /// Deserialization from bytes
+    ///
+    /// # Errors
+    /// [`Error::Deserialization`] if failed to deserialize `input`.
+    pub fn deserialize(mut input: &[u8]) -> Result<Self> {
+        let value = deserialize_scalar::<CS::Group>(&mut input, "sk")?;
 
         Ok(Self(value))
     }
 }
 
-fn deserialize_elem<G: Group>(input: &mut &[u8]) -> Result<G::Elem> {
-    let input = input
-        .take_ext(G::ElemLen::USIZE)
-        .ok_or(Error::Deserialization)?;
-    G::deserialize_elem(input)
+/// Length of [`OprfOutput`] in bytes for serialization.
+pub type OprfOutputLen<CS> = <<CS as CipherSuite>::Hash as OutputSizeUser>::OutputSize;
+
+impl<CS: CipherSuite> OprfOutput<CS> {
+    /// Serialization into bytes
+    pub fn serialize(&self) -> GenericArray<u8, OprfOutputLen<CS>> {
+        GenericArray::from_slice(&self.0).clone()
+    }
+
+    /// Deserialization from bytes
+    ///
+    /// # Errors
+    /// [`Error::Deserialization`] if failed to deserialize `input`.
+    pub fn deserialize(mut input: &[u8]) -> Result<Self> {
+        let bytes = take_field(&mut input, OprfOutputLen::<CS>::USIZE, "output")?;
+        let mut output = Output::<CS::Hash>::default();
+        output.copy_from_slice(bytes);
+
+        Ok(Self(output))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl BatchIndexMap {
+    /// Serialization into bytes, one bit per element of the batch
+    pub fn serialize(&self) -> Vec<u8> {
+        let mut output = vec![0; self.0.len().div_ceil(8)];
+
+        for index in self.accepted() {
+            output[index / 8] |= 1 << (index % 8);
+        }
+
+        output
+    }
+
+    /// Deserialization from bytes for a batch of `len` elements
+    ///
+    /// # Errors
+    /// [`Error::Deserialization`] if failed to deserialize `input`.
+    pub fn deserialize(input: &[u8], len: usize) -> Result<Self> {
+        if input.len() != len.div_ceil(8) {
+            return Err(Error::deserialization(
+                "index_map",
+                DeserializationReason::Length {
+                    expected: len.div_ceil(8),
+                    actual: input.len(),
+                },
+            ));
+        }
+
+        let index_map: Self = (0..len)
+            .map(|index| input[index / 8] & (1 << (index % 8)) != 0)
+            .collect();
+
+        // Unused bits have to be zero.
+        if index_map.serialize() != input {
+            return Err(Error::deserialization(
+                "index_map",
+                DeserializationReason::Encoding,
+            ));
+        }
+
+        Ok(index_map)
+    }
+}
+
+/////////////////////////
+// Serializable Trait //
+// ================== //
+/////////////////////////
+
+/// Common serialization of all protocol types, allowing transport code to be
+/// written generically. Implemented for every client, server and message type.
+///
+/// The inherent `serialize` and `deserialize` methods of each type take
+/// precedence over the ones of this trait, so call them through the trait,
+/// e.g. `<T as Serializable>::deserialize`, when both are in scope.
+pub trait Serializable: Sized {
+    /// Length in bytes of the serialized form.
+    type Len: ArrayLength;
+
+    /// Serialization into bytes
+    fn serialize(&self) -> GenericArray<u8, Self::Len>;
+
+    /// Serialization into the front of `output` without allocating. Returns the
+    /// rest of `output`.
+    ///
+    /// # Errors
+    /// [`Error::BufferLength`] if `output` is shorter then [`Self::Len`].
+    fn serialize_into<'a>(&self, output: &'a mut [u8]) -> Result<&'a mut [u8]> {
+        let len = Self::Len::USIZE;
+
+        if output.len() < len {
+            return Err(Error::BufferLength {
+                expected: len,
+                actual: output.len(),
+            });
+        }
+
+        let (front, back) = output.split_at_mut(len);
+        let mut bytes = Serializable::serialize(self);
+        front.copy_from_slice(&bytes);
+        bytes.as_mut_slice().zeroize();
+
+        Ok(back)
+    }
+
+    /// Deserialization from the front of `input`. Returns the rest of `input`
+    /// together with the value.
+    ///
+    /// # Errors
+    /// [`Error::Deserialization`] if failed to deserialize `input`.
+    fn deserialize(input: &[u8]) -> Result<(Self, &[u8])>;
+}
+
+impl<CS: CipherSuite> Serializable for OprfClient<CS> {
+    type Len = OprfClientLen<CS>;
+
+    fn serialize(&self) -> GenericArray<u8, Self::Len> {
+        self.serialize()
+    }
+
+    fn deserialize(input: &[u8]) -> Result<(Self, &[u8])> {
+        split_off(input, Self::deserialize)
+    }
+}
+
+impl<CS: CipherSuite> Serializable for VoprfClient<CS> {
+    type Len = VoprfClientLen<CS>;
+
+    fn serialize(&self) -> GenericArray<u8, Self::Len> {
+        self.serialize()
+    }
+
+    fn deserialize(input: &[u8]) -> Result<(Self, &[u8])> {
+        split_off(input, Self::deserialize)
+    }
+}
+
+impl<CS: CipherSuite> Serializable for PoprfClient<CS> {
+    type Len = PoprfClientLen<CS>;
+
+    fn serialize(&self) -> GenericArray<u8, Self::Len> {
+        self.serialize()
+    }
+
+    fn deserialize(input: &[u8]) -> Result<(Self, &[u8])> {
+        split_off(input, Self::deserialize)
+    }
+}
+
+#[cfg(feature = "danger")]
+impl<CS: CipherSuite> Serializable for VerifiedEvaluation<CS>
+where
+    VoprfClientLen<CS>: Add<<CS::Group as Group>::ElemLen>,
+    Sum<VoprfClientLen<CS>, <CS::Group as Group>::ElemLen>: ArrayLength + Add<ProofLen<CS>>,
+    Sum<Sum<VoprfClientLen<CS>, <CS::Group as Group>::ElemLen>, ProofLen<CS>>:
+        ArrayLength + Add<<CS::Group as Group>::ElemLen>,
+    VerifiedEvaluationLen<CS>: ArrayLength,
+{
+    type Len = VerifiedEvaluationLen<CS>;
+
+    fn serialize(&self) -> GenericArray<u8, Self::Len> {
+        self.serialize()
+    }
+
+    fn deserialize(input: &[u8]) -> Result<(Self, &[u8])> {
+        split_off(input, Self::deserialize)
+    }
+}
+
+impl<CS: CipherSuite> Serializable for OprfServer<CS> {
+    type Len = OprfServerLen<CS>;
+
+    fn serialize(&self) -> GenericArray<u8, Self::Len> {
+        self.serialize()
+    }
+
+    fn deserialize(input: &[u8]) -> Result<(Self, &[u8])> {
+        split_off(input, Self::deserialize)
+    }
+}
+
+impl<CS: CipherSuite> Serializable for VoprfServer<CS> {
+    type Len = VoprfServerLen<CS>;
+
+    fn serialize(&self) -> GenericArray<u8, Self::Len> {
+        self.serialize()
+    }
+
+    fn deserialize(input: &[u8]) -> Result<(Self, &[u8])> {
+        split_off(input, Self::deserialize)
+    }
+}
+
+impl<CS: CipherSuite> Serializable for PoprfServer<CS> {
+    type Len = PoprfServerLen<CS>;
+
+    fn serialize(&self) -> GenericArray<u8, Self::Len> {
+        self.serialize()
+    }
+
+    fn deserialize(input: &[u8]) -> Result<(Self, &[u8])> {
+        split_off(input, Self::deserialize)
+    }
+}
+
+impl<CS: CipherSuite> Serializable for Proof<CS> {
+    type Len = ProofLen<CS>;
+
+    fn serialize(&self) -> GenericArray<u8, Self::Len> {
+        self.serialize()
+    }
+
+    fn deserialize(input: &[u8]) -> Result<(Self, &[u8])> {
+        split_off(input, Self::deserialize)
+    }
+}
+
+impl<CS: CipherSuite, M: ProtocolMode> Serializable for BlindedElement<CS, M> {
+    type Len = BlindedElementLen<CS>;
+
+    fn serialize(&self) -> GenericArray<u8, Self::Len> {
+        self.serialize()
+    }
+
+    fn deserialize(input: &[u8]) -> Result<(Self, &[u8])> {
+        split_off(input, Self::deserialize)
+    }
+}
+
+impl<CS: CipherSuite, M: ProtocolMode> Serializable for EvaluationElement<CS, M> {
+    type Len = EvaluationElementLen<CS>;
+
+    fn serialize(&self) -> GenericArray<u8, Self::Len> {
+        self.serialize()
+    }
+
+    fn deserialize(input: &[u8]) -> Result<(Self, &[u8])> {
+        split_off(input, Self::deserialize)
+    }
 }
 
-fn deserialize_scalar<G: Group>(input: &mut &[u8]) -> Result<G::Scalar> {
-    let input = input
-        .take_ext(G::ScalarLen::USIZE)
-        .ok_or(Error::Deserialization)?;
-    G::deserialize_scalar(input)
+impl<CS: CipherSuite> Serializable for PublicKey<CS> {
+    type Len = PublicKeyLen<CS>;
+
+    fn serialize(&self) -> GenericArray<u8, Self::Len> {
+        self.serialize()
+    }
+
+    fn deserialize(input: &[u8]) -> Result<(Self, &[u8])> {
+        split_off(input, Self::deserialize)
+    }
+}
+
+impl<CS: CipherSuite> Serializable for PrivateKey<CS> {
+    type Len = PrivateKeyLen<CS>;
+
+    fn serialize(&self) -> GenericArray<u8, Self::Len> {
+        self.serialize()
+    }
+
+    fn deserialize(input: &[u8]) -> Result<(Self, &[u8])> {
+        split_off(input, Self::deserialize)
+    }
+}
+
+impl<CS: CipherSuite> Serializable for OprfOutput<CS> {
+    type Len = OprfOutputLen<CS>;
+
+    fn serialize(&self) -> GenericArray<u8, Self::Len> {
+        self.serialize()
+    }
+
+    fn deserialize(input: &[u8]) -> Result<(Self, &[u8])> {
+        split_off(input, Self::deserialize)
+    }
+}
+
+/// Deserializes a fixed-length `T` from the front of `input` with its inherent
+/// `deserialize` and returns the rest of `input`.
+pub(crate) fn split_off<T: Serializable>(
+    input: &[u8],
+    deserialize: fn(&[u8]) -> Result<T>,
+) -> Result<(T, &[u8])> {
+    let value = deserialize(input)?;
+
+    Ok((value, &input[T::Len::USIZE..]))
+}
+
+//////////////////
+// Hex Encoding //
+// ============ //
+//////////////////
+
+impl<CS: CipherSuite, M: ProtocolMode> Display for BlindedElement<CS, M> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{:x}", HexDisplay(&self.serialize()))
+    }
+}
+
+impl<CS: CipherSuite, M: ProtocolMode> FromStr for BlindedElement<CS, M> {
+    type Err = Error;
+
+    fn from_str(input: &str) -> Result<Self> {
+        Self::deserialize(&decode_hex::<BlindedElementLen<CS>>(
+            input,
+            "blinded_element",
+        )?)
+    }
+}
+
+impl<CS: CipherSuite, M: ProtocolMode> Display for EvaluationElement<CS, M> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{:x}", HexDisplay(&self.serialize()))
+    }
+}
+
+impl<CS: CipherSuite, M: ProtocolMode> FromStr for EvaluationElement<CS, M> {
+    type Err = Error;
+
+    fn from_str(input: &str) -> Result<Self> {
+        Self::deserialize(&decode_hex::<EvaluationElementLen<CS>>(
+            input,
+            "evaluation_element",
+        )?)
+    }
+}
+
+impl<CS: CipherSuite> Display for Proof<CS> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{:x}", HexDisplay(&self.serialize()))
+    }
+}
+
+impl<CS: CipherSuite> FromStr for Proof<CS> {
+    type Err = Error;
+
+    fn from_str(input: &str) -> Result<Self> {
+        Self::deserialize(&decode_hex::<ProofLen<CS>>(input, "proof")?)
+    }
+}
+
+impl<CS: CipherSuite> Display for PublicKey<CS> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{:x}", HexDisplay(&self.serialize()))
+    }
+}
+
+impl<CS: CipherSuite> FromStr for PublicKey<CS> {
+    type Err = Error;
+
+    fn from_str(input: &str) -> Result<Self> {
+        Self::deserialize(&decode_hex::<PublicKeyLen<CS>>(input, "pk")?)
+    }
+}
+
+/// Decodes `L` bytes of `field` from upper- or lowercase hex.
+fn decode_hex<L: ArrayLength>(input: &str, field: &'static str) -> Result<GenericArray<u8, L>> {
+    if input.len() % 2 != 0 {
+        return Err(Error::deserialization(
+            field,
+            DeserializationReason::Encoding,
+        ));
+    }
+
+    if input.len() / 2 != L::USIZE {
+        return Err(Error::deserialization(
+            field,
+            DeserializationReason::Length {
+                expected: L::USIZE,
+                actual: input.len() / 2,
+            },
+        ));
+    }
+
+    let mut output = GenericArray::default();
+    base16ct::mixed::decode(input, &mut output)
+        .map_err(|_| Error::deserialization(field, DeserializationReason::Encoding))?;
+
+    Ok(output)
+}
+
+pub(crate) fn deserialize_elem<G: Group>(
+    input: &mut &[u8],
+    field: &'static str,
+) -> Result<G::Elem> {
+    let input = take_field(input, G::ElemLen::USIZE, field)?;
+    G::deserialize_elem(input).map_err(|error| error.field(field))
+}
+
+pub(crate) fn deserialize_scalar<G: Group>(
+    input: &mut &[u8],
+    field: &'static str,
+) -> Result<G::Scalar> {
+    let input = take_field(input, G::ScalarLen::USIZE, field)?;
+    G::deserialize_scalar(input).map_err(|error| error.field(field))
+}
+
+/// Takes the next `len` bytes of `field` from `input`.
+pub(crate) fn take_field<'a>(
+    input: &mut &'a [u8],
+    len: usize,
+    field: &'static str,
+) -> Result<&'a [u8]> {
+    let actual = input.len();
+
+    input.take_ext(len).ok_or(Error::deserialization(
+        field,
+        DeserializationReason::Length {
+            expected: len,
+            actual,
+        },
+    ))
 }
 
 trait SliceExt {
@@ -262,17 +775,113 @@ impl<T> SliceExt for [T] {
     }
 }
 
+/// Constant-time Base64 encoding, see RFC 4648.
+#[cfg(any(feature = "jwk", feature = "pkcs8"))]
+pub(crate) mod base64 {
+    use alloc::string::String;
+    use alloc::vec;
+    use alloc::vec::Vec;
+
+    use base64ct::{Base64, Base64UrlUnpadded, Encoding};
+    use zeroize::Zeroizing;
+
+    /// The Base64 alphabets.
+    #[derive(Clone, Copy, Eq, PartialEq)]
+    pub(crate) enum Alphabet {
+        /// The standard alphabet with padding, used by PEM.
+        #[cfg_attr(not(feature = "pkcs8"), allow(dead_code))]
+        Standard,
+        /// The URL and filename safe alphabet without padding, used by JWK.
+        #[cfg_attr(not(feature = "jwk"), allow(dead_code))]
+        Url,
+    }
+
+    pub(crate) fn encode(input: &[u8], alphabet: Alphabet) -> String {
+        match alphabet {
+            Alphabet::Standard => Base64::encode_string(input),
+            Alphabet::Url => Base64UrlUnpadded::encode_string(input),
+        }
+    }
+
+    /// Returns [`None`] if `input` isn't canonically encoded.
+    pub(crate) fn decode(input: &str, alphabet: Alphabet) -> Option<Zeroizing<Vec<u8>>> {
+        // Large enough for any valid `input`, so the output is never
+        // reallocated.
+        let mut output = Zeroizing::new(vec![0; input.len() / 4 * 3 + 2]);
+
+        let len = match alphabet {
+            Alphabet::Standard => Base64::decode(input, &mut output),
+            Alphabet::Url => Base64UrlUnpadded::decode(input, &mut output),
+        }
+        .ok()?
+        .len();
+        output.truncate(len);
+
+        Some(output)
+    }
+}
+
 #[cfg(feature = "serde")]
 pub(crate) mod serde {
+    use core::fmt::{self, Formatter};
     use core::marker::PhantomData;
 
-    use generic_array::GenericArray;
-    use serde::de::{Deserializer, Error};
+    use base16ct::HexDisplay;
+    use digest::{Output, OutputSizeUser};
+    use generic_array::{ArrayLength, GenericArray};
+    use serde::de::{Deserializer, Error, Unexpected, Visitor};
     use serde::ser::Serializer;
     use serde::{Deserialize, Serialize};
 
     use crate::Group;
 
+    /// Serializes `bytes` as a lowercase hex string for human-readable formats
+    /// and as raw bytes otherwise.
+    fn serialize_bytes<S, L>(bytes: &GenericArray<u8, L>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+        L: ArrayLength,
+    {
+        if serializer.is_human_readable() {
+            serializer.collect_str(&format_args!("{:x}", HexDisplay(bytes)))
+        } else {
+            bytes.serialize(serializer)
+        }
+    }
+
+    /// Inverse of [`serialize_bytes()`], accepts upper- and lowercase hex.
+    fn deserialize_bytes<'de, D, L>(deserializer: D) -> Result<GenericArray<u8, L>, D::Error>
+    where
+        D: Deserializer<'de>,
+        L: ArrayLength,
+    {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(HexVisitor(PhantomData))
+        } else {
+            GenericArray::deserialize(deserializer)
+        }
+    }
+
+    struct HexVisitor<L: ArrayLength>(PhantomData<L>);
+
+    impl<L: ArrayLength> Visitor<'_> for HexVisitor<L> {
+        type Value = GenericArray<u8, L>;
+
+        fn expecting(&self, formatter: &mut Formatter<'_>) -> fmt::Result {
+            write!(formatter, "a hex string of {} bytes", L::USIZE)
+        }
+
+        fn visit_str<E: Error>(self, value: &str) -> Result<Self::Value, E> {
+            let mut bytes = GenericArray::default();
+
+            // Don't echo the value, it might be secret.
+            match base16ct::mixed::decode(value, &mut bytes) {
+                Ok(decoded) if decoded.len() == L::USIZE => Ok(bytes),
+                _ => Err(E::invalid_value(Unexpected::Other("string"), &self)),
+            }
+        }
+    }
+
     pub(crate) struct Element<G: Group>(PhantomData<G>);
 
     impl<'de, G: Group> Element<G> {
@@ -280,7 +889,7 @@ pub(crate) mod serde {
         where
             D: Deserializer<'de>,
         {
-            GenericArray::<_, G::ElemLen>::deserialize(deserializer)
+            deserialize_bytes::<_, G::ElemLen>(deserializer)
                 .and_then(|bytes| G::deserialize_elem(&bytes).map_err(D::Error::custom))
         }
 
@@ -288,7 +897,35 @@ pub(crate) mod serde {
         where
             S: Serializer,
         {
-            G::serialize_elem(*self_).serialize(serializer)
+            serialize_bytes(&G::serialize_elem(*self_), serializer)
+        }
+    }
+
+    pub(crate) struct Hash<H: OutputSizeUser>(PhantomData<H>);
+
+    impl<'de, H: OutputSizeUser> Hash<H>
+    where
+        H::OutputSize: ArrayLength,
+    {
+        pub(crate) fn deserialize<D>(deserializer: D) -> Result<Output<H>, D::Error>
+        where
+            D: Deserializer<'de>,
+        {
+            deserialize_bytes::<_, H::OutputSize>(deserializer).map(|bytes| {
+                let mut output = Output::<H>::default();
+                output.copy_from_slice(&bytes);
+                output
+            })
+        }
+
+        pub(crate) fn serialize<S>(self_: &Output<H>, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            serialize_bytes(
+                GenericArray::<_, H::OutputSize>::from_slice(self_),
+                serializer,
+            )
         }
     }
 
@@ -299,7 +936,7 @@ pub(crate) mod serde {
         where
             D: Deserializer<'de>,
         {
-            GenericArray::<_, G::ScalarLen>::deserialize(deserializer)
+            deserialize_bytes::<_, G::ScalarLen>(deserializer)
                 .and_then(|bytes| G::deserialize_scalar(&bytes).map_err(D::Error::custom))
         }
 
@@ -307,19 +944,27 @@ pub(crate) mod serde {
         where
             S: Serializer,
         {
-            G::serialize_scalar(*self_).serialize(serializer)
+            serialize_bytes(&G::serialize_scalar(*self_), serializer)
         }
     }
 }
 
 #[cfg(test)]
 mod test {
+    use ::alloc::string::ToString;
+    use core::fmt::Debug;
+
+    use generic_array::typenum::Unsigned;
     use proptest::collection::vec;
     use proptest::prelude::*;
+    use rand::rngs::OsRng;
 
+    use super::Serializable;
+    use crate::ecvrf::EcvrfP256Sha256Tai;
     use crate::{
-        BlindedElement, EvaluationElement, OprfClient, OprfServer, PoprfClient, PoprfServer, Proof,
-        VoprfClient, VoprfServer,
+        BlindedElement, CipherSuite, DeserializationError, DeserializationReason, EcvrfProver,
+        Error, EvaluationElement, Group, OprfClient, OprfMode, OprfOutput, OprfServer, PoprfClient,
+        PoprfServer, PrivateKey, Proof, PublicKey, VoprfClient, VoprfMode, VoprfServer,
     };
 
     macro_rules! test_deserialize {
@@ -333,9 +978,229 @@ mod test {
             let _ = $item::<p384::NistP384>::deserialize(&$bytes[..]);
             let _ = $item::<p521::NistP521>::deserialize(&$bytes[..]);
         };
+        ($item:ident, $mode:ty, $bytes:ident) => {
+            #[cfg(feature = "ristretto255")]
+            {
+                let _ = $item::<crate::Ristretto255, $mode>::deserialize(&$bytes[..]);
+            }
+
+            let _ = $item::<p256::NistP256, $mode>::deserialize(&$bytes[..]);
+            let _ = $item::<p384::NistP384, $mode>::deserialize(&$bytes[..]);
+            let _ = $item::<p521::NistP521, $mode>::deserialize(&$bytes[..]);
+        };
+    }
+
+    fn round_trip<T: Serializable>(value: &T) {
+        let len = T::Len::USIZE;
+        let mut buffer = [0xFF; 200];
+
+        let rest = value.serialize_into(&mut buffer).unwrap();
+        assert_eq!(rest.len(), buffer.len() - len);
+        let (deserialized, rest) = <T as Serializable>::deserialize(&buffer).unwrap();
+        assert!(rest.iter().all(|&byte| byte == 0xFF));
+        assert_eq!(
+            Serializable::serialize(&deserialized),
+            Serializable::serialize(value)
+        );
+
+        let result = value.serialize_into(&mut buffer[..len - 1]);
+        assert_eq!(
+            result.unwrap_err(),
+            Error::BufferLength {
+                expected: len,
+                actual: len - 1
+            }
+        );
+        assert!(<T as Serializable>::deserialize(&buffer[..len - 1]).is_err());
+    }
+
+    fn serializable<CS: CipherSuite>() {
+        let mut rng = OsRng;
+        let input = b"input";
+        let server = VoprfServer::<CS>::new(&mut rng).unwrap();
+        let client_blind_result = VoprfClient::<CS>::blind(input, &mut rng).unwrap();
+        let server_result = server.blind_evaluate(&mut rng, &client_blind_result.message);
+        let output = client_blind_result
+            .state
+            .finalize(
+                input,
+                &server_result.message,
+                &server_result.proof,
+                &server.get_public_key(),
+            )
+            .unwrap();
+
+        round_trip(&client_blind_result.state);
+        round_trip(&client_blind_result.message);
+        round_trip(&server_result.message);
+        round_trip(&server_result.proof);
+        round_trip(&server);
+        round_trip(&server.get_public_key());
+        round_trip(&server.get_private_key());
+        round_trip(&output);
+    }
+
+    #[test]
+    fn test_serializable() {
+        #[cfg(feature = "ristretto255")]
+        serializable::<crate::Ristretto255>();
+        serializable::<p256::NistP256>();
+        serializable::<p384::NistP384>();
+        serializable::<p521::NistP521>();
+
+        let prover = EcvrfProver::<EcvrfP256Sha256Tai>::new(&mut OsRng).unwrap();
+        round_trip(&prover.prove(b"alpha").unwrap());
+    }
+
+    fn hex_encoding<CS: CipherSuite>()
+    where
+        <CS::Group as Group>::Elem: Debug,
+    {
+        let mut rng = OsRng;
+        let server = VoprfServer::<CS>::new(&mut rng).unwrap();
+        let client_blind_result = VoprfClient::<CS>::blind(b"input", &mut rng).unwrap();
+        let server_result = server.blind_evaluate(&mut rng, &client_blind_result.message);
+        let pk = server.get_public_key();
+
+        let hex = client_blind_result.message.to_string();
+        assert_eq!(hex, hex::encode(client_blind_result.message.serialize()));
+        let blinded_element: BlindedElement<CS, VoprfMode> = hex.parse().unwrap();
+        assert_eq!(
+            blinded_element.serialize(),
+            client_blind_result.message.serialize()
+        );
+        let blinded_element: BlindedElement<CS, VoprfMode> = hex.to_uppercase().parse().unwrap();
+        assert_eq!(
+            blinded_element.serialize(),
+            client_blind_result.message.serialize()
+        );
+
+        let evaluation_element: EvaluationElement<CS, VoprfMode> =
+            server_result.message.to_string().parse().unwrap();
+        assert_eq!(
+            evaluation_element.serialize(),
+            server_result.message.serialize()
+        );
+        let proof: Proof<CS> = server_result.proof.to_string().parse().unwrap();
+        assert_eq!(proof.serialize(), server_result.proof.serialize());
+        assert_eq!(pk.to_string().parse::<PublicKey<CS>>().unwrap(), pk);
+
+        let hex = pk.to_string();
+        assert_eq!(
+            hex[1..].parse::<PublicKey<CS>>().unwrap_err(),
+            Error::Deserialization(DeserializationError {
+                field: "pk",
+                reason: DeserializationReason::Encoding,
+            })
+        );
+        assert_eq!(
+            hex[2..].parse::<PublicKey<CS>>().unwrap_err(),
+            Error::Deserialization(DeserializationError {
+                field: "pk",
+                reason: DeserializationReason::Length {
+                    expected: hex.len() / 2,
+                    actual: hex.len() / 2 - 1,
+                },
+            })
+        );
+        assert_eq!(
+            ::alloc::format!("zz{}", &hex[2..])
+                .parse::<PublicKey<CS>>()
+                .unwrap_err(),
+            Error::Deserialization(DeserializationError {
+                field: "pk",
+                reason: DeserializationReason::Encoding,
+            })
+        );
+
+        #[cfg(feature = "serde")]
+        {
+            let json = serde_json::to_string(&pk).unwrap();
+            assert_eq!(json, ::alloc::format!("\"{hex}\""));
+            let deserialized: PublicKey<CS> = serde_json::from_str(&json).unwrap();
+            assert_eq!(deserialized, pk);
+            let deserialized: PublicKey<CS> = serde_json::from_str(&json.to_uppercase()).unwrap();
+            assert_eq!(deserialized, pk);
+            assert!(serde_json::from_str::<PublicKey<CS>>(&json[1..]).is_err());
+            assert!(
+                serde_json::from_str::<PublicKey<CS>>(&::alloc::format!("\"{}\"", &hex[2..]))
+                    .is_err()
+            );
+
+            let json = serde_json::to_string(&server).unwrap();
+            let deserialized: VoprfServer<CS> = serde_json::from_str(&json).unwrap();
+            assert_eq!(deserialized.serialize(), server.serialize());
+            let json = serde_json::to_string(&server_result.proof).unwrap();
+            let deserialized: Proof<CS> = serde_json::from_str(&json).unwrap();
+            assert_eq!(deserialized.serialize(), server_result.proof.serialize());
+        }
+    }
+
+    #[test]
+    fn test_hex_encoding() {
+        #[cfg(feature = "ristretto255")]
+        hex_encoding::<crate::Ristretto255>();
+        hex_encoding::<p256::NistP256>();
+        hex_encoding::<p384::NistP384>();
+        hex_encoding::<p521::NistP521>();
+    }
+
+    #[cfg(any(feature = "jwk", feature = "pkcs8"))]
+    #[test]
+    fn test_base64() {
+        use super::base64::{decode, encode, Alphabet};
+
+        // RFC 4648, Section 10.
+        let vectors = [
+            ("", "", ""),
+            ("f", "Zg==", "Zg"),
+            ("fo", "Zm8=", "Zm8"),
+            ("foo", "Zm9v", "Zm9v"),
+            ("foob", "Zm9vYg==", "Zm9vYg"),
+            ("fooba", "Zm9vYmE=", "Zm9vYmE"),
+            ("foobar", "Zm9vYmFy", "Zm9vYmFy"),
+        ];
+
+        for (input, standard, url) in vectors {
+            assert_eq!(encode(input.as_bytes(), Alphabet::Standard), standard);
+            assert_eq!(encode(input.as_bytes(), Alphabet::Url), url);
+            assert_eq!(
+                decode(standard, Alphabet::Standard).unwrap().as_slice(),
+                input.as_bytes()
+            );
+            assert_eq!(
+                decode(url, Alphabet::Url).unwrap().as_slice(),
+                input.as_bytes()
+            );
+        }
+
+        assert_eq!(encode(&[0xfb, 0xff], Alphabet::Standard), "+/8=");
+        assert_eq!(encode(&[0xfb, 0xff], Alphabet::Url), "-_8");
+
+        // Padding, wrong alphabet and non-zero unused bits.
+        assert!(decode("Zg", Alphabet::Standard).is_none());
+        assert!(decode("Zg==", Alphabet::Url).is_none());
+        assert!(decode("Z===", Alphabet::Standard).is_none());
+        assert!(decode("Zg=a", Alphabet::Standard).is_none());
+        assert!(decode("+/8=", Alphabet::Url).is_none());
+        assert!(decode("-_8", Alphabet::Standard).is_none());
+        assert!(decode("Zh", Alphabet::Url).is_none());
+        assert!(decode("Z", Alphabet::Url).is_none());
     }
 
     proptest! {
+        #[cfg(any(feature = "jwk", feature = "pkcs8"))]
+        #[test]
+        fn test_base64_round_trip(bytes in vec(any::<u8>(), 0..200)) {
+            use super::base64::{decode, encode, Alphabet};
+
+            for alphabet in [Alphabet::Standard, Alphabet::Url] {
+                let encoded = encode(&bytes, alphabet);
+                let decoded = decode(&encoded, alphabet).unwrap();
+                prop_assert_eq!(decoded.as_slice(), &bytes[..]);
+            }
+        }
+
         #[test]
         fn test_nocrash_oprf_client(bytes in vec(any::<u8>(), 0..200)) {
             test_deserialize!(OprfClient, bytes);
@@ -369,17 +1234,32 @@ mod test {
 
         #[test]
         fn test_nocrash_blinded_element(bytes in vec(any::<u8>(), 0..200)) {
-            test_deserialize!(BlindedElement, bytes);
+            test_deserialize!(BlindedElement, OprfMode, bytes);
         }
 
         #[test]
         fn test_nocrash_evaluation_element(bytes in vec(any::<u8>(), 0..200)) {
-            test_deserialize!(EvaluationElement, bytes);
+            test_deserialize!(EvaluationElement, OprfMode, bytes);
         }
 
         #[test]
         fn test_nocrash_proof(bytes in vec(any::<u8>(), 0..200)) {
             test_deserialize!(Proof, bytes);
         }
+
+        #[test]
+        fn test_nocrash_oprf_output(bytes in vec(any::<u8>(), 0..200)) {
+            test_deserialize!(OprfOutput, bytes);
+        }
+
+        #[test]
+        fn test_nocrash_public_key(bytes in vec(any::<u8>(), 0..200)) {
+            test_deserialize!(PublicKey, bytes);
+        }
+
+        #[test]
+        fn test_nocrash_private_key(bytes in vec(any::<u8>(), 0..200)) {
+            test_deserialize!(PrivateKey, bytes);
+        }
     }
 }
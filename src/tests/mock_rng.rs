@@ -9,7 +9,7 @@
 use alloc::vec::Vec;
 use core::cmp::min;
 
-use rand_core::{CryptoRng, RngCore};
+use rand_core::{CryptoRng, RngCore, TryCryptoRng, TryRngCore};
 
 /// A simple implementation of `RngCore` for testing purposes.
 ///
@@ -58,3 +58,38 @@ impl RngCore for CycleRng {
 
 // This is meant for testing only
 impl CryptoRng for CycleRng {}
+
+/// An implementation of `TryRngCore` which always fails, for testing purposes.
+#[derive(Debug, Clone, Copy)]
+pub struct FailingRng;
+
+/// The error of a [`FailingRng`].
+#[derive(Debug, Clone, Copy)]
+pub struct FailingRngError;
+
+impl core::fmt::Display for FailingRngError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("the random number generator failed")
+    }
+}
+
+impl core::error::Error for FailingRngError {}
+
+impl TryRngCore for FailingRng {
+    type Error = FailingRngError;
+
+    fn try_next_u32(&mut self) -> Result<u32, Self::Error> {
+        Err(FailingRngError)
+    }
+
+    fn try_next_u64(&mut self) -> Result<u64, Self::Error> {
+        Err(FailingRngError)
+    }
+
+    fn try_fill_bytes(&mut self, _: &mut [u8]) -> Result<(), Self::Error> {
+        Err(FailingRngError)
+    }
+}
+
+// This is meant for testing only
+impl TryCryptoRng for FailingRng {}
@@ -7,6 +7,6 @@
 // licenses.
 
 mod cfrg_vectors;
-mod mock_rng;
+pub(crate) mod mock_rng;
 mod parser;
 mod test_cfrg_vectors;
@@ -12,12 +12,11 @@ use alloc::vec::Vec;
 
 use serde_json::Value;
 
-use crate::tests::mock_rng::CycleRng;
 use crate::tests::parser::*;
 use crate::{
     BlindedElement, CipherSuite, EvaluationElement, Group, OprfClient, OprfServer, PoprfClient,
     PoprfServer, PoprfServerBatchEvaluateFinishResult, PoprfServerBatchEvaluatePrepareResult,
-    Proof, Result, VoprfClient, VoprfServer, VoprfServerBatchEvaluateFinishResult,
+    Proof, PublicKey, Result, VoprfClient, VoprfServer, VoprfServerBatchEvaluateFinishResult,
 };
 
 #[derive(Debug)]
@@ -220,7 +219,7 @@ fn test_oprf_seed_to_key<CS: CipherSuite>(tvs: &[VOPRFTestVectorParameters]) ->
 
         assert_eq!(
             &parameters.sksm,
-            &CS::Group::serialize_scalar(server.get_private_key()).to_vec()
+            &server.get_private_key().serialize().to_vec()
         );
     }
     Ok(())
@@ -232,11 +231,11 @@ fn test_voprf_seed_to_key<CS: CipherSuite>(tvs: &[VOPRFTestVectorParameters]) ->
 
         assert_eq!(
             &parameters.sksm,
-            &CS::Group::serialize_scalar(server.get_private_key()).to_vec()
+            &server.get_private_key().serialize().to_vec()
         );
         assert_eq!(
             &parameters.pksm,
-            CS::Group::serialize_elem(server.get_public_key()).as_slice()
+            server.get_public_key().serialize().as_slice()
         );
     }
     Ok(())
@@ -248,11 +247,11 @@ fn test_poprf_seed_to_key<CS: CipherSuite>(tvs: &[VOPRFTestVectorParameters]) ->
 
         assert_eq!(
             &parameters.sksm,
-            &CS::Group::serialize_scalar(server.get_private_key()).to_vec()
+            &server.get_private_key().serialize().to_vec()
         );
         assert_eq!(
             &parameters.pksm,
-            CS::Group::serialize_elem(server.get_public_key()).as_slice()
+            server.get_public_key().serialize().as_slice()
         );
     }
     Ok(())
@@ -341,7 +340,7 @@ fn test_oprf_blind_evaluate<CS: CipherSuite>(tvs: &[VOPRFTestVectorParameters])
 
 fn test_voprf_blind_evaluate<CS: CipherSuite>(tvs: &[VOPRFTestVectorParameters]) -> Result<()> {
     for parameters in tvs {
-        let mut rng = CycleRng::new(parameters.proof_random_scalar.clone());
+        let proof_random_scalar = CS::Group::deserialize_scalar(&parameters.proof_random_scalar)?;
         let server = VoprfServer::<CS>::new_with_key(&parameters.sksm)?;
 
         let mut blinded_elements = vec![];
@@ -353,7 +352,11 @@ fn test_voprf_blind_evaluate<CS: CipherSuite>(tvs: &[VOPRFTestVectorParameters])
             server.batch_blind_evaluate_prepare(blinded_elements.iter());
         let prepared_elements: Vec<_> = prepared_evaluation_elements.collect();
         let VoprfServerBatchEvaluateFinishResult { messages, proof } = server
-            .batch_blind_evaluate_finish(&mut rng, blinded_elements.iter(), &prepared_elements)?;
+            .batch_blind_evaluate_finish_unchecked(
+                proof_random_scalar,
+                blinded_elements.iter(),
+                &prepared_elements,
+            )?;
         let messages: Vec<_> = messages.collect();
 
         for (parameter, message) in parameters.evaluation_element.iter().zip(messages) {
@@ -367,7 +370,7 @@ fn test_voprf_blind_evaluate<CS: CipherSuite>(tvs: &[VOPRFTestVectorParameters])
 
 fn test_poprf_blind_evaluate<CS: CipherSuite>(tvs: &[VOPRFTestVectorParameters]) -> Result<()> {
     for parameters in tvs {
-        let mut rng = CycleRng::new(parameters.proof_random_scalar.clone());
+        let proof_random_scalar = CS::Group::deserialize_scalar(&parameters.proof_random_scalar)?;
         let server = PoprfServer::<CS>::new_with_key(&parameters.sksm)?;
 
         let mut blinded_elements = vec![];
@@ -381,8 +384,8 @@ fn test_poprf_blind_evaluate<CS: CipherSuite>(tvs: &[VOPRFTestVectorParameters])
         } = server.batch_blind_evaluate_prepare(blinded_elements.iter(), Some(&parameters.info))?;
         let prepared_evaluation_elements: Vec<_> = prepared_evaluation_elements.collect();
         let PoprfServerBatchEvaluateFinishResult { messages, proof } =
-            PoprfServer::batch_blind_evaluate_finish::<_, _, Vec<_>>(
-                &mut rng,
+            PoprfServer::batch_blind_evaluate_finish_unchecked::<_, Vec<_>>(
+                proof_random_scalar,
                 blinded_elements.iter(),
                 &prepared_evaluation_elements,
                 &prepared_tweak,
@@ -412,7 +415,10 @@ fn test_oprf_finalize<CS: CipherSuite>(tvs: &[VOPRFTestVectorParameters]) -> Res
                 &EvaluationElement::deserialize(&parameters.evaluation_element[i])?,
             )?;
 
-            assert_eq!(&parameters.output[i], &client_finalize_result.to_vec());
+            assert_eq!(
+                &parameters.output[i],
+                &client_finalize_result.serialize().to_vec()
+            );
         }
     }
     Ok(())
@@ -440,14 +446,15 @@ fn test_voprf_finalize<CS: CipherSuite>(tvs: &[VOPRFTestVectorParameters]) -> Re
             &clients,
             &messages,
             &Proof::deserialize(&parameters.proof)?,
-            CS::Group::deserialize_elem(&parameters.pksm)?,
+            &PublicKey::deserialize(&parameters.pksm)?,
         )?;
 
         assert_eq!(
             parameters.output,
             batch_result
-                .map(|arr| arr.map(|message| message.to_vec()))
-                .collect::<Result<Vec<_>>>()?
+                .map(|arr| arr.map(|message| message.serialize().to_vec()))
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|error| error.error)?
         );
     }
     Ok(())
@@ -475,11 +482,13 @@ fn test_poprf_finalize<CS: CipherSuite>(tvs: &[VOPRFTestVectorParameters]) -> Re
             &clients,
             &messages,
             &Proof::deserialize(&parameters.proof)?,
-            CS::Group::deserialize_elem(&parameters.pksm)?,
+            &PublicKey::deserialize(&parameters.pksm)?,
             Some(&parameters.info),
         )?;
 
-        let result: Vec<Vec<u8>> = batch_result.map(|arr| arr.unwrap().to_vec()).collect();
+        let result: Vec<Vec<u8>> = batch_result
+            .map(|arr| arr.unwrap().serialize().to_vec())
+            .collect();
 
         assert_eq!(parameters.output, result);
     }
@@ -494,7 +503,10 @@ fn test_oprf_evaluate<CS: CipherSuite>(tvs: &[VOPRFTestVectorParameters]) -> Res
 
             let server_evaluate_result = server.evaluate(&parameters.input[i])?;
 
-            assert_eq!(&parameters.output[i], &server_evaluate_result.to_vec());
+            assert_eq!(
+                &parameters.output[i],
+                &server_evaluate_result.serialize().to_vec()
+            );
         }
     }
     Ok(())
@@ -507,7 +519,10 @@ fn test_voprf_evaluate<CS: CipherSuite>(tvs: &[VOPRFTestVectorParameters]) -> Re
 
             let server_evaluate_result = server.evaluate(&parameters.input[i])?;
 
-            assert_eq!(&parameters.output[i], &server_evaluate_result.to_vec());
+            assert_eq!(
+                &parameters.output[i],
+                &server_evaluate_result.serialize().to_vec()
+            );
         }
     }
     Ok(())
@@ -521,7 +536,10 @@ fn test_poprf_evaluate<CS: CipherSuite>(tvs: &[VOPRFTestVectorParameters]) -> Re
             let server_evaluate_result =
                 server.evaluate(&parameters.input[i], Some(&parameters.info))?;
 
-            assert_eq!(&parameters.output[i], &server_evaluate_result.to_vec());
+            assert_eq!(
+                &parameters.output[i],
+                &server_evaluate_result.serialize().to_vec()
+            );
         }
     }
     Ok(())
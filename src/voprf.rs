@@ -10,22 +10,31 @@
 
 #[cfg(feature = "alloc")]
 use alloc::vec::Vec;
-use core::iter::{self, Map, Repeat, Zip};
+use core::fmt::{self, Debug, Formatter};
+use core::iter::{self, Enumerate, Map, Repeat, Zip};
+use core::marker::PhantomData;
 
 use derive_where::derive_where;
-use digest::{Digest, Output};
+use digest::Digest;
 use generic_array::typenum::Unsigned;
 use generic_array::GenericArray;
 use rand_core::{TryCryptoRng, TryRngCore};
+#[cfg(feature = "rayon")]
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
 
+#[cfg(feature = "danger")]
+use crate::common::InsecureDebug;
 use crate::common::{
-    derive_keypair, deterministic_blind_unchecked, generate_proof, hash_to_group, i2osp_2,
-    server_evaluate_hash_input, verify_proof, BlindedElement, EvaluationElement, Mode,
-    PreparedEvaluationElement, Proof, STR_FINALIZE,
+    check_inputs, derive_keypair, deterministic_blind_unchecked, generate_proof, hash_to_group,
+    i2osp_2, proof_randomness, server_evaluate_hash_input, verify_proof, BlindedElement,
+    EvaluationElement, Hex, Mode, OprfOutput, PreparedEvaluationElement, PrivateKey, Proof,
+    PublicKey, VoprfMode, STR_FINALIZE,
 };
+#[cfg(feature = "alloc")]
+use crate::common::{next_chunk, BatchIndexMap, MAX_BATCH_SIZE};
 #[cfg(feature = "serde")]
 use crate::serialization::serde::{Element, Scalar};
-use crate::{CipherSuite, Error, Group, Result};
+use crate::{BatchError, CipherSuite, Error, Group, InputError, Result};
 
 ////////////////////////////
 // High-level API Structs //
@@ -35,7 +44,7 @@ use crate::{CipherSuite, Error, Group, Result};
 /// A client which engages with a [VoprfServer] in verifiable mode, meaning
 /// that the OPRF outputs can be checked against a server public key.
 #[derive_where(Clone, ZeroizeOnDrop)]
-#[derive_where(Debug, Eq, Hash, Ord, PartialEq, PartialOrd; <CS::Group as Group>::Scalar, <CS::Group as Group>::Elem)]
+#[derive_where(Eq, Hash, Ord, PartialEq, PartialOrd; <CS::Group as Group>::Scalar, <CS::Group as Group>::Elem)]
 #[cfg_attr(
     feature = "serde",
     derive(serde::Deserialize, serde::Serialize),
@@ -51,7 +60,7 @@ pub struct VoprfClient<CS: CipherSuite> {
 /// A server which engages with a [VoprfClient] in verifiable mode, meaning
 /// that the OPRF outputs can be checked against a server public key.
 #[derive_where(Clone, ZeroizeOnDrop)]
-#[derive_where(Debug, Eq, Hash, Ord, PartialEq, PartialOrd; <CS::Group as Group>::Scalar, <CS::Group as Group>::Elem)]
+#[derive_where(Eq, Hash, Ord, PartialEq, PartialOrd; <CS::Group as Group>::Scalar, <CS::Group as Group>::Elem)]
 #[cfg_attr(
     feature = "serde",
     derive(serde::Deserialize, serde::Serialize),
@@ -64,6 +73,34 @@ pub struct VoprfServer<CS: CipherSuite> {
     pub(crate) pk: <CS::Group as Group>::Elem,
 }
 
+/// An [`EvaluationElement`] whose proof was verified by
+/// [`VoprfClient::verify`] or [`VoprfClient::batch_verify`]. Can be persisted
+/// and later turned into the VOPRF output with
+/// [`VoprfClient::finalize_verified`].
+///
+/// The whole transcript is kept: the client state, the [`EvaluationElement`],
+/// the [`Proof`] and the [`PublicKey`] it was verified against. Loading it with
+/// [`deserialize_and_verify`](Self::deserialize_and_verify) or
+/// [`batch_deserialize_and_verify`](Self::batch_deserialize_and_verify)
+/// verifies the proof again, deserializing without verification is only
+/// available with the `danger` feature.
+#[derive_where(Clone, ZeroizeOnDrop)]
+#[derive_where(Eq, Hash, Ord, PartialEq, PartialOrd; <CS::Group as Group>::Scalar, <CS::Group as Group>::Elem)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize), serde(bound = ""))]
+#[cfg_attr(all(feature = "serde", feature = "danger"), derive(serde::Deserialize))]
+pub struct VerifiedEvaluation<CS: CipherSuite> {
+    #[cfg_attr(feature = "serde", serde(with = "Scalar::<CS::Group>"))]
+    pub(crate) blind: <CS::Group as Group>::Scalar,
+    #[cfg_attr(feature = "serde", serde(with = "Element::<CS::Group>"))]
+    pub(crate) blinded_element: <CS::Group as Group>::Elem,
+    #[cfg_attr(feature = "serde", serde(with = "Element::<CS::Group>"))]
+    pub(crate) evaluation_element: <CS::Group as Group>::Elem,
+    #[derive_where(skip(Zeroize))]
+    pub(crate) proof: Proof<CS>,
+    #[cfg_attr(feature = "serde", serde(with = "Element::<CS::Group>"))]
+    pub(crate) pk: <CS::Group as Group>::Elem,
+}
+
 /////////////////////////
 // API Implementations //
 // =================== //
@@ -113,7 +150,7 @@ impl<CS: CipherSuite> VoprfClient<CS> {
                 blind,
                 blinded_element,
             },
-            message: BlindedElement(blinded_element),
+            message: BlindedElement(blinded_element, PhantomData),
         })
     }
 
@@ -126,16 +163,16 @@ impl<CS: CipherSuite> VoprfClient<CS> {
     pub fn finalize(
         &self,
         input: &[u8],
-        evaluation_element: &EvaluationElement<CS>,
+        evaluation_element: &EvaluationElement<CS, VoprfMode>,
         proof: &Proof<CS>,
-        pk: <CS::Group as Group>::Elem,
-    ) -> Result<Output<CS::Hash>> {
+        pk: &PublicKey<CS>,
+    ) -> Result<OprfOutput<CS>> {
         let inputs = core::array::from_ref(&input);
         let clients = core::array::from_ref(self);
         let messages = core::array::from_ref(evaluation_element);
 
         let mut batch_result = Self::batch_finalize(inputs, clients, messages, proof, pk)?;
-        batch_result.next().unwrap()
+        batch_result.next().unwrap().map_err(|error| error.error)
     }
 
     /// Allows for batching of the finalization of multiple [VoprfClient]
@@ -146,14 +183,15 @@ impl<CS: CipherSuite> VoprfClient<CS> {
     ///   or is longer then [`u16::MAX`].
     /// - [`Error::ProofVerification`] if the `proof` failed to verify.
     ///
-    /// The resulting messages can each fail individually with [`Error::Input`]
-    /// if the `input` is empty or longer then [`u16::MAX`].
+    /// The resulting messages can each fail individually with a [`BatchError`]
+    /// of [`Error::Input`] and its index if the `input` is empty or longer then
+    /// [`u16::MAX`].
     pub fn batch_finalize<'a, I, II, IC, IM>(
         inputs: &'a II,
         clients: &'a IC,
         messages: &'a IM,
         proof: &Proof<CS>,
-        pk: <CS::Group as Group>::Elem,
+        pk: &PublicKey<CS>,
     ) -> Result<VoprfClientBatchFinalizeResult<'a, CS, I, II, IC, IM>>
     where
         CS: 'a,
@@ -162,16 +200,229 @@ impl<CS: CipherSuite> VoprfClient<CS> {
         <&'a II as IntoIterator>::IntoIter: ExactSizeIterator,
         &'a IC: 'a + IntoIterator<Item = &'a VoprfClient<CS>>,
         <&'a IC as IntoIterator>::IntoIter: ExactSizeIterator,
-        &'a IM: 'a + IntoIterator<Item = &'a EvaluationElement<CS>>,
+        &'a IM: 'a + IntoIterator<Item = &'a EvaluationElement<CS, VoprfMode>>,
         <&'a IM as IntoIterator>::IntoIter: ExactSizeIterator,
     {
-        let unblinded_elements = verifiable_unblind(clients, messages, pk, proof)?;
+        let unblinded_elements = verifiable_unblind(clients, messages, pk.0, proof)?;
         let inputs_and_unblinded_elements = inputs.into_iter().zip(unblinded_elements);
         Ok(finalize_after_unblind::<CS, _, _>(
             inputs_and_unblinded_elements,
         ))
     }
 
+    /// Same as [`batch_finalize`](Self::batch_finalize), but checks all
+    /// `inputs` before verifying the `proof`, so the resulting messages can't
+    /// fail individually.
+    ///
+    /// # Errors
+    /// - [`Error::Input`] with the index of the first `input` which is empty
+    ///   or longer then [`u16::MAX`].
+    /// - [`Error::Batch`] if the number of `clients` and `messages` don't match
+    ///   or is longer then [`u16::MAX`].
+    /// - [`Error::ProofVerification`] if the `proof` failed to verify.
+    pub fn batch_finalize_checked<'a, I, II, IC, IM>(
+        inputs: &'a II,
+        clients: &'a IC,
+        messages: &'a IM,
+        proof: &Proof<CS>,
+        pk: &PublicKey<CS>,
+    ) -> Result<VoprfClientBatchFinalizeResult<'a, CS, I, II, IC, IM>, BatchError>
+    where
+        CS: 'a,
+        I: 'a + AsRef<[u8]>,
+        &'a II: 'a + IntoIterator<Item = I>,
+        <&'a II as IntoIterator>::IntoIter: ExactSizeIterator,
+        &'a IC: 'a + IntoIterator<Item = &'a VoprfClient<CS>>,
+        <&'a IC as IntoIterator>::IntoIter: ExactSizeIterator,
+        &'a IM: 'a + IntoIterator<Item = &'a EvaluationElement<CS, VoprfMode>>,
+        <&'a IM as IntoIterator>::IntoIter: ExactSizeIterator,
+    {
+        check_inputs(inputs)?;
+
+        Ok(Self::batch_finalize(inputs, clients, messages, proof, pk)?)
+    }
+
+    /// Allows for finalization of a batch evaluated by
+    /// [`VoprfServer::batch_blind_evaluate_filtered`]. The `inputs` and
+    /// `clients` cover the whole batch, while the `messages` and the `proof`
+    /// only cover the elements accepted in `index_map`.
+    ///
+    /// Returns one result per input, in the same order, which is [`None`] if
+    /// the element was rejected by the server.
+    ///
+    /// # Errors
+    /// - [`Error::Batch`] if the number of `inputs` and `clients` doesn't match
+    ///   the `index_map` or the number of `messages` doesn't match the accepted
+    ///   elements.
+    /// - [`Error::ProofVerification`] if the `proof` failed to verify.
+    ///
+    /// The resulting outputs can each fail individually with a [`BatchError`]
+    /// of [`Error::Input`] and its index in `inputs` if the `input` is empty or
+    /// longer then [`u16::MAX`].
+    #[cfg(feature = "alloc")]
+    pub fn batch_finalize_filtered<'a, I, II, IC, IM>(
+        inputs: &'a II,
+        clients: &'a IC,
+        messages: &'a IM,
+        proof: &Proof<CS>,
+        pk: &PublicKey<CS>,
+        index_map: &BatchIndexMap,
+    ) -> Result<VoprfClientBatchFinalizeFilteredResult<CS>>
+    where
+        CS: 'a,
+        I: 'a + AsRef<[u8]>,
+        &'a II: 'a + IntoIterator<Item = I>,
+        &'a IC: 'a + IntoIterator<Item = &'a VoprfClient<CS>>,
+        &'a IM: 'a + IntoIterator<Item = &'a EvaluationElement<CS, VoprfMode>>,
+    {
+        let inputs: Vec<_> = inputs.into_iter().collect();
+        let clients: Vec<_> = clients.into_iter().collect();
+        let messages: Vec<_> = messages.into_iter().cloned().collect();
+
+        if inputs.len() != index_map.len() || clients.len() != index_map.len() {
+            return Err(Error::Batch);
+        }
+
+        let accepted_inputs: Vec<_> = index_map
+            .accepted()
+            .map(|index| inputs[index].as_ref())
+            .collect();
+        let accepted_clients: Vec<_> = index_map
+            .accepted()
+            .map(|index| clients[index].clone())
+            .collect();
+        let mut outputs =
+            Self::batch_finalize(&accepted_inputs, &accepted_clients, &messages, proof, pk)?;
+
+        Ok((0..index_map.len())
+            .map(|index| {
+                index_map.is_accepted(index).then(|| {
+                    // The number of outputs matches the accepted elements.
+                    outputs
+                        .next()
+                        .unwrap()
+                        .map_err(|error| BatchError::new(index, error.error))
+                })
+            })
+            .collect())
+    }
+
+    /// Verifies the `proof` of the server's message, without computing the
+    /// output yet. The output can be computed later with
+    /// [`finalize_verified`](Self::finalize_verified).
+    ///
+    /// # Errors
+    /// [`Error::ProofVerification`] if the `proof` failed to verify.
+    pub fn verify(
+        &self,
+        evaluation_element: &EvaluationElement<CS, VoprfMode>,
+        proof: &Proof<CS>,
+        pk: &PublicKey<CS>,
+    ) -> Result<VerifiedEvaluation<CS>> {
+        let clients = core::array::from_ref(self);
+        let messages = core::array::from_ref(evaluation_element);
+
+        let mut batch_result = Self::batch_verify(clients, messages, proof, pk)?;
+        Ok(batch_result.next().unwrap())
+    }
+
+    /// Allows for batching of the verification of multiple [VoprfClient] and
+    /// [EvaluationElement] pairs, see [`verify`](Self::verify).
+    ///
+    /// # Errors
+    /// - [`Error::Batch`] if the number of `clients` and `messages` don't match
+    ///   or is longer then [`u16::MAX`].
+    /// - [`Error::ProofVerification`] if the `proof` failed to verify.
+    pub fn batch_verify<'a, IC, IM>(
+        clients: &'a IC,
+        messages: &'a IM,
+        proof: &Proof<CS>,
+        pk: &PublicKey<CS>,
+    ) -> Result<
+        VoprfClientBatchVerifyResult<
+            CS,
+            <&'a IC as IntoIterator>::IntoIter,
+            <&'a IM as IntoIterator>::IntoIter,
+        >,
+    >
+    where
+        CS: 'a,
+        &'a IC: 'a + IntoIterator<Item = &'a VoprfClient<CS>>,
+        <&'a IC as IntoIterator>::IntoIter: ExactSizeIterator,
+        &'a IM: 'a + IntoIterator<Item = &'a EvaluationElement<CS, VoprfMode>>,
+        <&'a IM as IntoIterator>::IntoIter: ExactSizeIterator,
+    {
+        verify_proof(
+            CS::Group::base_elem(),
+            pk.0,
+            clients.into_iter().map(|client| client.blinded_element),
+            messages.into_iter().map(|message| message.0),
+            proof,
+            Mode::Voprf,
+        )?;
+
+        Ok(VoprfClientBatchVerifyResult {
+            clients: clients.into_iter(),
+            messages: messages.into_iter(),
+            proof: proof.clone(),
+            pk: pk.0,
+        })
+    }
+
+    /// Computes the VOPRF output for `input` from an `evaluation` returned by
+    /// [`verify`](Self::verify) or [`batch_verify`](Self::batch_verify).
+    ///
+    /// # Errors
+    /// [`Error::Input`] if the `input` is empty or longer then [`u16::MAX`].
+    pub fn finalize_verified(
+        input: &[u8],
+        evaluation: &VerifiedEvaluation<CS>,
+    ) -> Result<OprfOutput<CS>> {
+        let unblinded_element =
+            evaluation.evaluation_element * &CS::Group::invert_scalar(evaluation.blind);
+        let inputs_and_unblinded_elements = iter::once((input, unblinded_element));
+        finalize_after_unblind::<CS, _, _>(inputs_and_unblinded_elements)
+            .next()
+            .unwrap()
+            .map_err(|error| error.error)
+    }
+
+    /// Allows for finalization of batches of any size, evaluated in chunks by
+    /// [`VoprfServer::batch_blind_evaluate_chunked`]. Each of the `responses`
+    /// is matched in order against the next `inputs` and `clients`, and the
+    /// outputs of a chunk are only returned after its proof was verified.
+    ///
+    /// Only the current chunk is kept in memory.
+    ///
+    /// Each chunk can fail individually with a [`BatchError`] of:
+    /// - [`Error::Batch`] if `inputs` or `clients` are exhausted before the
+    ///   chunk is complete.
+    /// - [`Error::ProofVerification`] if the `proof` of the chunk failed to
+    ///   verify.
+    /// - [`Error::Input`] and its index, counted over all chunks, if an
+    ///   `input` is empty or longer then [`u16::MAX`].
+    #[cfg(feature = "alloc")]
+    pub fn batch_finalize_chunked<I, II, IC, IR>(
+        inputs: II,
+        clients: IC,
+        responses: IR,
+        pk: &PublicKey<CS>,
+    ) -> VoprfClientBatchFinalizeChunks<CS, II::IntoIter, IC::IntoIter, IR::IntoIter>
+    where
+        I: AsRef<[u8]>,
+        II: IntoIterator<Item = I>,
+        IC: IntoIterator<Item = VoprfClient<CS>>,
+        IR: IntoIterator<Item = VoprfServerBatchEvaluateResult<CS>>,
+    {
+        VoprfClientBatchFinalizeChunks {
+            inputs: inputs.into_iter(),
+            clients: clients.into_iter(),
+            responses: responses.into_iter(),
+            pk: pk.0,
+            position: 0,
+        }
+    }
+
     /// Only used for test functions
     #[cfg(test)]
     pub fn from_blind_and_element(
@@ -191,14 +442,88 @@ impl<CS: CipherSuite> VoprfClient<CS> {
     }
 }
 
+impl<CS: CipherSuite> VerifiedEvaluation<CS> {
+    /// Deserialization from bytes, verifying the proof again.
+    ///
+    /// An evaluation returned by [`VoprfClient::batch_verify`] is covered by
+    /// the proof of the whole batch, load those with
+    /// [`batch_deserialize_and_verify`](Self::batch_deserialize_and_verify).
+    ///
+    /// # Errors
+    /// - [`Error::Deserialization`] if failed to deserialize `input`.
+    /// - [`Error::ProofVerification`] if the proof failed to verify.
+    pub fn deserialize_and_verify(input: &[u8]) -> Result<Self> {
+        let evaluation = Self::deserialize_unchecked(input)?;
+        verify_proof(
+            CS::Group::base_elem(),
+            evaluation.pk,
+            iter::once(evaluation.blinded_element),
+            iter::once(evaluation.evaluation_element),
+            &evaluation.proof,
+            Mode::Voprf,
+        )?;
+
+        Ok(evaluation)
+    }
+
+    /// Deserialization of all evaluations returned by a single
+    /// [`VoprfClient::batch_verify`], in the same order, verifying the proof
+    /// of the batch again.
+    ///
+    /// # Errors
+    /// - [`Error::Deserialization`] if failed to deserialize any of the
+    ///   `inputs`.
+    /// - [`Error::Batch`] if `inputs` is empty or longer then [`u16::MAX`].
+    /// - [`Error::ProofVerification`] if the evaluations don't share the same
+    ///   proof and public key or the proof failed to verify.
+    #[cfg(feature = "alloc")]
+    pub fn batch_deserialize_and_verify<I: AsRef<[u8]>>(
+        inputs: impl IntoIterator<Item = I>,
+    ) -> Result<Vec<Self>> {
+        use subtle::ConstantTimeEq;
+
+        let evaluations = inputs
+            .into_iter()
+            .map(|input| Self::deserialize_unchecked(input.as_ref()))
+            .collect::<Result<Vec<_>>>()?;
+        let first = evaluations.first().ok_or(Error::Batch)?;
+
+        if !evaluations.iter().all(|evaluation| {
+            bool::from(
+                evaluation.proof.c_scalar.ct_eq(&first.proof.c_scalar)
+                    & evaluation.proof.s_scalar.ct_eq(&first.proof.s_scalar)
+                    & evaluation.pk.ct_eq(&first.pk),
+            )
+        }) {
+            return Err(Error::ProofVerification);
+        }
+
+        verify_proof(
+            CS::Group::base_elem(),
+            first.pk,
+            evaluations
+                .iter()
+                .map(|evaluation| evaluation.blinded_element),
+            evaluations
+                .iter()
+                .map(|evaluation| evaluation.evaluation_element),
+            &first.proof,
+            Mode::Voprf,
+        )?;
+
+        Ok(evaluations)
+    }
+}
+
 impl<CS: CipherSuite> VoprfServer<CS> {
     /// Produces a new instance of a [VoprfServer] using a supplied RNG
     ///
     /// # Errors
-    /// [`Error::Protocol`] if the protocol fails and can't be completed.
+    /// - [`Error::Rng`] if the random number generator fails.
+    /// - [`Error::Protocol`] if the protocol fails and can't be completed.
     pub fn new<R: TryRngCore + TryCryptoRng>(rng: &mut R) -> Result<Self> {
         let mut seed = GenericArray::<_, <CS::Group as Group>::ScalarLen>::default();
-        rng.try_fill_bytes(&mut seed).map_err(|_| Error::Protocol)?;
+        rng.try_fill_bytes(&mut seed).map_err(|_| Error::Rng)?;
         // This can't fail as the hash output is type constrained.
         Self::new_from_seed(&seed, &[])
     }
@@ -229,19 +554,53 @@ impl<CS: CipherSuite> VoprfServer<CS> {
         Ok(Self { sk, pk })
     }
 
-    /// Only used for tests
-    #[cfg(test)]
-    pub fn get_private_key(&self) -> <CS::Group as Group>::Scalar {
-        self.sk
+    /// Produces a new instance of a [VoprfServer] from the server's
+    /// [`PrivateKey`].
+    pub fn new_with_private_key(private_key: &PrivateKey<CS>) -> Self {
+        Self {
+            sk: private_key.0,
+            pk: CS::Group::base_elem() * &private_key.0,
+        }
+    }
+
+    /// Retrieves the server's private key
+    #[cfg(any(feature = "danger", test))]
+    pub fn get_private_key(&self) -> PrivateKey<CS> {
+        PrivateKey(self.sk)
     }
 
     /// Computes the second step for the multiplicative blinding version of
     /// DH-OPRF. This message is sent from the server (who holds the OPRF key)
     /// to the client.
+    ///
+    /// The nonce of the proof is hedged: it is derived from the private key, the
+    /// proof transcript and fresh randomness from `rng`. If `rng` fails, the
+    /// nonce falls back to the one of
+    /// [`blind_evaluate_deterministic`](Self::blind_evaluate_deterministic).
     pub fn blind_evaluate<R: TryRngCore + TryCryptoRng>(
         &self,
         rng: &mut R,
-        blinded_element: &BlindedElement<CS>,
+        blinded_element: &BlindedElement<CS, VoprfMode>,
+    ) -> VoprfServerEvaluateResult<CS> {
+        let randomness = proof_randomness::<CS, _>(rng);
+
+        self.blind_evaluate_inner(randomness.as_deref().unwrap_or_default(), blinded_element)
+    }
+
+    /// Same as [`blind_evaluate`](Self::blind_evaluate), but fully
+    /// deterministic: the nonce of the proof is derived from the private key
+    /// and the proof transcript only. Useful for reproducible testing.
+    pub fn blind_evaluate_deterministic(
+        &self,
+        blinded_element: &BlindedElement<CS, VoprfMode>,
+    ) -> VoprfServerEvaluateResult<CS> {
+        self.blind_evaluate_inner(&[], blinded_element)
+    }
+
+    fn blind_evaluate_inner(
+        &self,
+        randomness: &[u8],
+        blinded_element: &BlindedElement<CS, VoprfMode>,
     ) -> VoprfServerEvaluateResult<CS> {
         let mut prepared_evaluation_elements =
             self.batch_blind_evaluate_prepare(iter::once(blinded_element));
@@ -252,8 +611,9 @@ impl<CS: CipherSuite> VoprfServer<CS> {
             mut messages,
             proof,
         } = self
-            .batch_blind_evaluate_finish(
-                rng,
+            .batch_blind_evaluate_finish_inner(
+                randomness,
+                None,
                 iter::once(blinded_element),
                 &prepared_evaluation_element,
             )
@@ -278,7 +638,7 @@ impl<CS: CipherSuite> VoprfServer<CS> {
     ) -> Result<VoprfServerBatchEvaluateResult<CS>>
     where
         CS: 'a,
-        &'a I: IntoIterator<Item = &'a BlindedElement<CS>>,
+        &'a I: IntoIterator<Item = &'a BlindedElement<CS, VoprfMode>>,
         <&'a I as IntoIterator>::IntoIter: ExactSizeIterator,
     {
         let prepared_evaluation_elements = self
@@ -295,11 +655,98 @@ impl<CS: CipherSuite> VoprfServer<CS> {
         Ok(VoprfServerBatchEvaluateResult { messages, proof })
     }
 
+    /// Allows for batching of the evaluation of any number of
+    /// [BlindedElement] messages from a [VoprfClient]. The `blinded_elements`
+    /// are split into chunks of at most [`MAX_BATCH_SIZE`] elements, each
+    /// evaluated with its own [`Proof`], so only the current chunk is kept in
+    /// memory.
+    ///
+    /// The returned chunks can be finalized with
+    /// [`VoprfClient::batch_finalize_chunked`].
+    #[cfg(feature = "alloc")]
+    pub fn batch_blind_evaluate_chunked<'a, R: TryRngCore + TryCryptoRng, I>(
+        &'a self,
+        rng: &'a mut R,
+        blinded_elements: I,
+    ) -> VoprfServerBatchEvaluateChunks<'a, CS, R, I::IntoIter>
+    where
+        I: IntoIterator<Item = BlindedElement<CS, VoprfMode>>,
+    {
+        self.batch_blind_evaluate_chunks_of(rng, blinded_elements, MAX_BATCH_SIZE)
+    }
+
+    /// Same as [`batch_blind_evaluate_chunked`](Self::batch_blind_evaluate_chunked),
+    /// but splits the `blinded_elements` into chunks of `chunk_size` elements.
+    #[cfg(feature = "alloc")]
+    pub(crate) fn batch_blind_evaluate_chunks_of<'a, R: TryRngCore + TryCryptoRng, I>(
+        &'a self,
+        rng: &'a mut R,
+        blinded_elements: I,
+        chunk_size: usize,
+    ) -> VoprfServerBatchEvaluateChunks<'a, CS, R, I::IntoIter>
+    where
+        I: IntoIterator<Item = BlindedElement<CS, VoprfMode>>,
+    {
+        VoprfServerBatchEvaluateChunks {
+            server: self,
+            rng,
+            blinded_elements: blinded_elements.into_iter(),
+            chunk_size,
+        }
+    }
+
+    /// Same as [`batch_blind_evaluate`](Self::batch_blind_evaluate), but only
+    /// evaluates the `blinded_elements` accepted by `filter`, which receives
+    /// each element with its index. The proof only covers the accepted
+    /// elements and the returned [`BatchIndexMap`] records which elements were
+    /// accepted, so the client can match the messages with
+    /// [`VoprfClient::batch_finalize_filtered`].
+    ///
+    /// # Errors
+    /// [`Error::Batch`] if no element was accepted or the number of accepted
+    /// elements is longer then [`u16::MAX`].
+    #[cfg(feature = "alloc")]
+    pub fn batch_blind_evaluate_filtered<'a, R: TryRngCore + TryCryptoRng, I, F>(
+        &self,
+        rng: &mut R,
+        blinded_elements: &'a I,
+        mut filter: F,
+    ) -> Result<VoprfServerBatchEvaluateFilteredResult<CS>>
+    where
+        CS: 'a,
+        &'a I: IntoIterator<Item = &'a BlindedElement<CS, VoprfMode>>,
+        F: FnMut(usize, &BlindedElement<CS, VoprfMode>) -> bool,
+    {
+        let blinded_elements: Vec<_> = blinded_elements.into_iter().collect();
+        let index_map: BatchIndexMap = blinded_elements
+            .iter()
+            .enumerate()
+            .map(|(index, blinded_element)| filter(index, blinded_element))
+            .collect();
+        let accepted_elements: Vec<_> = index_map
+            .accepted()
+            .map(|index| blinded_elements[index].clone())
+            .collect();
+
+        if accepted_elements.is_empty() {
+            return Err(Error::Batch);
+        }
+
+        let VoprfServerBatchEvaluateResult { messages, proof } =
+            self.batch_blind_evaluate::<_, Vec<_>>(rng, &accepted_elements)?;
+
+        Ok(VoprfServerBatchEvaluateFilteredResult {
+            messages,
+            proof,
+            index_map,
+        })
+    }
+
     /// Alternative version of `batch_blind_evaluate` without memory allocation.
     /// Returned [`PreparedEvaluationElement`] have to be
     /// [`collect`](Iterator::collect)ed and passed into
     /// [`batch_blind_evaluate_finish`](Self::batch_blind_evaluate_finish).
-    pub fn batch_blind_evaluate_prepare<'a, I: Iterator<Item = &'a BlindedElement<CS>>>(
+    pub fn batch_blind_evaluate_prepare<'a, I: Iterator<Item = &'a BlindedElement<CS, VoprfMode>>>(
         &self,
         blinded_elements: I,
     ) -> VoprfServerBatchEvaluatePreparedEvaluationElements<CS, I>
@@ -309,13 +756,17 @@ impl<CS: CipherSuite> VoprfServer<CS> {
         blinded_elements
             .zip(iter::repeat(self.sk))
             .map(|(blinded_element, sk)| {
-                PreparedEvaluationElement(EvaluationElement(blinded_element.0 * &sk))
+                PreparedEvaluationElement(EvaluationElement(blinded_element.0 * &sk, PhantomData))
             })
     }
 
     /// See [`batch_blind_evaluate_prepare`](Self::batch_blind_evaluate_prepare)
     /// for more details.
     ///
+    /// The nonce of the proof is hedged: it is derived from the private key, the
+    /// proof transcript and fresh randomness from `rng`. If `rng` fails, the
+    /// nonce is derived without fresh randomness instead.
+    ///
     /// # Errors
     /// [`Error::Batch`] if the number of `blinded_elements` and
     /// `evaluation_elements` don't match or is longer then [`u16::MAX`]
@@ -323,7 +774,7 @@ impl<CS: CipherSuite> VoprfServer<CS> {
         'a,
         'b,
         R: TryRngCore + TryCryptoRng,
-        IB: Iterator<Item = &'a BlindedElement<CS>> + ExactSizeIterator,
+        IB: Iterator<Item = &'a BlindedElement<CS, VoprfMode>> + ExactSizeIterator,
         IE,
     >(
         &self,
@@ -333,12 +784,104 @@ impl<CS: CipherSuite> VoprfServer<CS> {
     ) -> Result<VoprfServerBatchEvaluateFinishResult<'b, CS, IE>>
     where
         CS: 'a + 'b,
-        &'b IE: IntoIterator<Item = &'b PreparedEvaluationElement<CS>>,
+        &'b IE: IntoIterator<Item = &'b PreparedEvaluationElement<CS, VoprfMode>>,
+        <&'b IE as IntoIterator>::IntoIter: ExactSizeIterator,
+    {
+        let randomness = proof_randomness::<CS, _>(rng);
+
+        self.batch_blind_evaluate_finish_inner(
+            randomness.as_deref().unwrap_or_default(),
+            None,
+            blinded_elements,
+            evaluation_elements,
+        )
+    }
+
+    /// Same as
+    /// [`batch_blind_evaluate_finish`](Self::batch_blind_evaluate_finish), but
+    /// fully deterministic: the nonce of the proof is derived from the private
+    /// key and the proof transcript only. Useful for reproducible testing.
+    ///
+    /// # Errors
+    /// [`Error::Batch`] if the number of `blinded_elements` and
+    /// `evaluation_elements` don't match or is longer then [`u16::MAX`]
+    pub fn batch_blind_evaluate_finish_deterministic<
+        'a,
+        'b,
+        IB: Iterator<Item = &'a BlindedElement<CS, VoprfMode>> + ExactSizeIterator,
+        IE,
+    >(
+        &self,
+        blinded_elements: IB,
+        evaluation_elements: &'b IE,
+    ) -> Result<VoprfServerBatchEvaluateFinishResult<'b, CS, IE>>
+    where
+        CS: 'a + 'b,
+        &'b IE: IntoIterator<Item = &'b PreparedEvaluationElement<CS, VoprfMode>>,
+        <&'b IE as IntoIterator>::IntoIter: ExactSizeIterator,
+    {
+        self.batch_blind_evaluate_finish_inner(&[], None, blinded_elements, evaluation_elements)
+    }
+
+    /// Same as
+    /// [`batch_blind_evaluate_finish`](Self::batch_blind_evaluate_finish), but
+    /// taking the nonce of the proof as input instead of deriving it.
+    ///
+    /// # Caution
+    ///
+    /// This should be used with caution, since reusing a nonce for different
+    /// inputs reveals the private key!
+    ///
+    /// # Errors
+    /// [`Error::Batch`] if the number of `blinded_elements` and
+    /// `evaluation_elements` don't match or is longer then [`u16::MAX`]
+    #[cfg(any(feature = "danger", test))]
+    pub fn batch_blind_evaluate_finish_unchecked<
+        'a,
+        'b,
+        IB: Iterator<Item = &'a BlindedElement<CS, VoprfMode>> + ExactSizeIterator,
+        IE,
+    >(
+        &self,
+        proof_random_scalar: <CS::Group as Group>::Scalar,
+        blinded_elements: IB,
+        evaluation_elements: &'b IE,
+    ) -> Result<VoprfServerBatchEvaluateFinishResult<'b, CS, IE>>
+    where
+        CS: 'a + 'b,
+        &'b IE: IntoIterator<Item = &'b PreparedEvaluationElement<CS, VoprfMode>>,
+        <&'b IE as IntoIterator>::IntoIter: ExactSizeIterator,
+    {
+        self.batch_blind_evaluate_finish_inner(
+            &[],
+            Some(proof_random_scalar),
+            blinded_elements,
+            evaluation_elements,
+        )
+    }
+
+    /// Can only fail with [`Error::Batch`].
+    fn batch_blind_evaluate_finish_inner<
+        'a,
+        'b,
+        IB: Iterator<Item = &'a BlindedElement<CS, VoprfMode>> + ExactSizeIterator,
+        IE,
+    >(
+        &self,
+        randomness: &[u8],
+        proof_random_scalar: Option<<CS::Group as Group>::Scalar>,
+        blinded_elements: IB,
+        evaluation_elements: &'b IE,
+    ) -> Result<VoprfServerBatchEvaluateFinishResult<'b, CS, IE>>
+    where
+        CS: 'a + 'b,
+        &'b IE: IntoIterator<Item = &'b PreparedEvaluationElement<CS, VoprfMode>>,
         <&'b IE as IntoIterator>::IntoIter: ExactSizeIterator,
     {
         let g = CS::Group::base_elem();
         let proof = generate_proof(
-            rng,
+            randomness,
+            proof_random_scalar,
             self.sk,
             g,
             self.pk,
@@ -348,9 +891,10 @@ impl<CS: CipherSuite> VoprfServer<CS> {
         )?;
 
         let messages = evaluation_elements.into_iter().map(<fn(
-            &PreparedEvaluationElement<CS>,
-        ) -> EvaluationElement<CS>>::from(
-            |element| EvaluationElement(element.0 .0),
+            &PreparedEvaluationElement<CS, VoprfMode>,
+        )
+            -> EvaluationElement<CS, VoprfMode>>::from(
+            |element| EvaluationElement(element.0 .0, PhantomData),
         ));
 
         Ok(VoprfServerBatchEvaluateFinishResult { messages, proof })
@@ -360,10 +904,10 @@ impl<CS: CipherSuite> VoprfServer<CS> {
     ///
     /// # Errors
     /// [`Error::Input`]  if the `input` is longer then [`u16::MAX`].
-    pub fn evaluate(&self, input: &[u8]) -> Result<Output<<CS as CipherSuite>::Hash>> {
+    pub fn evaluate(&self, input: &[u8]) -> Result<OprfOutput<CS>> {
         let input_element = hash_to_group::<CS>(input, Mode::Voprf)?;
         if CS::Group::is_identity_elem(input_element).into() {
-            return Err(Error::Input);
+            return Err(Error::Input(InputError::HashToGroup));
         };
         let evaluated_element = input_element * &self.sk;
 
@@ -372,24 +916,295 @@ impl<CS: CipherSuite> VoprfServer<CS> {
         server_evaluate_hash_input::<CS>(input, None, issued_element)
     }
 
-    /// Retrieves the server's public key
-    pub fn get_public_key(&self) -> <CS::Group as Group>::Elem {
-        self.pk
-    }
-}
-
-/////////////////////////
-// Convenience Structs //
-//==================== //
-/////////////////////////
+    /// Computes the output of the VOPRF on the server side together with the
+    /// evaluated element and a [`Proof`] that it was computed with the private
+    /// key belonging to [`get_public_key`](Self::get_public_key).
+    ///
+    /// Anyone knowing the `input` and the public key can check the result with
+    /// [`verify_evaluation`], making the key usable as a verifiable PRF.
+    ///
+    /// # Errors
+    /// [`Error::Input`] if the `input` is longer then [`u16::MAX`].
+    pub fn evaluate_with_proof<R: TryRngCore + TryCryptoRng>(
+        &self,
+        rng: &mut R,
+        input: &[u8],
+    ) -> Result<VoprfServerEvaluateWithProofResult<CS>> {
+        let input_element = hash_to_group::<CS>(input, Mode::Voprf)?;
+        if CS::Group::is_identity_elem(input_element).into() {
+            return Err(Error::Input(InputError::HashToGroup));
+        };
+        let evaluated_element = input_element * &self.sk;
 
-/// Contains the fields that are returned by a verifiable client blind
+        let randomness = proof_randomness::<CS, _>(rng);
+        let proof = generate_proof(
+            randomness.as_deref().unwrap_or_default(),
+            None,
+            self.sk,
+            CS::Group::base_elem(),
+            self.pk,
+            iter::once(input_element),
+            iter::once(evaluated_element),
+            Mode::Voprf,
+        )?;
+
+        let issued_element = CS::Group::serialize_elem(evaluated_element);
+        let output = server_evaluate_hash_input::<CS>(input, None, issued_element)?;
+
+        Ok(VoprfServerEvaluateWithProofResult {
+            output,
+            element: EvaluationElement(evaluated_element, PhantomData),
+            proof,
+        })
+    }
+
+    /// Computes the output of the VOPRF on the server side for each of the
+    /// `inputs`, in the same order.
+    ///
+    /// The resulting outputs can each fail individually with [`Error::Input`]
+    /// if the `input` is longer then [`u16::MAX`].
+    pub fn evaluate_many<I>(&self, inputs: I) -> VoprfServerEvaluateManyResult<'_, CS, I::IntoIter>
+    where
+        I: IntoIterator,
+        I::Item: AsRef<[u8]>,
+    {
+        inputs
+            .into_iter()
+            .zip(iter::repeat(self))
+            // Convert to `fn` pointer to make a return type possible.
+            .map(<fn((I::Item, &Self)) -> _>::from(|(input, server)| {
+                server.evaluate(input.as_ref())
+            }))
+    }
+
+    /// Parallel version of [`evaluate_many`](Self::evaluate_many). The outputs
+    /// are returned in the same order as the `inputs`.
+    ///
+    /// The resulting outputs can each fail individually with [`Error::Input`]
+    /// if the `input` is longer then [`u16::MAX`].
+    #[cfg(feature = "rayon")]
+    pub fn par_evaluate_many<I>(&self, inputs: I) -> Vec<Result<OprfOutput<CS>>>
+    where
+        Self: Sync,
+        I: IntoParallelIterator,
+        I::Item: AsRef<[u8]>,
+    {
+        inputs
+            .into_par_iter()
+            .map(|input| self.evaluate(input.as_ref()))
+            .collect()
+    }
+
+    /// Verifies a batch response of a [VoprfServer] against its public key
+    /// `pk`, without any client state. Allows a third party which only sees the
+    /// messages exchanged between client and server to audit the server.
+    ///
+    /// # Errors
+    /// - [`Error::Batch`] if the number of `blinded_elements` and
+    ///   `evaluation_elements` don't match or is longer then [`u16::MAX`].
+    /// - [`Error::ProofVerification`] if the `proof` failed to verify.
+    pub fn verify_server_response<'a, IB, IE>(
+        pk: &PublicKey<CS>,
+        blinded_elements: &'a IB,
+        evaluation_elements: &'a IE,
+        proof: &Proof<CS>,
+    ) -> Result<()>
+    where
+        CS: 'a,
+        IB: ?Sized,
+        IE: ?Sized,
+        &'a IB: IntoIterator<Item = &'a BlindedElement<CS, VoprfMode>>,
+        <&'a IB as IntoIterator>::IntoIter: ExactSizeIterator,
+        &'a IE: IntoIterator<Item = &'a EvaluationElement<CS, VoprfMode>>,
+        <&'a IE as IntoIterator>::IntoIter: ExactSizeIterator,
+    {
+        verify_proof(
+            CS::Group::base_elem(),
+            pk.0,
+            blinded_elements.into_iter().map(|element| element.0),
+            evaluation_elements.into_iter().map(|element| element.0),
+            proof,
+            Mode::Voprf,
+        )
+    }
+
+    /// Verifies multiple batch responses of a [VoprfServer], e.g. the chunks of
+    /// `batch_blind_evaluate_chunked`, with
+    /// [`verify_server_response`](Self::verify_server_response).
+    ///
+    /// # Errors
+    /// A [`BatchError`] with the error returned by
+    /// [`verify_server_response`](Self::verify_server_response). Its
+    /// [`index`](BatchError::index) is the index of the first response in
+    /// `responses` which failed to verify, not of an element.
+    pub fn verify_server_responses<'a, I>(
+        pk: &PublicKey<CS>,
+        responses: I,
+    ) -> Result<(), BatchError>
+    where
+        CS: 'a,
+        I: IntoIterator<
+            Item = (
+                &'a [BlindedElement<CS, VoprfMode>],
+                &'a [EvaluationElement<CS, VoprfMode>],
+                &'a Proof<CS>,
+            ),
+        >,
+    {
+        responses.into_iter().enumerate().try_for_each(
+            |(index, (blinded_elements, evaluation_elements, proof))| {
+                Self::verify_server_response(pk, blinded_elements, evaluation_elements, proof)
+                    .map_err(|error| BatchError::new(index, error))
+            },
+        )
+    }
+
+    /// Retrieves the server's public key
+    pub fn get_public_key(&self) -> PublicKey<CS> {
+        PublicKey(self.pk)
+    }
+}
+
+/// Verifies the result of [`VoprfServer::evaluate_with_proof`] against the
+/// server's public key `pk` and returns the VOPRF output for `input`.
+///
+/// # Errors
+/// - [`Error::Input`] if the `input` is longer then [`u16::MAX`].
+/// - [`Error::ProofVerification`] if the `proof` failed to verify.
+pub fn verify_evaluation<CS: CipherSuite>(
+    pk: &PublicKey<CS>,
+    input: &[u8],
+    element: &EvaluationElement<CS, VoprfMode>,
+    proof: &Proof<CS>,
+) -> Result<OprfOutput<CS>> {
+    let input_element = hash_to_group::<CS>(input, Mode::Voprf)?;
+    if CS::Group::is_identity_elem(input_element).into() {
+        return Err(Error::Input(InputError::HashToGroup));
+    };
+
+    verify_proof(
+        CS::Group::base_elem(),
+        pk.0,
+        iter::once(input_element),
+        iter::once(element.0),
+        proof,
+        Mode::Voprf,
+    )?;
+
+    let issued_element = CS::Group::serialize_elem(element.0);
+    server_evaluate_hash_input::<CS>(input, None, issued_element)
+}
+
+impl<CS: CipherSuite> Debug for VoprfClient<CS> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("VoprfClient")
+            .field("suite", &CS::ID)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "danger")]
+impl<CS: CipherSuite> VoprfClient<CS> {
+    /// Includes the blind and the blinded element in the [`Debug`] output,
+    /// which together link the request seen by the server to this client's
+    /// input.
+    pub fn insecure_debug(&self) -> InsecureDebug<'_, Self> {
+        InsecureDebug(self)
+    }
+}
+
+#[cfg(feature = "danger")]
+impl<CS: CipherSuite> Debug for InsecureDebug<'_, VoprfClient<CS>>
+where
+    <CS::Group as Group>::Scalar: Debug,
+    <CS::Group as Group>::Elem: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("VoprfClient")
+            .field("blind", &self.0.blind)
+            .field("blinded_element", &self.0.blinded_element)
+            .finish()
+    }
+}
+
+impl<CS: CipherSuite> Debug for VoprfServer<CS> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("VoprfServer")
+            .field("suite", &CS::ID)
+            .field("fingerprint", &Hex(&PublicKey::<CS>(self.pk).fingerprint()))
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "danger")]
+impl<CS: CipherSuite> VoprfServer<CS> {
+    /// Includes the private key next to the public key in the [`Debug`]
+    /// output, anyone who sees it can evaluate the VOPRF and forge proofs.
+    pub fn insecure_debug(&self) -> InsecureDebug<'_, Self> {
+        InsecureDebug(self)
+    }
+}
+
+#[cfg(feature = "danger")]
+impl<CS: CipherSuite> Debug for InsecureDebug<'_, VoprfServer<CS>>
+where
+    <CS::Group as Group>::Scalar: Debug,
+    <CS::Group as Group>::Elem: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("VoprfServer")
+            .field("sk", &self.0.sk)
+            .field("pk", &self.0.pk)
+            .finish()
+    }
+}
+
+impl<CS: CipherSuite> Debug for VerifiedEvaluation<CS> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("VerifiedEvaluation")
+            .field("suite", &CS::ID)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "danger")]
+impl<CS: CipherSuite> VerifiedEvaluation<CS> {
+    /// Includes the whole transcript in the [`Debug`] output, including the
+    /// blind, from which the VOPRF output of the input can be computed.
+    pub fn insecure_debug(&self) -> InsecureDebug<'_, Self> {
+        InsecureDebug(self)
+    }
+}
+
+#[cfg(feature = "danger")]
+impl<CS: CipherSuite> Debug for InsecureDebug<'_, VerifiedEvaluation<CS>>
+where
+    <CS::Group as Group>::Scalar: Debug,
+    <CS::Group as Group>::Elem: Debug,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("VerifiedEvaluation")
+            .field("blind", &self.0.blind)
+            .field("blinded_element", &self.0.blinded_element)
+            .field("evaluation_element", &self.0.evaluation_element)
+            .field("proof", &self.0.proof)
+            .field("pk", &self.0.pk)
+            .finish()
+    }
+}
+
+/////////////////////////
+// Convenience Structs //
+//==================== //
+/////////////////////////
+
+/// Contains the fields that are returned by a verifiable client blind
 #[derive_where(Debug; <CS::Group as Group>::Scalar, <CS::Group as Group>::Elem)]
 pub struct VoprfClientBlindResult<CS: CipherSuite> {
     /// The state to be persisted on the client
     pub state: VoprfClient<CS>,
     /// The message to send to the server
-    pub message: BlindedElement<CS>,
+    pub message: BlindedElement<CS, VoprfMode>,
 }
 
 /// Concrete return type for [`VoprfClient::batch_finalize`].
@@ -400,42 +1215,116 @@ pub type VoprfClientBatchFinalizeResult<'a, C, I, II, IC, IM> = FinalizeAfterUnb
     Zip<<&'a II as IntoIterator>::IntoIter, VoprfUnblindResult<'a, C, IC, IM>>,
 >;
 
+/// Iterator returned by [`VoprfClient::batch_verify`], yielding a
+/// [`VerifiedEvaluation`] for each client and message.
+#[derive_where(Clone; IC, IM)]
+#[derive_where(Debug; IC, IM, <CS::Group as Group>::Scalar, <CS::Group as Group>::Elem)]
+pub struct VoprfClientBatchVerifyResult<CS: CipherSuite, IC, IM> {
+    clients: IC,
+    messages: IM,
+    proof: Proof<CS>,
+    pk: <CS::Group as Group>::Elem,
+}
+
+impl<'a, CS: 'a + CipherSuite, IC, IM> Iterator for VoprfClientBatchVerifyResult<CS, IC, IM>
+where
+    IC: Iterator<Item = &'a VoprfClient<CS>>,
+    IM: Iterator<Item = &'a EvaluationElement<CS, VoprfMode>>,
+{
+    type Item = VerifiedEvaluation<CS>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let client = self.clients.next()?;
+        let message = self.messages.next()?;
+
+        Some(VerifiedEvaluation {
+            blind: client.blind,
+            blinded_element: client.blinded_element,
+            evaluation_element: message.0,
+            proof: self.proof.clone(),
+            pk: self.pk,
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.clients.size_hint()
+    }
+}
+
+impl<'a, CS: 'a + CipherSuite, IC, IM> ExactSizeIterator
+    for VoprfClientBatchVerifyResult<CS, IC, IM>
+where
+    IC: ExactSizeIterator<Item = &'a VoprfClient<CS>>,
+    IM: Iterator<Item = &'a EvaluationElement<CS, VoprfMode>>,
+{
+}
+
 /// Contains the fields that are returned by a verifiable server evaluate
 #[derive_where(Debug; <CS::Group as Group>::Scalar, <CS::Group as Group>::Elem)]
 pub struct VoprfServerEvaluateResult<CS: CipherSuite> {
     /// The message to send to the client
-    pub message: EvaluationElement<CS>,
+    pub message: EvaluationElement<CS, VoprfMode>,
     /// The proof for the client to verify
     pub proof: Proof<CS>,
 }
 
+/// Contains the fields that are returned by
+/// [`VoprfServer::evaluate_with_proof`]
+#[derive_where(Debug; <CS::Group as Group>::Scalar, <CS::Group as Group>::Elem)]
+pub struct VoprfServerEvaluateWithProofResult<CS: CipherSuite> {
+    /// The output of the VOPRF
+    pub output: OprfOutput<CS>,
+    /// The evaluated element the output was computed from
+    pub element: EvaluationElement<CS, VoprfMode>,
+    /// The proof that `element` was computed with the server's private key
+    pub proof: Proof<CS>,
+}
+
 /// Contains the fields that are returned by a verifiable server batch evaluate
 #[derive_where(Debug; <CS::Group as Group>::Scalar, <CS::Group as Group>::Elem)]
 #[cfg(feature = "alloc")]
 pub struct VoprfServerBatchEvaluateResult<CS: CipherSuite> {
     /// The messages to send to the client
-    pub messages: Vec<EvaluationElement<CS>>,
+    pub messages: Vec<EvaluationElement<CS, VoprfMode>>,
     /// The proof for the client to verify
     pub proof: Proof<CS>,
 }
 
+/// Concrete return type for [`VoprfClient::batch_finalize_filtered`].
+#[cfg(feature = "alloc")]
+pub type VoprfClientBatchFinalizeFilteredResult<CS> =
+    Vec<Option<Result<OprfOutput<CS>, BatchError>>>;
+
+/// Contains the fields that are returned by
+/// [`VoprfServer::batch_blind_evaluate_filtered`]
+#[derive_where(Debug; <CS::Group as Group>::Scalar, <CS::Group as Group>::Elem)]
+#[cfg(feature = "alloc")]
+pub struct VoprfServerBatchEvaluateFilteredResult<CS: CipherSuite> {
+    /// The messages to send to the client, one per accepted element
+    pub messages: Vec<EvaluationElement<CS, VoprfMode>>,
+    /// The proof for the client to verify
+    pub proof: Proof<CS>,
+    /// The accepted and rejected elements, to send to the client
+    pub index_map: BatchIndexMap,
+}
+
 /// Concrete type of [`EvaluationElement`]s returned by
 /// [`VoprfServer::batch_blind_evaluate_prepare`].
 pub type VoprfServerBatchEvaluatePreparedEvaluationElements<CS, I> = Map<
     Zip<I, Repeat<<<CS as CipherSuite>::Group as Group>::Scalar>>,
     fn(
         (
-            &BlindedElement<CS>,
+            &BlindedElement<CS, VoprfMode>,
             <<CS as CipherSuite>::Group as Group>::Scalar,
         ),
-    ) -> PreparedEvaluationElement<CS>,
+    ) -> PreparedEvaluationElement<CS, VoprfMode>,
 >;
 
 /// Concrete type of [`EvaluationElement`]s in
 /// [`VoprfServerBatchEvaluateFinishResult`].
 pub type VoprfServerBatchEvaluateFinishedMessages<'a, CS, I> = Map<
     <&'a I as IntoIterator>::IntoIter,
-    fn(&PreparedEvaluationElement<CS>) -> EvaluationElement<CS>,
+    fn(&PreparedEvaluationElement<CS, VoprfMode>) -> EvaluationElement<CS, VoprfMode>,
 >;
 
 /// Contains the fields that are returned by a verifiable server batch evaluate
@@ -443,7 +1332,7 @@ pub type VoprfServerBatchEvaluateFinishedMessages<'a, CS, I> = Map<
 #[derive_where(Debug; <&'a I as IntoIterator>::IntoIter, <CS::Group as Group>::Scalar)]
 pub struct VoprfServerBatchEvaluateFinishResult<'a, CS: 'a + CipherSuite, I>
 where
-    &'a I: IntoIterator<Item = &'a PreparedEvaluationElement<CS>>,
+    &'a I: IntoIterator<Item = &'a PreparedEvaluationElement<CS, VoprfMode>>,
 {
     /// The [`EvaluationElement`]s to send to the client
     pub messages: VoprfServerBatchEvaluateFinishedMessages<'a, CS, I>,
@@ -451,6 +1340,93 @@ where
     pub proof: Proof<CS>,
 }
 
+/// Concrete return type for [`VoprfServer::evaluate_many`].
+pub type VoprfServerEvaluateManyResult<'a, CS, I> = Map<
+    Zip<I, Repeat<&'a VoprfServer<CS>>>,
+    fn((<I as Iterator>::Item, &'a VoprfServer<CS>)) -> Result<OprfOutput<CS>>,
+>;
+
+/// Iterator returned by [`VoprfServer::batch_blind_evaluate_chunked`],
+/// yielding one [`VoprfServerBatchEvaluateResult`] per chunk.
+#[cfg(feature = "alloc")]
+#[derive_where(Debug; R, I, <CS::Group as Group>::Scalar, <CS::Group as Group>::Elem)]
+pub struct VoprfServerBatchEvaluateChunks<'a, CS: CipherSuite, R, I> {
+    server: &'a VoprfServer<CS>,
+    rng: &'a mut R,
+    blinded_elements: I,
+    chunk_size: usize,
+}
+
+#[cfg(feature = "alloc")]
+impl<
+        CS: CipherSuite,
+        R: TryRngCore + TryCryptoRng,
+        I: Iterator<Item = BlindedElement<CS, VoprfMode>>,
+    > Iterator for VoprfServerBatchEvaluateChunks<'_, CS, R, I>
+{
+    type Item = Result<VoprfServerBatchEvaluateResult<CS>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let blinded_elements = next_chunk(&mut self.blinded_elements, self.chunk_size)?;
+
+        Some(
+            self.server
+                .batch_blind_evaluate(self.rng, &blinded_elements),
+        )
+    }
+}
+
+/// Iterator returned by [`VoprfClient::batch_finalize_chunked`], yielding the
+/// outputs of each chunk.
+#[cfg(feature = "alloc")]
+#[derive_where(Debug; II, IC, IR, <CS::Group as Group>::Elem)]
+pub struct VoprfClientBatchFinalizeChunks<CS: CipherSuite, II, IC, IR> {
+    inputs: II,
+    clients: IC,
+    responses: IR,
+    pk: <CS::Group as Group>::Elem,
+    position: usize,
+}
+
+#[cfg(feature = "alloc")]
+impl<CS, I, II, IC, IR> Iterator for VoprfClientBatchFinalizeChunks<CS, II, IC, IR>
+where
+    CS: CipherSuite,
+    I: AsRef<[u8]>,
+    II: Iterator<Item = I>,
+    IC: Iterator<Item = VoprfClient<CS>>,
+    IR: Iterator<Item = VoprfServerBatchEvaluateResult<CS>>,
+{
+    type Item = Result<Vec<OprfOutput<CS>>, BatchError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let VoprfServerBatchEvaluateResult { messages, proof } = self.responses.next()?;
+        let inputs: Vec<_> = self.inputs.by_ref().take(messages.len()).collect();
+        let clients: Vec<_> = self.clients.by_ref().take(messages.len()).collect();
+        let position = self.position;
+        self.position += messages.len();
+
+        if inputs.len() != messages.len() || clients.len() != messages.len() {
+            return Some(Err(Error::Batch.into()));
+        }
+
+        Some(
+            VoprfClient::batch_finalize(&inputs, &clients, &messages, &proof, &PublicKey(self.pk))
+                .map_err(BatchError::from)
+                .and_then(|outputs| {
+                    outputs
+                        .map(|output| {
+                            output.map_err(|error| BatchError {
+                                index: error.index.map(|index| position + index),
+                                ..error
+                            })
+                        })
+                        .collect()
+                }),
+        )
+    }
+}
+
 /////////////////////
 // Inner functions //
 // =============== //
@@ -467,7 +1443,7 @@ type VoprfUnblindResult<'a, CS, IC, IM> = Map<
     fn(
         (
             <<CS as CipherSuite>::Group as Group>::Scalar,
-            &EvaluationElement<CS>,
+            &EvaluationElement<CS, VoprfMode>,
         ),
     ) -> <<CS as CipherSuite>::Group as Group>::Elem,
 >;
@@ -482,7 +1458,7 @@ fn verifiable_unblind<'a, CS: 'a + CipherSuite, IC, IM>(
 where
     &'a IC: 'a + IntoIterator<Item = &'a VoprfClient<CS>>,
     <&'a IC as IntoIterator>::IntoIter: ExactSizeIterator,
-    &'a IM: 'a + IntoIterator<Item = &'a EvaluationElement<CS>>,
+    &'a IM: 'a + IntoIterator<Item = &'a EvaluationElement<CS, VoprfMode>>,
     <&'a IM as IntoIterator>::IntoIter: ExactSizeIterator,
 {
     let g = CS::Group::base_elem();
@@ -509,11 +1485,13 @@ where
 }
 
 type FinalizeAfterUnblindResult<'a, C, I, IE> = Map<
-    IE,
-    fn((I, <<C as CipherSuite>::Group as Group>::Elem)) -> Result<Output<<C as CipherSuite>::Hash>>,
+    Enumerate<IE>,
+    fn(
+        (usize, (I, <<C as CipherSuite>::Group as Group>::Elem)),
+    ) -> Result<OprfOutput<C>, BatchError>,
 >;
 
-/// Returned values can only fail with [`Error::Input`].
+/// Returned values can only fail with [`Error::Input`] and their index.
 fn finalize_after_unblind<
     'a,
     CS: CipherSuite,
@@ -522,21 +1500,27 @@ fn finalize_after_unblind<
 >(
     inputs_and_unblinded_elements: IE,
 ) -> FinalizeAfterUnblindResult<'a, CS, I, IE> {
-    inputs_and_unblinded_elements.map(|(input, unblinded_element)| {
-        let elem_len = <CS::Group as Group>::ElemLen::U16.to_be_bytes();
-
-        // hashInput = I2OSP(len(input), 2) || input ||
-        //             I2OSP(len(unblindedElement), 2) || unblindedElement ||
-        //             "Finalize"
-        // return Hash(hashInput)
-        Ok(CS::Hash::new()
-            .chain_update(i2osp_2(input.as_ref().len()).map_err(|_| Error::Input)?)
-            .chain_update(input.as_ref())
-            .chain_update(elem_len)
-            .chain_update(CS::Group::serialize_elem(unblinded_element))
-            .chain_update(STR_FINALIZE)
-            .finalize())
-    })
+    inputs_and_unblinded_elements
+        .enumerate()
+        .map(|(index, (input, unblinded_element))| {
+            let elem_len = <CS::Group as Group>::ElemLen::U16.to_be_bytes();
+            let input_len = i2osp_2(input.as_ref().len())
+                .map_err(|_| BatchError::new(index, Error::input_too_long(input.as_ref().len())))?;
+
+            // hashInput = I2OSP(len(input), 2) || input ||
+            //             I2OSP(len(unblindedElement), 2) || unblindedElement ||
+            //             "Finalize"
+            // return Hash(hashInput)
+            Ok(OprfOutput(
+                CS::Hash::new()
+                    .chain_update(input_len)
+                    .chain_update(input.as_ref())
+                    .chain_update(elem_len)
+                    .chain_update(CS::Group::serialize_elem(unblinded_element))
+                    .chain_update(STR_FINALIZE)
+                    .finalize(),
+            ))
+        })
 }
 
 ///////////
@@ -548,19 +1532,26 @@ fn finalize_after_unblind<
 mod tests {
     use core::ptr;
 
-    use ::alloc::vec;
     use ::alloc::vec::Vec;
+    use ::alloc::{format, vec};
+    use core::ops::Add;
+
+    use generic_array::typenum::Sum;
+    use generic_array::ArrayLength;
     use rand::rngs::OsRng;
+    use subtle::ConstantTimeEq;
 
     use super::*;
     use crate::common::{Dst, STR_HASH_TO_GROUP};
-    use crate::Group;
+    use crate::serialization::{ProofLen, VerifiedEvaluationLen, VoprfClientLen};
+    use crate::tests::mock_rng::{CycleRng, FailingRng};
+    use crate::{DeserializationReason, Group};
 
     fn prf<CS: CipherSuite>(
         input: &[u8],
         key: <CS::Group as Group>::Scalar,
         mode: Mode,
-    ) -> Output<CS::Hash> {
+    ) -> OprfOutput<CS> {
         let dst = Dst::new::<CS, _, _>(STR_HASH_TO_GROUP, mode);
         let point = CS::Group::hash_to_curve::<CS::Hash>(&[input], &dst.as_dst()).unwrap();
 
@@ -584,10 +1575,10 @@ mod tests {
                 input,
                 &server_result.message,
                 &server_result.proof,
-                server.get_public_key(),
+                &server.get_public_key(),
             )
             .unwrap();
-        let res2 = prf::<CS>(input, server.get_private_key(), Mode::Voprf);
+        let res2 = prf::<CS>(input, server.get_private_key().0, Mode::Voprf);
         assert_eq!(client_finalize_result, res2);
     }
 
@@ -622,19 +1613,463 @@ mod tests {
             &client_states,
             &messages,
             &proof,
-            server.get_public_key(),
+            &server.get_public_key(),
         )
         .unwrap()
-        .collect::<Result<Vec<_>>>()
+        .collect::<Result<Vec<_>, _>>()
         .unwrap();
         let mut res2 = vec![];
         for input in inputs.iter().take(num_iterations) {
-            let output = prf::<CS>(input, server.get_private_key(), Mode::Voprf);
+            let output = prf::<CS>(input, server.get_private_key().0, Mode::Voprf);
             res2.push(output);
         }
         assert_eq!(client_finalize_result, res2);
     }
 
+    #[cfg(feature = "alloc")]
+    fn verifiable_filtered_retrieval<CS: CipherSuite>() {
+        let inputs = [b"input 1", b"input 2", b"input 3", b"input 4"];
+        let mut rng = OsRng;
+        let client_blind_results =
+            inputs.map(|input| VoprfClient::<CS>::blind(input, &mut rng).unwrap());
+        let clients = client_blind_results
+            .each_ref()
+            .map(|result| result.state.clone());
+        let client_messages = client_blind_results
+            .each_ref()
+            .map(|result| result.message.clone());
+        let server = VoprfServer::<CS>::new(&mut rng).unwrap();
+        let VoprfServerBatchEvaluateFilteredResult {
+            messages,
+            proof,
+            index_map,
+        } = server
+            .batch_blind_evaluate_filtered(&mut rng, &client_messages, |index, _| index != 1)
+            .unwrap();
+        assert_eq!(messages.len(), 3);
+        assert!(index_map.rejected().eq([1]));
+
+        // The index map can be sent to the client
+        let index_map = BatchIndexMap::deserialize(&index_map.serialize(), inputs.len()).unwrap();
+        assert_eq!(index_map.serialize(), [0b1101]);
+        let result = BatchIndexMap::deserialize(&[0b1_1101], inputs.len());
+        assert_eq!(
+            result.unwrap_err(),
+            Error::deserialization("index_map", DeserializationReason::Encoding)
+        );
+        let result = BatchIndexMap::deserialize(&[0b1101, 0], inputs.len());
+        assert_eq!(
+            result.unwrap_err(),
+            Error::deserialization(
+                "index_map",
+                DeserializationReason::Length {
+                    expected: 1,
+                    actual: 2
+                }
+            )
+        );
+        let outputs = VoprfClient::batch_finalize_filtered(
+            &inputs,
+            &clients,
+            &messages,
+            &proof,
+            &server.get_public_key(),
+            &index_map,
+        )
+        .unwrap();
+        assert_eq!(outputs.len(), inputs.len());
+        assert!(outputs[1].is_none());
+        for index in index_map.accepted() {
+            let res2 = prf::<CS>(inputs[index], server.get_private_key().0, Mode::Voprf);
+            assert_eq!(outputs[index], Some(Ok(res2)));
+        }
+
+        // The messages don't match an index map accepting all elements
+        let index_map = BatchIndexMap::from_iter([true; 4]);
+        let result = VoprfClient::batch_finalize_filtered(
+            &inputs,
+            &clients,
+            &messages,
+            &proof,
+            &server.get_public_key(),
+            &index_map,
+        );
+        assert_eq!(result.unwrap_err(), Error::Batch);
+
+        // Rejecting all elements fails
+        let result = server.batch_blind_evaluate_filtered(&mut rng, &client_messages, |_, _| false);
+        assert!(matches!(result, Err(Error::Batch)));
+    }
+
+    fn typed_keys<CS: CipherSuite>() {
+        let mut rng = OsRng;
+        let server = VoprfServer::<CS>::new(&mut rng).unwrap();
+        let private_key = server.get_private_key();
+        let public_key = server.get_public_key();
+        assert!(private_key.public_key() == public_key);
+        assert!(private_key.fingerprint() == public_key.fingerprint());
+
+        // Keys can be persisted and restored
+        let private_key = PrivateKey::<CS>::deserialize(&private_key.serialize()).unwrap();
+        let public_key = PublicKey::<CS>::deserialize(&public_key.serialize()).unwrap();
+        let restored = VoprfServer::new_with_private_key(&private_key);
+        assert!(restored.get_public_key() == public_key);
+        assert!(bool::from(restored.get_private_key().ct_eq(&private_key)));
+
+        let other = VoprfServer::<CS>::new(&mut rng).unwrap();
+        assert!(other.get_public_key() != public_key);
+        assert!(other.get_public_key().fingerprint() != public_key.fingerprint());
+    }
+
+    fn redacted_debug<CS: CipherSuite>()
+    where
+        <CS::Group as Group>::Scalar: Debug,
+        <CS::Group as Group>::Elem: Debug,
+    {
+        let mut rng = OsRng;
+        let server = VoprfServer::<CS>::new(&mut rng).unwrap();
+        let client = VoprfClient::<CS>::blind(b"input", &mut rng).unwrap().state;
+        let fingerprint = hex::encode(server.get_public_key().fingerprint());
+
+        // Secrets are redacted
+        assert_eq!(
+            format!("{server:?}"),
+            format!(
+                "VoprfServer {{ suite: {:?}, fingerprint: {fingerprint}, .. }}",
+                CS::ID
+            )
+        );
+        assert_eq!(
+            format!("{:?}", server.get_private_key()),
+            format!(
+                "PrivateKey {{ suite: {:?}, fingerprint: {fingerprint}, .. }}",
+                CS::ID
+            )
+        );
+        assert_eq!(
+            format!("{client:?}"),
+            format!("VoprfClient {{ suite: {:?}, .. }}", CS::ID)
+        );
+
+        // Unless exposed explicitly
+        #[cfg(feature = "danger")]
+        {
+            assert_eq!(
+                format!("{:?}", server.insecure_debug()),
+                format!("VoprfServer {{ sk: {:?}, pk: {:?} }}", server.sk, server.pk)
+            );
+            assert_eq!(
+                format!("{:?}", client.insecure_debug()),
+                format!(
+                    "VoprfClient {{ blind: {:?}, blinded_element: {:?} }}",
+                    client.blind, client.blinded_element
+                )
+            );
+        }
+    }
+
+    fn deserialization_errors<CS: CipherSuite>() {
+        let scalar_len = <CS::Group as Group>::ScalarLen::USIZE;
+
+        // Errors name the field which failed to deserialize
+        let result = VoprfServer::<CS>::deserialize(&[]);
+        assert_eq!(
+            result.err(),
+            Some(Error::deserialization(
+                "sk",
+                DeserializationReason::Length {
+                    expected: scalar_len,
+                    actual: 0
+                }
+            ))
+        );
+        let mut bytes =
+            CS::Group::serialize_scalar(CS::Group::random_scalar(&mut OsRng).unwrap()).to_vec();
+        bytes.push(0);
+        let result = Proof::<CS>::deserialize(&bytes);
+        assert_eq!(
+            result.err(),
+            Some(Error::deserialization(
+                "s_scalar",
+                DeserializationReason::Length {
+                    expected: scalar_len,
+                    actual: 1
+                }
+            ))
+        );
+        let result = Proof::<CS>::deserialize(&vec![0xFF; scalar_len * 2]);
+        assert_eq!(
+            result.err(),
+            Some(Error::deserialization(
+                "c_scalar",
+                DeserializationReason::Scalar
+            ))
+        );
+    }
+
+    fn verifiable_batch_errors<CS: CipherSuite>() {
+        let mut rng = OsRng;
+        let client_blind_results = [b"input 1", b"input 2", b"input 3"]
+            .map(|input| VoprfClient::<CS>::blind(input, &mut rng).unwrap());
+        let clients = client_blind_results
+            .each_ref()
+            .map(|result| result.state.clone());
+        let client_messages = client_blind_results
+            .each_ref()
+            .map(|result| result.message.clone());
+        let server = VoprfServer::<CS>::new(&mut rng).unwrap();
+        let prepared_evaluation_elements: Vec<_> = server
+            .batch_blind_evaluate_prepare(client_messages.iter())
+            .collect();
+        let VoprfServerBatchEvaluateFinishResult { messages, proof } = server
+            .batch_blind_evaluate_finish(
+                &mut rng,
+                client_messages.iter(),
+                &prepared_evaluation_elements,
+            )
+            .unwrap();
+        let messages: Vec<_> = messages.collect();
+        let long_input = vec![0; usize::from(u16::MAX) + 1];
+        let inputs = [b"input 1".as_slice(), &long_input, b""];
+
+        // Invalid inputs are reported with their index
+        let outputs: Vec<_> = VoprfClient::batch_finalize(
+            &inputs,
+            &clients,
+            &messages,
+            &proof,
+            &server.get_public_key(),
+        )
+        .unwrap()
+        .collect();
+        assert!(outputs[0].is_ok());
+        assert_eq!(
+            outputs[1],
+            Err(BatchError::new(1, Error::input_too_long(65536)))
+        );
+
+        // All inputs are checked before the proof
+        let result = VoprfClient::batch_finalize_checked(
+            &inputs,
+            &clients,
+            &messages,
+            &proof,
+            &PublicKey(CS::Group::base_elem()),
+        );
+        assert_eq!(
+            result.err(),
+            Some(BatchError::new(1, Error::input_too_long(65536)))
+        );
+        let inputs = [b"input 1".as_slice(), b"input 2", b"input 3"];
+        let result = VoprfClient::batch_finalize_checked(
+            &inputs,
+            &clients,
+            &messages,
+            &proof,
+            &PublicKey(CS::Group::base_elem()),
+        );
+        assert_eq!(result.err(), Some(Error::ProofVerification.into()));
+        let result = VoprfClient::batch_finalize_checked(
+            &inputs,
+            &clients,
+            &messages,
+            &proof,
+            &server.get_public_key(),
+        );
+        assert!(result.unwrap().all(|output| output.is_ok()));
+        let inputs = [b"input 1".as_slice(), b"input 2", b""];
+        let result = VoprfClient::batch_finalize_checked(
+            &inputs,
+            &clients,
+            &messages,
+            &proof,
+            &server.get_public_key(),
+        );
+        assert_eq!(
+            result.err(),
+            Some(BatchError::new(2, Error::Input(InputError::Empty)))
+        );
+    }
+
+    fn verifiable_split_finalize<CS: CipherSuite>()
+    where
+        VoprfClientLen<CS>: Add<<CS::Group as Group>::ElemLen>,
+        Sum<VoprfClientLen<CS>, <CS::Group as Group>::ElemLen>: ArrayLength + Add<ProofLen<CS>>,
+        Sum<Sum<VoprfClientLen<CS>, <CS::Group as Group>::ElemLen>, ProofLen<CS>>:
+            ArrayLength + Add<<CS::Group as Group>::ElemLen>,
+        VerifiedEvaluationLen<CS>: ArrayLength,
+    {
+        let inputs = [b"input 1", b"input 2"];
+        let mut rng = OsRng;
+        let client_blind_results =
+            inputs.map(|input| VoprfClient::<CS>::blind(input, &mut rng).unwrap());
+        let clients = client_blind_results
+            .each_ref()
+            .map(|result| result.state.clone());
+        let client_messages = client_blind_results
+            .each_ref()
+            .map(|result| result.message.clone());
+        let server = VoprfServer::<CS>::new(&mut rng).unwrap();
+        let prepared_evaluation_elements: Vec<_> = server
+            .batch_blind_evaluate_prepare(client_messages.iter())
+            .collect();
+        let VoprfServerBatchEvaluateFinishResult { messages, proof } = server
+            .batch_blind_evaluate_finish(
+                &mut rng,
+                client_messages.iter(),
+                &prepared_evaluation_elements,
+            )
+            .unwrap();
+        let messages: Vec<_> = messages.collect();
+
+        let verified_evaluations: Vec<_> =
+            VoprfClient::batch_verify(&clients, &messages, &proof, &server.get_public_key())
+                .unwrap()
+                .collect();
+
+        // Verified evaluations can be persisted and finalized later
+        for (input, verified_evaluation) in inputs.iter().zip(&verified_evaluations) {
+            #[cfg(feature = "danger")]
+            let verified_evaluation =
+                &VerifiedEvaluation::<CS>::deserialize(&verified_evaluation.serialize()).unwrap();
+            let output = VoprfClient::finalize_verified(*input, verified_evaluation).unwrap();
+            let res2 = prf::<CS>(*input, server.get_private_key().0, Mode::Voprf);
+            assert_eq!(output, res2);
+        }
+
+        // The proof of a batch is verified again when loading all of it, but
+        // doesn't cover a single evaluation of it.
+        let serialized: Vec<_> = verified_evaluations
+            .iter()
+            .map(VerifiedEvaluation::serialize)
+            .collect();
+        #[cfg(feature = "alloc")]
+        {
+            let loaded =
+                VerifiedEvaluation::<CS>::batch_deserialize_and_verify(&serialized).unwrap();
+            assert!(loaded
+                .iter()
+                .map(VerifiedEvaluation::serialize)
+                .eq(serialized.iter().cloned()));
+            let reversed = [&serialized[1], &serialized[0]];
+            assert_eq!(
+                VerifiedEvaluation::<CS>::batch_deserialize_and_verify(reversed).err(),
+                Some(Error::ProofVerification)
+            );
+            assert_eq!(
+                VerifiedEvaluation::<CS>::batch_deserialize_and_verify::<&[u8]>([]).err(),
+                Some(Error::Batch)
+            );
+        }
+        assert_eq!(
+            VerifiedEvaluation::<CS>::deserialize_and_verify(&serialized[0]).err(),
+            Some(Error::ProofVerification)
+        );
+
+        let server_result = server.blind_evaluate(&mut rng, &client_messages[0]);
+        let verified_evaluation = clients[0]
+            .verify(
+                &server_result.message,
+                &server_result.proof,
+                &server.get_public_key(),
+            )
+            .unwrap();
+        let loaded =
+            VerifiedEvaluation::<CS>::deserialize_and_verify(&verified_evaluation.serialize())
+                .unwrap();
+        assert_eq!(loaded.serialize(), verified_evaluation.serialize());
+        let output = VoprfClient::finalize_verified(inputs[0], &loaded).unwrap();
+        let res2 = prf::<CS>(inputs[0], server.get_private_key().0, Mode::Voprf);
+        assert_eq!(output, res2);
+
+        // A tampered transcript fails to load
+        let mut tampered = verified_evaluation.clone();
+        tampered.evaluation_element = tampered.evaluation_element + &CS::Group::base_elem();
+        assert_eq!(
+            VerifiedEvaluation::<CS>::deserialize_and_verify(&tampered.serialize()).err(),
+            Some(Error::ProofVerification)
+        );
+
+        // Verification fails with a wrong public key or mismatched messages
+        let result = clients[0].verify(
+            &server_result.message,
+            &server_result.proof,
+            &PublicKey(CS::Group::base_elem()),
+        );
+        assert!(matches!(result, Err(Error::ProofVerification)));
+        let result = clients[1].verify(
+            &server_result.message,
+            &server_result.proof,
+            &server.get_public_key(),
+        );
+        assert!(matches!(result, Err(Error::ProofVerification)));
+    }
+
+    #[cfg(feature = "alloc")]
+    fn verifiable_chunked_retrieval<CS: CipherSuite>() {
+        let mut rng = OsRng;
+        let mut inputs = vec![];
+        let mut client_states = vec![];
+        let mut client_messages = vec![];
+        let num_iterations = 10;
+        for _ in 0..num_iterations {
+            let mut input = [0u8; 32];
+            rng.try_fill_bytes(&mut input).unwrap();
+            let client_blind_result = VoprfClient::<CS>::blind(&input, &mut rng).unwrap();
+            inputs.push(input);
+            client_states.push(client_blind_result.state);
+            client_messages.push(client_blind_result.message);
+        }
+        let server = VoprfServer::<CS>::new(&mut rng).unwrap();
+        let mut server_rng = OsRng;
+        let responses = server
+            .batch_blind_evaluate_chunks_of(&mut server_rng, client_messages, 3)
+            .collect::<Result<Vec<_>>>()
+            .unwrap();
+        assert_eq!(responses.len(), 4);
+        assert_eq!(responses[3].messages.len(), 1);
+
+        let client_finalize_result = VoprfClient::batch_finalize_chunked(
+            &inputs,
+            client_states.iter().cloned(),
+            responses,
+            &server.get_public_key(),
+        )
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap()
+        .concat();
+        let mut res2 = vec![];
+        for input in inputs.iter().take(num_iterations) {
+            let output = prf::<CS>(input, server.get_private_key().0, Mode::Voprf);
+            res2.push(output);
+        }
+        assert_eq!(client_finalize_result, res2);
+
+        // Running out of client states fails the affected chunk only
+        let responses = server
+            .batch_blind_evaluate_chunks_of(
+                &mut server_rng,
+                client_states
+                    .iter()
+                    .map(|state| BlindedElement(state.blinded_element, PhantomData)),
+                3,
+            )
+            .collect::<Result<Vec<_>>>()
+            .unwrap();
+        let mut client_finalize_result = VoprfClient::batch_finalize_chunked(
+            &inputs,
+            client_states.iter().take(8).cloned(),
+            responses,
+            &server.get_public_key(),
+        );
+        assert!(client_finalize_result.next().unwrap().is_ok());
+        assert!(client_finalize_result.next().unwrap().is_ok());
+        assert_eq!(
+            client_finalize_result.next().unwrap().unwrap_err(),
+            Error::Batch.into()
+        );
+    }
+
     fn verifiable_batch_bad_public_key<CS: CipherSuite>() {
         let mut rng = OsRng;
         let mut inputs = vec![];
@@ -664,10 +2099,10 @@ mod tests {
         let wrong_pk = {
             let dst = Dst::new::<CS, _, _>(STR_HASH_TO_GROUP, Mode::Oprf);
             // Choose a group element that is unlikely to be the right public key
-            CS::Group::hash_to_curve::<CS::Hash>(&[b"msg"], &dst.as_dst()).unwrap()
+            PublicKey(CS::Group::hash_to_curve::<CS::Hash>(&[b"msg"], &dst.as_dst()).unwrap())
         };
         let client_finalize_result =
-            VoprfClient::batch_finalize(&inputs, &client_states, &messages, &proof, wrong_pk);
+            VoprfClient::batch_finalize(&inputs, &client_states, &messages, &proof, &wrong_pk);
         assert!(client_finalize_result.is_err());
     }
 
@@ -680,13 +2115,13 @@ mod tests {
         let wrong_pk = {
             let dst = Dst::new::<CS, _, _>(STR_HASH_TO_GROUP, Mode::Oprf);
             // Choose a group element that is unlikely to be the right public key
-            CS::Group::hash_to_curve::<CS::Hash>(&[b"msg"], &dst.as_dst()).unwrap()
+            PublicKey(CS::Group::hash_to_curve::<CS::Hash>(&[b"msg"], &dst.as_dst()).unwrap())
         };
         let client_finalize_result = client_blind_result.state.finalize(
             input,
             &server_result.message,
             &server_result.proof,
-            wrong_pk,
+            &wrong_pk,
         );
         assert!(client_finalize_result.is_err());
     }
@@ -704,7 +2139,7 @@ mod tests {
                 input,
                 &server_result.message,
                 &server_result.proof,
-                server.get_public_key(),
+                &server.get_public_key(),
             )
             .unwrap();
 
@@ -718,6 +2153,211 @@ mod tests {
         let wrong_input = b"wrong input";
         let server_evaluate = server.evaluate(wrong_input).unwrap();
         assert!(client_finalize != server_evaluate);
+
+        // We expect batched outputs to match the individual ones in order
+        let inputs = [input.as_slice(), wrong_input];
+        let server_evaluate_many = server
+            .evaluate_many(inputs)
+            .collect::<Result<Vec<_>>>()
+            .unwrap();
+        assert_eq!(server_evaluate_many, [client_finalize, server_evaluate]);
+    }
+
+    fn verifiable_evaluate_with_proof<CS: CipherSuite>() {
+        let input = b"input";
+        let mut rng = OsRng;
+        let server = VoprfServer::<CS>::new(&mut rng).unwrap();
+        let VoprfServerEvaluateWithProofResult {
+            output,
+            element,
+            proof,
+        } = server.evaluate_with_proof(&mut rng, input).unwrap();
+
+        // The output matches the plain server evaluation and can be verified
+        assert_eq!(output, server.evaluate(input).unwrap());
+        let verified_output =
+            verify_evaluation(&server.get_public_key(), input, &element, &proof).unwrap();
+        assert_eq!(output, verified_output);
+
+        // Verification fails for a different input or public key
+        let result = verify_evaluation(&server.get_public_key(), b"wrong input", &element, &proof);
+        assert!(matches!(result, Err(Error::ProofVerification)));
+        let wrong_pk = PublicKey(CS::Group::base_elem());
+        let result = verify_evaluation(&wrong_pk, input, &element, &proof);
+        assert!(matches!(result, Err(Error::ProofVerification)));
+    }
+
+    fn hedged_proof_nonce<CS: CipherSuite>() {
+        let mut rng = OsRng;
+        let server = VoprfServer::<CS>::new(&mut rng).unwrap();
+        let blinded_elements = [b"input 1", b"input 2"]
+            .map(|input| VoprfClient::<CS>::blind(input, &mut rng).unwrap().message);
+
+        // With the same nonce in two proofs, `s1 - s2 = (c2 - c1) * sk`
+        let recover_key = |proofs: [Proof<CS>; 2]| {
+            (proofs[0].s_scalar - &proofs[1].s_scalar)
+                * &CS::Group::invert_scalar(proofs[1].c_scalar - &proofs[0].c_scalar)
+        };
+
+        let proof_random_scalar = CS::Group::random_scalar(&mut rng).unwrap();
+        let proofs = blinded_elements.each_ref().map(|blinded_element| {
+            let prepared = [server
+                .batch_blind_evaluate_prepare(iter::once(blinded_element))
+                .next()
+                .unwrap()];
+            server
+                .batch_blind_evaluate_finish_unchecked(
+                    proof_random_scalar,
+                    iter::once(blinded_element),
+                    &prepared,
+                )
+                .unwrap()
+                .proof
+        });
+        assert!(bool::from(
+            recover_key(proofs).ct_eq(&server.get_private_key().0)
+        ));
+
+        // A broken RNG repeating its output doesn't repeat the nonce
+        let mut broken_rng = CycleRng::new(vec![0; 64]);
+        let proofs = blinded_elements.each_ref().map(|blinded_element| {
+            server
+                .blind_evaluate(&mut broken_rng, blinded_element)
+                .proof
+        });
+        assert!(!bool::from(
+            recover_key(proofs).ct_eq(&server.get_private_key().0)
+        ));
+    }
+
+    fn deterministic_proof<CS: CipherSuite>() {
+        let input = b"input";
+        let mut rng = OsRng;
+        let client_blind_result = VoprfClient::<CS>::blind(input, &mut rng).unwrap();
+        let server = VoprfServer::<CS>::new(&mut rng).unwrap();
+
+        let server_result = server.blind_evaluate_deterministic(&client_blind_result.message);
+        let repeated_result = server.blind_evaluate_deterministic(&client_blind_result.message);
+        assert_eq!(
+            server_result.proof.serialize(),
+            repeated_result.proof.serialize()
+        );
+
+        // Hedged proofs differ between evaluations
+        let hedged_result = server.blind_evaluate(&mut rng, &client_blind_result.message);
+        assert!(server_result.proof.serialize() != hedged_result.proof.serialize());
+
+        // A failing RNG falls back to the deterministic proof
+        let fallback_result = server.blind_evaluate(&mut FailingRng, &client_blind_result.message);
+        assert_eq!(
+            server_result.proof.serialize(),
+            fallback_result.proof.serialize()
+        );
+
+        let client_finalize_result = client_blind_result
+            .state
+            .finalize(
+                input,
+                &server_result.message,
+                &server_result.proof,
+                &server.get_public_key(),
+            )
+            .unwrap();
+        let res2 = prf::<CS>(input, server.get_private_key().0, Mode::Voprf);
+        assert_eq!(client_finalize_result, res2);
+    }
+
+    #[cfg(feature = "alloc")]
+    fn verifiable_server_response<CS: CipherSuite>() {
+        let mut rng = OsRng;
+        let server = VoprfServer::<CS>::new(&mut rng).unwrap();
+        let pk = &server.get_public_key();
+        let batches = [[b"input 1", b"input 2"], [b"input 3", b"input 4"]].map(|inputs| {
+            inputs.map(|input| VoprfClient::<CS>::blind(input, &mut rng).unwrap().message)
+        });
+        let results = batches
+            .each_ref()
+            .map(|batch| server.batch_blind_evaluate(&mut rng, batch).unwrap());
+
+        // An auditor only needs the exchanged messages and the public key
+        VoprfServer::<CS>::verify_server_response(
+            pk,
+            &batches[0],
+            &results[0].messages,
+            &results[0].proof,
+        )
+        .unwrap();
+        let result = VoprfServer::<CS>::verify_server_response(
+            &PublicKey(CS::Group::base_elem()),
+            &batches[0],
+            &results[0].messages,
+            &results[0].proof,
+        );
+        assert!(matches!(result, Err(Error::ProofVerification)));
+        let result = VoprfServer::<CS>::verify_server_response(
+            pk,
+            &batches[1],
+            &results[0].messages,
+            &results[0].proof,
+        );
+        assert!(matches!(result, Err(Error::ProofVerification)));
+        let result = VoprfServer::<CS>::verify_server_response(
+            pk,
+            &batches[0][..1],
+            &results[0].messages,
+            &results[0].proof,
+        );
+        assert!(matches!(result, Err(Error::Batch)));
+
+        let responses = batches
+            .iter()
+            .zip(&results)
+            .map(|(batch, result)| (batch.as_slice(), result.messages.as_slice(), &result.proof));
+        VoprfServer::<CS>::verify_server_responses(pk, responses).unwrap();
+
+        // The index of the first failing batch is reported
+        let responses = [
+            (
+                batches[0].as_slice(),
+                results[0].messages.as_slice(),
+                &results[0].proof,
+            ),
+            (
+                batches[0].as_slice(),
+                results[1].messages.as_slice(),
+                &results[1].proof,
+            ),
+        ];
+        let result = VoprfServer::<CS>::verify_server_responses(pk, responses);
+        assert!(matches!(
+            result,
+            Err(BatchError {
+                index: Some(1),
+                error: Error::ProofVerification
+            })
+        ));
+    }
+
+    #[cfg(feature = "rayon")]
+    fn par_server_evaluate<CS: CipherSuite>()
+    where
+        VoprfServer<CS>: Sync,
+        <CS::Group as Group>::Scalar: Send + Sync,
+    {
+        let inputs = [b"input 1".as_slice(), b"input 2", b"input 3"];
+        let mut rng = OsRng;
+        let server = VoprfServer::<CS>::new(&mut rng).unwrap();
+
+        let server_evaluate_many = server
+            .evaluate_many(inputs)
+            .collect::<Result<Vec<_>>>()
+            .unwrap();
+        let par_server_evaluate_many = server
+            .par_evaluate_many(inputs)
+            .into_iter()
+            .collect::<Result<Vec<_>>>()
+            .unwrap();
+        assert_eq!(server_evaluate_many, par_server_evaluate_many);
     }
 
     fn zeroize_voprf_client<CS: CipherSuite>() {
@@ -766,9 +2406,25 @@ mod tests {
 
             verifiable_retrieval::<Ristretto255>();
             verifiable_batch_retrieval::<Ristretto255>();
+            verifiable_split_finalize::<Ristretto255>();
+            verifiable_batch_errors::<Ristretto255>();
+            deserialization_errors::<Ristretto255>();
+            typed_keys::<Ristretto255>();
+            redacted_debug::<Ristretto255>();
+            #[cfg(feature = "alloc")]
+            verifiable_filtered_retrieval::<Ristretto255>();
+            #[cfg(feature = "alloc")]
+            verifiable_chunked_retrieval::<Ristretto255>();
             verifiable_bad_public_key::<Ristretto255>();
             verifiable_batch_bad_public_key::<Ristretto255>();
             verifiable_server_evaluate::<Ristretto255>();
+            verifiable_evaluate_with_proof::<Ristretto255>();
+            hedged_proof_nonce::<Ristretto255>();
+            deterministic_proof::<Ristretto255>();
+            #[cfg(feature = "alloc")]
+            verifiable_server_response::<Ristretto255>();
+            #[cfg(feature = "rayon")]
+            par_server_evaluate::<Ristretto255>();
 
             zeroize_voprf_client::<Ristretto255>();
             zeroize_voprf_server::<Ristretto255>();
@@ -776,27 +2432,75 @@ mod tests {
 
         verifiable_retrieval::<NistP256>();
         verifiable_batch_retrieval::<NistP256>();
+        verifiable_split_finalize::<NistP256>();
+        verifiable_batch_errors::<NistP256>();
+        deserialization_errors::<NistP256>();
+        typed_keys::<NistP256>();
+        redacted_debug::<NistP256>();
+        #[cfg(feature = "alloc")]
+        verifiable_filtered_retrieval::<NistP256>();
+        #[cfg(feature = "alloc")]
+        verifiable_chunked_retrieval::<NistP256>();
         verifiable_bad_public_key::<NistP256>();
         verifiable_batch_bad_public_key::<NistP256>();
         verifiable_server_evaluate::<NistP256>();
+        verifiable_evaluate_with_proof::<NistP256>();
+        hedged_proof_nonce::<NistP256>();
+        deterministic_proof::<NistP256>();
+        #[cfg(feature = "alloc")]
+        verifiable_server_response::<NistP256>();
+        #[cfg(feature = "rayon")]
+        par_server_evaluate::<NistP256>();
 
         zeroize_voprf_client::<NistP256>();
         zeroize_voprf_server::<NistP256>();
 
         verifiable_retrieval::<NistP384>();
         verifiable_batch_retrieval::<NistP384>();
+        verifiable_split_finalize::<NistP384>();
+        verifiable_batch_errors::<NistP384>();
+        deserialization_errors::<NistP384>();
+        typed_keys::<NistP384>();
+        redacted_debug::<NistP384>();
+        #[cfg(feature = "alloc")]
+        verifiable_filtered_retrieval::<NistP384>();
+        #[cfg(feature = "alloc")]
+        verifiable_chunked_retrieval::<NistP384>();
         verifiable_bad_public_key::<NistP384>();
         verifiable_batch_bad_public_key::<NistP384>();
         verifiable_server_evaluate::<NistP384>();
+        verifiable_evaluate_with_proof::<NistP384>();
+        hedged_proof_nonce::<NistP384>();
+        deterministic_proof::<NistP384>();
+        #[cfg(feature = "alloc")]
+        verifiable_server_response::<NistP384>();
+        #[cfg(feature = "rayon")]
+        par_server_evaluate::<NistP384>();
 
         zeroize_voprf_client::<NistP384>();
         zeroize_voprf_server::<NistP384>();
 
         verifiable_retrieval::<NistP521>();
         verifiable_batch_retrieval::<NistP521>();
+        verifiable_split_finalize::<NistP521>();
+        verifiable_batch_errors::<NistP521>();
+        deserialization_errors::<NistP521>();
+        typed_keys::<NistP521>();
+        redacted_debug::<NistP521>();
+        #[cfg(feature = "alloc")]
+        verifiable_filtered_retrieval::<NistP521>();
+        #[cfg(feature = "alloc")]
+        verifiable_chunked_retrieval::<NistP521>();
         verifiable_bad_public_key::<NistP521>();
         verifiable_batch_bad_public_key::<NistP521>();
         verifiable_server_evaluate::<NistP521>();
+        verifiable_evaluate_with_proof::<NistP521>();
+        hedged_proof_nonce::<NistP521>();
+        deterministic_proof::<NistP521>();
+        #[cfg(feature = "alloc")]
+        verifiable_server_response::<NistP521>();
+        #[cfg(feature = "rayon")]
+        par_server_evaluate::<NistP521>();
 
         zeroize_voprf_client::<NistP521>();
         zeroize_voprf_server::<NistP521>();